@@ -144,4 +144,36 @@ pub fn ecdsa_p384_verify(
     }
 }
 
+pub closed spec fn spec_ecdsa_p521_verify(
+    alg: SpecAlgorithmIdentifierValue,
+    pub_key: Seq<u8>,
+    sig: Seq<u8>,
+    msg: Seq<u8>,
+) -> bool;
+
+/// Verify ECDSA P-521 signature with SHA-512 through AWS-LC
+///
+/// NOTE: AWS-LC does not implement the brainpool curves,
+/// so there are no corresponding verifiers for them
+#[verifier::external_body]
+pub fn ecdsa_p521_verify(
+    alg: &AlgorithmIdentifierValue,
+    pub_key: &[u8],
+    sig: &[u8],
+    msg: &[u8],
+) -> (res: Result<(), ECDSAError>)
+    ensures
+        res.is_ok() == spec_ecdsa_p521_verify(alg@, pub_key@, sig@, msg@),
+{
+    if !alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA512)) {
+        return Err(ECDSAError::UnsupportedAlgorithm);
+    }
+
+    if aws_lc_rs::signature::ECDSA_P521_SHA512_ASN1.verify_sig(pub_key, msg, sig).is_ok() {
+        Ok(())
+    } else {
+        Err(ECDSAError::VerificationFailed)
+    }
+}
+
 }
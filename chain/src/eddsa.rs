@@ -0,0 +1,49 @@
+/// Wrapper for EdDSA verification
+
+use vstd::prelude::*;
+
+use aws_lc_rs::signature::VerificationAlgorithm;
+
+use parser::PolyfillEq;
+use parser::x509::*;
+
+verus! {
+
+pub enum EdDSAError {
+    UnsupportedAlgorithm,
+    VerificationFailed,
+}
+
+pub closed spec fn spec_ed25519_verify(
+    alg: SpecAlgorithmIdentifierValue,
+    pub_key: Seq<u8>,
+    sig: Seq<u8>,
+    msg: Seq<u8>,
+) -> bool;
+
+/// Verify Ed25519 signature (RFC 8032) through AWS-LC
+///
+/// `pub_key` is the raw 32-byte public key and `sig` is the raw
+/// 64-byte signature, as encoded in X.509 (RFC 8410)
+#[verifier::external_body]
+pub fn ed25519_verify(
+    alg: &AlgorithmIdentifierValue,
+    pub_key: &[u8],
+    sig: &[u8],
+    msg: &[u8],
+) -> (res: Result<(), EdDSAError>)
+    ensures
+        res.is_ok() == spec_ed25519_verify(alg@, pub_key@, sig@, msg@),
+{
+    if !alg.id.polyfill_eq(&oid!(ED25519)) {
+        return Err(EdDSAError::UnsupportedAlgorithm);
+    }
+
+    if aws_lc_rs::signature::ED25519.verify_sig(pub_key, msg, sig).is_ok() {
+        Ok(())
+    } else {
+        Err(EdDSAError::VerificationFailed)
+    }
+}
+
+}
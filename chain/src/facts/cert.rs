@@ -81,7 +81,17 @@ impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for BasicFacts {
 
                 spec_fact!("version", t.spec_cert(), spec_int!(t.x.cert.version as int)),
 
+                spec_fact!("serialNumber", t.spec_cert(), spec_str!(BigIntValue::spec_to_hex(t.x.cert.serial))),
+
                 spec_fact!("signatureAlgorithm", t.spec_cert(), spec_str!(Self::spec_oid_to_string(t.x.sig_alg.id))),
+
+                spec_fact!("weakSignatureAlgorithm",
+                    t.spec_cert(),
+                    match Self::spec_weak_sig_alg_name(t.x.sig_alg.param) {
+                        Some(name) => spec_atom!(name),
+                        None => spec_atom!("na".view()),
+                    },
+                ),
             ])
         }
     }
@@ -95,10 +105,23 @@ impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for BasicFacts {
 
             RuleX::fact("version", vec![ t.cert(), TermX::int(t.x.get().cert.get().version) ]),
 
+            RuleX::fact("serialNumber", vec![
+                t.cert(),
+                TermX::str(t.x.get().cert.get().serial.to_hex().as_str()),
+            ]),
+
             RuleX::fact("signatureAlgorithm", vec![
                 t.cert(),
                 TermX::str(Self::oid_to_string(&t.x.get().sig_alg.id).as_str()),
             ]),
+
+            RuleX::fact("weakSignatureAlgorithm", vec![
+                t.cert(),
+                match Self::weak_sig_alg_name(&t.x.get().sig_alg.param) {
+                    Some(name) => TermX::atom(name),
+                    None => TermX::atom("na"),
+                },
+            ]),
         ]);
         Ok(())
     }
@@ -268,22 +291,49 @@ impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for SubjectPKIFacts {
                 ),
             };
 
-            // Fact about RSA modulus length
-            let Some(rsa_fact) = match t.x.cert.subject_key.alg.param {
+            // Facts about RSA modulus length and public exponent
+            let Some((rsa_fact, rsa_exp_fact)) = match t.x.cert.subject_key.alg.param {
                 SpecAlgorithmParamValue::RSAEncryption(..) => {
-                    // Parse the public key field to get the modulus length
+                    // Parse the public key field to get the modulus and exponent
                     let pub_key = BitStringValue::spec_bytes(t.x.cert.subject_key.pub_key);
 
                     if_let! {
                         let Ok((_, parsed)) = ASN1(RSAParam).view().spec_parse(pub_key);
-                        Some(spec_fact!("spkiRSAModLength", t.spec_cert(), spec_int!((parsed.modulus.len() - 1) as usize * 8)))
+                        let modulus = BigIntValue::spec_to_int(parsed.modulus);
+                        let exponent = BigIntValue::spec_to_int(parsed.exponent);
+
+                        if modulus >= 0 &&
+                           BigIntValue::spec_bit_len(modulus as nat) <= LiteralInt::MAX &&
+                           LiteralInt::MIN <= exponent <= LiteralInt::MAX {
+                            Some((
+                                spec_fact!("spkiRSAModLength", t.spec_cert(), spec_int!(BigIntValue::spec_bit_len(modulus as nat) as int)),
+                                spec_fact!("rsaExponent", t.spec_cert(), spec_int!(exponent)),
+                            ))
+                        } else {
+                            None
+                        }
+                    }
+                }
+
+                _ => Some((
+                    spec_fact!("spkiRSAModLength", t.spec_cert(), spec_atom!("na".view())),
+                    spec_fact!("rsaExponent", t.spec_cert(), spec_atom!("na".view())),
+                )),
+            };
+
+            // Fact about the named curve of an EC key
+            let ec_fact = match t.x.cert.subject_key.alg.param {
+                SpecAlgorithmParamValue::ECPublicKey(curve) => {
+                    match Self::spec_ec_curve_name(curve) {
+                        Some(name) => spec_fact!("spkiECCurve", t.spec_cert(), spec_atom!(name)),
+                        None => spec_fact!("spkiECCurve", t.spec_cert(), spec_str!(BasicFacts::spec_oid_to_string(curve))),
                     }
                 }
 
-                _ => Some(spec_fact!("spkiRSAModLength", t.spec_cert(), spec_atom!("na".view()))),
+                _ => spec_fact!("spkiECCurve", t.spec_cert(), spec_atom!("na".view())),
             };
 
-            Some(seq![dsa_fact, rsa_fact])
+            Some(seq![dsa_fact, rsa_fact, rsa_exp_fact, ec_fact])
         }
     }
 
@@ -316,7 +366,7 @@ impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for SubjectPKIFacts {
             ]),
         };
 
-        let rsa_fact = match &t.x.get().cert.get().subject_key.alg.param {
+        let (rsa_fact, rsa_exp_fact) = match &t.x.get().cert.get().subject_key.alg.param {
             AlgorithmParamValue::RSAEncryption(..) => {
                 let pub_key = t.x.get().cert.get().subject_key.pub_key.bytes();
                 let parsed = match ASN1(RSAParam).parse(pub_key) {
@@ -324,27 +374,87 @@ impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for SubjectPKIFacts {
                     Err(_) => return Err(ValidationError::RSAPubKeyParseError),
                 };
 
-                let mod_len = parsed.modulus.byte_len();
+                let mod_len = match parsed.modulus.bit_len() {
+                    Some(mod_len) if mod_len <= LiteralInt::MAX as usize => mod_len,
+                    _ => return Err(ValidationError::IntegerOverflow),
+                };
 
-                if mod_len > LiteralInt::MAX as usize / 8 {
-                    return Err(ValidationError::IntegerOverflow);
-                }
+                let exponent = match parsed.exponent.to_i128() {
+                    Some(e) if LiteralInt::MIN as i128 <= e && e <= LiteralInt::MAX as i128 => e,
+                    _ => return Err(ValidationError::IntegerOverflow),
+                };
 
-                RuleX::fact("spkiRSAModLength", vec![
-                    t.cert(),
-                    TermX::int((mod_len * 8) as LiteralInt),
-                ])
+                (
+                    RuleX::fact("spkiRSAModLength", vec![ t.cert(), TermX::int(mod_len as LiteralInt) ]),
+                    RuleX::fact("rsaExponent", vec![ t.cert(), TermX::int(exponent as LiteralInt) ]),
+                )
+            }
+
+            _ => (
+                RuleX::fact("spkiRSAModLength", vec![ t.cert(), TermX::atom("na") ]),
+                RuleX::fact("rsaExponent", vec![ t.cert(), TermX::atom("na") ]),
+            ),
+        };
+
+        let ec_fact = match &t.x.get().cert.get().subject_key.alg.param {
+            AlgorithmParamValue::ECPublicKey(curve) => {
+                match Self::ec_curve_name(curve) {
+                    Some(name) => RuleX::fact("spkiECCurve", vec![ t.cert(), TermX::atom(name) ]),
+                    None => RuleX::fact("spkiECCurve", vec![
+                        t.cert(),
+                        TermX::str(BasicFacts::oid_to_string(curve).as_str()),
+                    ]),
+                }
             }
 
-            _ => RuleX::fact("spkiRSAModLength", vec![ t.cert(), TermX::atom("na") ]),
+            _ => RuleX::fact("spkiECCurve", vec![ t.cert(), TermX::atom("na") ]),
         };
 
         out.push(dsa_fact);
         out.push(rsa_fact);
+        out.push(rsa_exp_fact);
+        out.push(ec_fact);
         Ok(())
     }
 }
 
+impl SubjectPKIFacts {
+    /// Short names of the known named curves (RFC 5480 and RFC 5639);
+    /// other curves are reported with their dotted OID string instead
+    pub closed spec fn spec_ec_curve_name(oid: SpecObjectIdentifierValue) -> Option<Seq<char>>
+    {
+        if oid == spec_oid!(EC_P_256) { Some("p256"@) }
+        else if oid == spec_oid!(EC_P_384) { Some("p384"@) }
+        else if oid == spec_oid!(EC_P_521) { Some("p521"@) }
+        else if oid == spec_oid!(EC_BRAINPOOL_P256R1) { Some("brainpoolP256r1"@) }
+        else if oid == spec_oid!(EC_BRAINPOOL_P384R1) { Some("brainpoolP384r1"@) }
+        else if oid == spec_oid!(EC_BRAINPOOL_P512R1) { Some("brainpoolP512r1"@) }
+        else { None }
+    }
+
+    /// Exec version of spec_ec_curve_name
+    pub fn ec_curve_name(oid: &ObjectIdentifierValue) -> (res: Option<&'static str>)
+        ensures
+            res matches Some(name) ==> Self::spec_ec_curve_name(oid@) == Some(name@),
+            res is None ==> Self::spec_ec_curve_name(oid@) is None,
+    {
+        let id = oid!(EC_P_256); assert(id@ == spec_oid!(EC_P_256));
+        let id = oid!(EC_P_384); assert(id@ == spec_oid!(EC_P_384));
+        let id = oid!(EC_P_521); assert(id@ == spec_oid!(EC_P_521));
+        let id = oid!(EC_BRAINPOOL_P256R1); assert(id@ == spec_oid!(EC_BRAINPOOL_P256R1));
+        let id = oid!(EC_BRAINPOOL_P384R1); assert(id@ == spec_oid!(EC_BRAINPOOL_P384R1));
+        let id = oid!(EC_BRAINPOOL_P512R1); assert(id@ == spec_oid!(EC_BRAINPOOL_P512R1));
+
+        if oid.polyfill_eq(&oid!(EC_P_256)) { Some("p256") }
+        else if oid.polyfill_eq(&oid!(EC_P_384)) { Some("p384") }
+        else if oid.polyfill_eq(&oid!(EC_P_521)) { Some("p521") }
+        else if oid.polyfill_eq(&oid!(EC_BRAINPOOL_P256R1)) { Some("brainpoolP256r1") }
+        else if oid.polyfill_eq(&oid!(EC_BRAINPOOL_P384R1)) { Some("brainpoolP384r1") }
+        else if oid.polyfill_eq(&oid!(EC_BRAINPOOL_P512R1)) { Some("brainpoolP512r1") }
+        else { None }
+    }
+}
+
 impl BasicFacts {
     pub closed spec fn spec_oid_to_string(oid: SpecObjectIdentifierValue) -> Seq<char>
     {
@@ -364,6 +474,32 @@ impl BasicFacts {
 
         join_strings(&strings, ".")
     }
+
+    /// Names of signature algorithms that are only parsed for
+    /// legacy PKIs, so that the policy can decide whether to accept them
+    pub closed spec fn spec_weak_sig_alg_name(param: SpecAlgorithmParamValue) -> Option<Seq<char>>
+    {
+        match param {
+            SpecAlgorithmParamValue::RSASignatureWithMD2(..) => Some("md2WithRSAEncryption"@),
+            SpecAlgorithmParamValue::RSASignatureWithMD5(..) => Some("md5WithRSAEncryption"@),
+            SpecAlgorithmParamValue::RSASignatureWithSHA1(..) => Some("sha1WithRSAEncryption"@),
+            _ => None,
+        }
+    }
+
+    /// Exec version of spec_weak_sig_alg_name
+    pub fn weak_sig_alg_name(param: &AlgorithmParamValue) -> (res: Option<&'static str>)
+        ensures
+            res matches Some(name) ==> Self::spec_weak_sig_alg_name(param@) == Some(name@),
+            res is None ==> Self::spec_weak_sig_alg_name(param@) is None,
+    {
+        match param {
+            AlgorithmParamValue::RSASignatureWithMD2(..) => Some("md2WithRSAEncryption"),
+            AlgorithmParamValue::RSASignatureWithMD5(..) => Some("md5WithRSAEncryption"),
+            AlgorithmParamValue::RSASignatureWithSHA1(..) => Some("sha1WithRSAEncryption"),
+            _ => None,
+        }
+    }
 }
 
 impl TimeFacts {
@@ -421,15 +557,16 @@ impl SubjectNameFacts {
             SpecDirectoryStringValue::PrintableString(s) => Some(s),
             SpecDirectoryStringValue::UTF8String(s) => Some(s),
             SpecDirectoryStringValue::IA5String(s) => Some(s),
-            SpecDirectoryStringValue::TeletexString(s) => None,
-            SpecDirectoryStringValue::UniversalString(s) => None,
-            SpecDirectoryStringValue::BMPString(s) => None,
+            SpecDirectoryStringValue::TeletexString(s) => Some(s),
+            SpecDirectoryStringValue::UniversalString(s) => Some(s),
+            SpecDirectoryStringValue::BMPString(s) => Some(s),
+            SpecDirectoryStringValue::NumericString(s) => Some(s),
             SpecDirectoryStringValue::Unreachable => None,
         }
     }
 
     /// Exec version of spec_dir_string_to_string
-    pub fn dir_string_to_string<'a, 'b>(dir: &'b DirectoryStringValue<'a>) -> (res: Option<&'a str>)
+    pub fn dir_string_to_string<'a, 'b>(dir: &'b DirectoryStringValue<'a>) -> (res: Option<&'b str>)
         ensures
             res matches Some(res) ==> Self::spec_dir_string_to_string(dir@) == Some(res@),
             res.is_none() ==> Self::spec_dir_string_to_string(dir@).is_none(),
@@ -438,9 +575,10 @@ impl SubjectNameFacts {
             DirectoryStringValue::PrintableString(s) => Some(s),
             DirectoryStringValue::UTF8String(s) => Some(s),
             DirectoryStringValue::IA5String(s) => Some(s),
-            DirectoryStringValue::TeletexString(s) => None,
-            DirectoryStringValue::UniversalString(s) => None,
-            DirectoryStringValue::BMPString(s) => None,
+            DirectoryStringValue::TeletexString(s) => Some(s.as_str()),
+            DirectoryStringValue::UniversalString(s) => Some(s.as_str()),
+            DirectoryStringValue::BMPString(s) => Some(s.as_str()),
+            DirectoryStringValue::NumericString(s) => Some(s),
             DirectoryStringValue::Unreachable => None,
         }
     }
@@ -461,7 +599,7 @@ impl SubjectNameFacts {
     }
 
     /// Exec version of spec_get_rdn
-    pub fn get_rdn<'a, 'b>(name: &'b NameValue<'a>, oid: &'b ObjectIdentifierValue) -> (res: Option<&'a str>)
+    pub fn get_rdn<'a, 'b, 'c>(name: &'b NameValue<'a>, oid: &'c ObjectIdentifierValue) -> (res: Option<&'b str>)
         ensures
             res matches Some(res) ==> Self::spec_get_rdn(name@, oid@) == Some(res@),
             res.is_none() ==> Self::spec_get_rdn(name@, oid@).is_none(),
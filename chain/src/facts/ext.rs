@@ -4,6 +4,7 @@ use polyfill::*;
 use vpl::*;
 use parser::{*, x509::*, asn1::*};
 
+use crate::hash;
 use crate::validate::*;
 use crate::specs::*;
 use crate::error::*;
@@ -19,16 +20,40 @@ pub type ExtensionFacts = seq_facts![
     ExtBasicConstraintsFacts,
     ExtKeyUsageFacts,
     ExtSubjectAltNameFacts,
+    ExtIssuerAltNameFacts,
+    ExtSubjectDirectoryAttributesFacts,
+    ExtPrivateKeyUsagePeriodFacts,
     ExtNameConstraintsFacts,
     ExtCertificatePoliciesFacts,
     ExtExtendedKeyUsageFacts,
+    ExtCRLDistributionPointsFacts,
+    ExtAuthorityInfoAccessFacts,
+    ExtPolicyMappingsFacts,
+    ExtPolicyConstraintsFacts,
+    ExtInhibitAnyPolicyFacts,
+    ExtSignedCertTimestampsFacts,
+    ExtQCStatementsFacts,
+    ExtTLSFeatureFacts,
+    ExtCTPoisonFacts,
 ];
 pub struct ExtBasicConstraintsFacts;
 pub struct ExtKeyUsageFacts;
 pub struct ExtSubjectAltNameFacts;
+pub struct ExtIssuerAltNameFacts;
+pub struct ExtSubjectDirectoryAttributesFacts;
+pub struct ExtPrivateKeyUsagePeriodFacts;
 pub struct ExtNameConstraintsFacts;
 pub struct ExtCertificatePoliciesFacts;
 pub struct ExtExtendedKeyUsageFacts;
+pub struct ExtCRLDistributionPointsFacts;
+pub struct ExtAuthorityInfoAccessFacts;
+pub struct ExtPolicyMappingsFacts;
+pub struct ExtPolicyConstraintsFacts;
+pub struct ExtInhibitAnyPolicyFacts;
+pub struct ExtSignedCertTimestampsFacts;
+pub struct ExtQCStatementsFacts;
+pub struct ExtTLSFeatureFacts;
+pub struct ExtCTPoisonFacts;
 
 impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtExtendedKeyUsageFacts {
     closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
@@ -107,6 +132,110 @@ impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtExtendedKeyUsag
     }
 }
 
+impl ExtCertificatePoliciesFacts {
+    /// Convert a DisplayText to a term, if it is in a supported string form
+    pub closed spec fn spec_display_text_to_term(text: SpecDisplayTextValue) -> SpecTerm
+    {
+        match text {
+            SpecDisplayTextValue::IA5String(s) => spec_str!(s),
+            SpecDisplayTextValue::VisibleString(s) => spec_str!(s),
+            SpecDisplayTextValue::BMPString(s) => spec_str!(s),
+            SpecDisplayTextValue::UTF8String(s) => spec_str!(s),
+            _ => spec_atom!("unsupported".view()),
+        }
+    }
+
+    /// Exec version of spec_display_text_to_term
+    pub fn display_text_to_term(text: &DisplayTextValue) -> (res: Term)
+        ensures res@ =~~= Self::spec_display_text_to_term(text@)
+    {
+        match text {
+            DisplayTextValue::IA5String(s) => TermX::str(s),
+            DisplayTextValue::VisibleString(s) => TermX::str(s),
+            DisplayTextValue::BMPString(s) => TermX::str(s.as_str()),
+            DisplayTextValue::UTF8String(s) => TermX::str(s),
+            _ => TermX::atom("unsupported"),
+        }
+    }
+
+    /// Convert a policy qualifier to a pair of (kind, value), where kind is
+    /// "cps" or "userNotice" for known qualifiers, and the OID otherwise
+    pub closed spec fn spec_qualifier_to_terms(qualifier: SpecPolicyQualifierInfoValue) -> (SpecTerm, SpecTerm)
+    {
+        match qualifier.param {
+            SpecPolicyQualifierParamValue::CPSUri(uri) => (spec_atom!("cps".view()), spec_str!(uri)),
+            SpecPolicyQualifierParamValue::UserNotice(notice) => (
+                spec_atom!("userNotice".view()),
+                match notice.explicit_text {
+                    OptionDeep::Some(text) => Self::spec_display_text_to_term(text),
+                    OptionDeep::None => spec_atom!("none".view()),
+                }
+            ),
+            _ => (spec_str!(BasicFacts::spec_oid_to_string(qualifier.id)), spec_atom!("unsupported".view())),
+        }
+    }
+
+    /// Exec version of spec_qualifier_to_terms
+    pub fn qualifier_to_terms(qualifier: &PolicyQualifierInfoValue) -> (res: (Term, Term))
+        ensures (res.0@, res.1@) =~~= Self::spec_qualifier_to_terms(qualifier@)
+    {
+        match &qualifier.param {
+            PolicyQualifierParamValue::CPSUri(uri) => (TermX::atom("cps"), TermX::str(uri)),
+            PolicyQualifierParamValue::UserNotice(notice) => (
+                TermX::atom("userNotice"),
+                match &notice.explicit_text {
+                    OptionDeep::Some(text) => Self::display_text_to_term(text),
+                    OptionDeep::None => TermX::atom("none"),
+                }
+            ),
+            _ => (TermX::str(BasicFacts::oid_to_string(&qualifier.id).as_str()), TermX::atom("unsupported")),
+        }
+    }
+
+    /// Generate certificatePolicyQualifier(cert, policy, kind, value) for each qualifier of a policy
+    pub closed spec fn spec_qualifier_facts(t: CertIndexed<SpecCertificateValue>, policy: SpecPolicyInfoValue) -> Seq<SpecRule>
+    {
+        if let OptionDeep::Some(qualifiers) = policy.qualifiers {
+            qualifiers.map_values(|qualifier: SpecPolicyQualifierInfoValue|
+                spec_fact!(
+                    "certificatePolicyQualifier",
+                    t.spec_cert(),
+                    spec_str!(BasicFacts::spec_oid_to_string(policy.policy_id)),
+                    Self::spec_qualifier_to_terms(qualifier).0,
+                    Self::spec_qualifier_to_terms(qualifier).1,
+                ))
+        } else {
+            seq![]
+        }
+    }
+
+    /// Exec version of spec_qualifier_facts
+    pub fn qualifier_facts<'a, 'b>(t: &CertIndexed<&'b CertificateValue<'a>>, policy: &PolicyInfoValue) -> (res: VecDeep<Rule>)
+        ensures res@ =~~= Self::spec_qualifier_facts(t@, policy@)
+    {
+        let mut facts = vec_deep![];
+
+        if let OptionDeep::Some(qualifiers) = &policy.qualifiers {
+            let policy_id = BasicFacts::oid_to_string(&policy.policy_id);
+
+            let len = qualifiers.len();
+            for i in 0..len
+                invariant
+                    len == qualifiers@.len(),
+                    policy_id@ =~= BasicFacts::spec_oid_to_string(policy@.policy_id),
+                    facts@ =~~= Self::spec_qualifier_facts(t@, policy@).take(i as int),
+            {
+                let (kind, value) = Self::qualifier_to_terms(qualifiers.get(i));
+                facts.push(RuleX::fact("certificatePolicyQualifier", vec![ t.cert(), TermX::str(policy_id.as_str()), kind, value ]));
+            }
+
+            assert(Self::spec_qualifier_facts(t@, policy@).take(len as int) == Self::spec_qualifier_facts(t@, policy@));
+        }
+
+        facts
+    }
+}
+
 impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtCertificatePoliciesFacts {
     closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
         Some(if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(CERT_POLICIES)) {
@@ -116,6 +245,7 @@ impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtCertificatePoli
                     spec_fact!("certificatePoliciesCritical", t.spec_cert(), spec_bool!(ext.critical)),
                 ] + policies.map_values(|policy: SpecPolicyInfoValue|
                     spec_fact!("certificatePolicies", t.spec_cert(), spec_str!(BasicFacts::spec_oid_to_string(policy.policy_id))))
+                + Seq::new(policies.len(), |i| Self::spec_qualifier_facts(t, policies[i])).flatten()
             } else {
                 seq![
                     spec_fact!("certificatePoliciesExt", t.spec_cert(), spec_bool!(false)),
@@ -144,6 +274,19 @@ impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtCertificatePoli
                     out.push(RuleX::fact("certificatePolicies", vec![ t.cert(), TermX::str(BasicFacts::oid_to_string(&policies.get(i).policy_id).as_str()) ]));
                 }
 
+                // Add certificatePolicyQualifier facts for each policy
+                let mut qualifier_facts = vec_deep![];
+                for i in 0..len
+                    invariant
+                        len == policies@.len(),
+                        out@ =~~= old(out)@ + Self::spec_facts(t@).unwrap().take(len + 2),
+                        qualifier_facts@ =~~= Seq::new(i as nat, |j| Self::spec_qualifier_facts(t@, policies@[j])),
+                {
+                    qualifier_facts.push(Self::qualifier_facts(t, policies.get(i)));
+                }
+
+                out.append_owned(VecDeep::flatten(qualifier_facts));
+
                 return Ok(());
             }
         }
@@ -356,12 +499,63 @@ impl ExtSubjectAltNameFacts {
         else { "UNKNOWN" }
     }
 
+    /// Convert an OtherName to a pair of (typ, str), where typ is
+    /// "Other/<type>" for known type-ids, and "Other/<OID>" otherwise
+    ///
+    /// A HardwareModuleName is encoded as "<hwType>:<hwSerialNum in hex>"
+    pub closed spec fn spec_extract_other_name(name: SpecOtherNameValue) -> (Seq<char>, SpecTerm)
+    {
+        match name.param {
+            SpecOtherNameParamValue::UPN(s) => ("Other/UPN"@, spec_str!(s)),
+            SpecOtherNameParamValue::SmtpUTF8Mailbox(s) => ("Other/SmtpUTF8Mailbox"@, spec_str!(s)),
+            SpecOtherNameParamValue::PermanentIdentifier(id) => (
+                "Other/PermanentIdentifier"@,
+                match id.identifier_value {
+                    OptionDeep::Some(s) => spec_str!(s),
+                    OptionDeep::None => spec_atom!("none".view()),
+                }
+            ),
+            SpecOtherNameParamValue::HardwareModuleName(hw) => (
+                "Other/HardwareModuleName"@,
+                spec_str!(BasicFacts::spec_oid_to_string(hw.hw_type) + ":"@ + hash::spec_to_hex_upper(hw.hw_serial_num)),
+            ),
+            _ => ("Other/"@ + BasicFacts::spec_oid_to_string(name.id), spec_atom!("unsupported".view())),
+        }
+    }
+
+    /// Exec version of spec_extract_other_name
+    pub fn extract_other_name(name: &OtherNameValue) -> (res: (String, Term))
+        ensures (res.0@, res.1@) =~~= Self::spec_extract_other_name(name@)
+    {
+        match &name.param {
+            OtherNameParamValue::UPN(s) => ("Other/UPN".to_string(), TermX::str(s)),
+            OtherNameParamValue::SmtpUTF8Mailbox(s) => ("Other/SmtpUTF8Mailbox".to_string(), TermX::str(s)),
+            OtherNameParamValue::PermanentIdentifier(id) => (
+                "Other/PermanentIdentifier".to_string(),
+                match &id.identifier_value {
+                    OptionDeep::Some(s) => TermX::str(s),
+                    OptionDeep::None => TermX::atom("none"),
+                }
+            ),
+            OtherNameParamValue::HardwareModuleName(hw) => (
+                "Other/HardwareModuleName".to_string(),
+                TermX::str(BasicFacts::oid_to_string(&hw.hw_type)
+                    .concat(":")
+                    .concat(hash::to_hex_upper(hw.hw_serial_num).as_str()).as_str()),
+            ),
+            _ => (
+                "Other/".to_string().concat(BasicFacts::oid_to_string(&name.id).as_str()),
+                TermX::atom("unsupported"),
+            ),
+        }
+    }
+
     /// Generate pairs of (typ, str) where typ is the name of the variant
     /// and str is the content of the general name
     pub closed spec fn spec_extract_general_name(name: SpecGeneralNameValue) -> Seq<(Seq<char>, SpecTerm)>
     {
         match name {
-            SpecGeneralNameValue::Other(..) => seq![ ("Other"@, spec_atom!("unsupported".view())) ],
+            SpecGeneralNameValue::Other(other) => seq![ Self::spec_extract_other_name(other) ],
             SpecGeneralNameValue::RFC822(s) => seq![ ("RFC822"@, spec_str!(s)) ],
             SpecGeneralNameValue::DNS(s) => seq![ ("DNS"@, spec_str!(s)) ],
             SpecGeneralNameValue::X400(..) => seq![ ("X400"@, spec_atom!("unsupported".view())) ],
@@ -376,7 +570,13 @@ impl ExtSubjectAltNameFacts {
                     ))
                 ).flatten()
             }
-            SpecGeneralNameValue::EDIParty(..) => seq![ ("EDIParty"@, spec_atom!("unsupported".view())) ],
+            SpecGeneralNameValue::EDIParty(name) => seq![ (
+                "EDIParty"@,
+                match SubjectNameFacts::spec_dir_string_to_string(name.party_name) {
+                    Some(s) => spec_str!(s),
+                    None => spec_atom!("unsupported".view()),
+                }
+            ) ],
             SpecGeneralNameValue::URI(s) => seq![ ("URI"@, spec_str!(s)) ],
             SpecGeneralNameValue::IP(..) => seq![ ("IP"@, spec_atom!("unsupported".view())) ],
             SpecGeneralNameValue::RegisteredID(..) => seq![ ("RegisteredID"@, spec_atom!("unsupported".view())) ],
@@ -390,7 +590,7 @@ impl ExtSubjectAltNameFacts {
         ensures res@ =~~= Self::spec_extract_general_name(name@)
     {
         match name {
-            GeneralNameValue::Other(..) => vec_deep![("Other".to_string(), TermX::atom("unsupported"))],
+            GeneralNameValue::Other(other) => vec_deep![Self::extract_other_name(other)],
             GeneralNameValue::RFC822(s) => vec_deep![("RFC822".to_string(), TermX::str(s))],
             GeneralNameValue::DNS(s) => vec_deep![("DNS".to_string(), TermX::str(s))],
             GeneralNameValue::X400(..) => vec_deep![("X400".to_string(), TermX::atom("unsupported"))],
@@ -446,7 +646,13 @@ impl ExtSubjectAltNameFacts {
 
                 dir_name_pairs
             }
-            GeneralNameValue::EDIParty(..) => vec_deep![("EDIParty".to_string(), TermX::atom("unsupported"))],
+            GeneralNameValue::EDIParty(name) => {
+                let val = match SubjectNameFacts::dir_string_to_string(&name.party_name) {
+                    Some(s) => TermX::str(s),
+                    None => TermX::atom("unsupported"),
+                };
+                vec_deep![("EDIParty".to_string(), val)]
+            }
             GeneralNameValue::URI(s) => vec_deep![("URI".to_string(), TermX::str(s))],
             GeneralNameValue::IP(..) => vec_deep![("IP".to_string(), TermX::atom("unsupported"))],
             GeneralNameValue::RegisteredID(..) => vec_deep![("RegisteredID".to_string(), TermX::atom("unsupported"))],
@@ -532,6 +738,214 @@ impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtSubjectAltNameF
     }
 }
 
+impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtIssuerAltNameFacts {
+    closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
+        Some(if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(ISSUER_ALT_NAME)) {
+            if let SpecExtensionParamValue::IssuerAltName(names) = ext.param {
+                seq![
+                    spec_fact!("ianExt", t.spec_cert(), spec_bool!(true)),
+                    spec_fact!("ianCritical", t.spec_cert(), spec_bool!(ext.critical)),
+                ] +
+                ExtSubjectAltNameFacts::spec_extract_general_names(names).map_values(|v: (Seq<char>, SpecTerm)| spec_fact!("ian", t.spec_cert(), v.1))
+            } else {
+                seq![
+                    spec_fact!("ianExt", t.spec_cert(), spec_bool!(false)),
+                ]
+            }
+        } else {
+            seq![
+                spec_fact!("ianExt", t.spec_cert(), spec_bool!(false)),
+            ]
+        })
+    }
+
+    fn facts(t: &CertIndexed<&'b CertificateValue<'a>>, out: &mut VecDeep<Rule>) -> (res: Result<(), ValidationError>) {
+        if let OptionDeep::Some(ext) = get_extension(t.x, &oid!(ISSUER_ALT_NAME)) {
+            if let ExtensionParamValue::IssuerAltName(names) = &ext.param {
+                out.push(RuleX::fact("ianExt", vec![ t.cert(), TermX::bool(true) ]));
+                out.push(RuleX::fact("ianCritical", vec![ t.cert(), TermX::bool(ext.critical) ]));
+
+                let typ_names = ExtSubjectAltNameFacts::extract_general_names(names);
+
+                // Push all issuer alt names as facts
+                let ghost prev_out = out@;
+                let len = typ_names.len();
+                for i in 0..len
+                    invariant
+                        len == typ_names@.len(),
+                        typ_names@ == ExtSubjectAltNameFacts::spec_extract_general_names(names@),
+                        out@ =~~= prev_out + ExtSubjectAltNameFacts::spec_extract_general_names(names@)
+                            .map_values(|v: (Seq<char>, SpecTerm)| spec_fact!("ian", t.view().spec_cert(), v.1)).take(i as int),
+                {
+                    out.push(RuleX::fact("ian", vec![ t.cert(), arc_clone(&typ_names.get(i).1) ]));
+                }
+
+                return Ok(());
+            }
+        }
+
+        out.push(RuleX::fact("ianExt", vec![ t.cert(), TermX::bool(false) ]));
+        Ok(())
+    }
+}
+
+impl ExtSubjectDirectoryAttributesFacts {
+    /// Convert the type of a directory attribute to a term:
+    /// an atom for the personal data attributes in RFC 3739,
+    /// and the OID string otherwise
+    pub closed spec fn spec_attr_type_to_term(attr: SpecDirectoryAttributeValue) -> SpecTerm
+    {
+        match attr.param {
+            SpecDirectoryAttributeParamValue::DateOfBirth(..) => spec_atom!("dateOfBirth".view()),
+            SpecDirectoryAttributeParamValue::PlaceOfBirth(..) => spec_atom!("placeOfBirth".view()),
+            SpecDirectoryAttributeParamValue::Gender(..) => spec_atom!("gender".view()),
+            SpecDirectoryAttributeParamValue::CountryOfCitizenship(..) => spec_atom!("countryOfCitizenship".view()),
+            SpecDirectoryAttributeParamValue::CountryOfResidence(..) => spec_atom!("countryOfResidence".view()),
+            _ => spec_str!(BasicFacts::spec_oid_to_string(attr.typ)),
+        }
+    }
+
+    /// Exec version of spec_attr_type_to_term
+    pub fn attr_type_to_term(attr: &DirectoryAttributeValue) -> (res: Term)
+        ensures res@ =~~= Self::spec_attr_type_to_term(attr@)
+    {
+        match &attr.param {
+            DirectoryAttributeParamValue::DateOfBirth(..) => TermX::atom("dateOfBirth"),
+            DirectoryAttributeParamValue::PlaceOfBirth(..) => TermX::atom("placeOfBirth"),
+            DirectoryAttributeParamValue::Gender(..) => TermX::atom("gender"),
+            DirectoryAttributeParamValue::CountryOfCitizenship(..) => TermX::atom("countryOfCitizenship"),
+            DirectoryAttributeParamValue::CountryOfResidence(..) => TermX::atom("countryOfResidence"),
+            _ => TermX::str(BasicFacts::oid_to_string(&attr.typ).as_str()),
+        }
+    }
+}
+
+impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtSubjectDirectoryAttributesFacts {
+    closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
+        Some(if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(SUBJECT_DIR_ATTRS)) {
+            if let SpecExtensionParamValue::SubjectDirectoryAttributes(attrs) = ext.param {
+                seq![
+                    spec_fact!("subjectDirectoryAttributesExt", t.spec_cert(), spec_bool!(true)),
+                    spec_fact!("subjectDirectoryAttributesCritical", t.spec_cert(), spec_bool!(ext.critical)),
+                ] + attrs.map_values(|attr: SpecDirectoryAttributeValue|
+                    spec_fact!("subjectDirectoryAttribute", t.spec_cert(), Self::spec_attr_type_to_term(attr)))
+            } else {
+                seq![
+                    spec_fact!("subjectDirectoryAttributesExt", t.spec_cert(), spec_bool!(false)),
+                ]
+            }
+        } else {
+            seq![
+                spec_fact!("subjectDirectoryAttributesExt", t.spec_cert(), spec_bool!(false)),
+            ]
+        })
+    }
+
+    #[verifier::loop_isolation(false)]
+    fn facts(t: &CertIndexed<&'b CertificateValue<'a>>, out: &mut VecDeep<Rule>) -> (res: Result<(), ValidationError>) {
+        if let OptionDeep::Some(ext) = get_extension(t.x, &oid!(SUBJECT_DIR_ATTRS)) {
+            if let ExtensionParamValue::SubjectDirectoryAttributes(attrs) = &ext.param {
+                out.push(RuleX::fact("subjectDirectoryAttributesExt", vec![ t.cert(), TermX::bool(true) ]));
+                out.push(RuleX::fact("subjectDirectoryAttributesCritical", vec![ t.cert(), TermX::bool(ext.critical) ]));
+
+                let len = attrs.len();
+                for i in 0..len
+                    invariant
+                        len == attrs@.len(),
+                        out@ =~~= old(out)@ + Self::spec_facts(t@).unwrap().take(i + 2),
+                {
+                    out.push(RuleX::fact("subjectDirectoryAttribute", vec![ t.cert(), Self::attr_type_to_term(attrs.get(i)) ]));
+                }
+
+                return Ok(());
+            }
+        }
+
+        out.push(RuleX::fact("subjectDirectoryAttributesExt", vec![ t.cert(), TermX::bool(false) ]));
+        Ok(())
+    }
+}
+
+impl ExtPrivateKeyUsagePeriodFacts {
+    /// Convert an optional GeneralizedTime to a UNIX timestamp term,
+    /// or the atom `none` if absent
+    pub closed spec fn spec_time_to_term(time: OptionDeep<SpecGeneralizedTimeValue>) -> Option<SpecTerm>
+    {
+        match time {
+            OptionDeep::Some(time) => {
+                if_let! {
+                    let Some(ts) = TimeFacts::spec_time_to_timestamp(SpecTimeValue::GeneralizedTime(time));
+                    Some(spec_int!(ts as int))
+                }
+            }
+            OptionDeep::None => Some(spec_atom!("none".view())),
+        }
+    }
+
+    /// Exec version of spec_time_to_term
+    pub fn time_to_term(time: &OptionDeep<GeneralizedTimeValue>) -> (res: Result<Term, ValidationError>)
+        ensures
+            res matches Ok(res) ==> Self::spec_time_to_term(time@) matches Some(spec_res) && res@ =~~= spec_res,
+            res is Err ==> Self::spec_time_to_term(time@) is None,
+    {
+        match time {
+            OptionDeep::Some(time) => {
+                let ts = TimeFacts::time_to_timestamp(&TimeValue::GeneralizedTime(time.clone()))
+                    .ok_or(ValidationError::TimeParseError)?;
+                Ok(TermX::int(ts))
+            }
+            OptionDeep::None => Ok(TermX::atom("none")),
+        }
+    }
+}
+
+impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtPrivateKeyUsagePeriodFacts {
+    closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
+        if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(PRIVATE_KEY_USAGE_PERIOD)) {
+            if let SpecExtensionParamValue::PrivateKeyUsagePeriod(param) = ext.param {
+                if_let! {
+                    let Some(not_before) = Self::spec_time_to_term(param.not_before);
+                    let Some(not_after) = Self::spec_time_to_term(param.not_after);
+
+                    Some(seq![
+                        spec_fact!("privateKeyUsagePeriodExt", t.spec_cert(), spec_bool!(true)),
+                        spec_fact!("privateKeyUsagePeriodCritical", t.spec_cert(), spec_bool!(ext.critical)),
+                        spec_fact!("privateKeyNotBefore", t.spec_cert(), not_before),
+                        spec_fact!("privateKeyNotAfter", t.spec_cert(), not_after),
+                    ])
+                }
+            } else {
+                Some(seq![
+                    spec_fact!("privateKeyUsagePeriodExt", t.spec_cert(), spec_bool!(false)),
+                ])
+            }
+        } else {
+            Some(seq![
+                spec_fact!("privateKeyUsagePeriodExt", t.spec_cert(), spec_bool!(false)),
+            ])
+        }
+    }
+
+    fn facts(t: &CertIndexed<&'b CertificateValue<'a>>, out: &mut VecDeep<Rule>) -> (res: Result<(), ValidationError>) {
+        if let OptionDeep::Some(ext) = get_extension(t.x, &oid!(PRIVATE_KEY_USAGE_PERIOD)) {
+            if let ExtensionParamValue::PrivateKeyUsagePeriod(param) = &ext.param {
+                let not_before = Self::time_to_term(&param.not_before)?;
+                let not_after = Self::time_to_term(&param.not_after)?;
+
+                out.push(RuleX::fact("privateKeyUsagePeriodExt", vec![ t.cert(), TermX::bool(true) ]));
+                out.push(RuleX::fact("privateKeyUsagePeriodCritical", vec![ t.cert(), TermX::bool(ext.critical) ]));
+                out.push(RuleX::fact("privateKeyNotBefore", vec![ t.cert(), not_before ]));
+                out.push(RuleX::fact("privateKeyNotAfter", vec![ t.cert(), not_after ]));
+
+                return Ok(());
+            }
+        }
+
+        out.push(RuleX::fact("privateKeyUsagePeriodExt", vec![ t.cert(), TermX::bool(false) ]));
+        Ok(())
+    }
+}
+
 impl ExtNameConstraintsFacts {
     /// Generate nameConstraintsPermited and nameConstraintsExcluded facts
     pub closed spec fn spec_gen_general_subtree_facts(
@@ -652,4 +1066,650 @@ impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtNameConstraints
     }
 }
 
+impl ExtCRLDistributionPointsFacts {
+    /// Get all URIs in the fullName of a distribution point
+    pub closed spec fn spec_dist_point_uris(dp: SpecDistributionPointValue) -> Seq<Seq<char>>
+    {
+        if let OptionDeep::Some(SpecDistributionPointNameValue::FullName(names)) = dp.name {
+            Seq::new(names.len(), |i| if let SpecGeneralNameValue::URI(uri) = names[i] { seq![uri] } else { seq![] }).flatten()
+        } else {
+            seq![]
+        }
+    }
+
+    /// Exec version of spec_dist_point_uris
+    pub fn dist_point_uris<'a, 'b>(dp: &'b DistributionPointValue<'a>) -> (res: VecDeep<&'a str>)
+        ensures res@ =~~= Self::spec_dist_point_uris(dp@)
+    {
+        if let OptionDeep::Some(DistributionPointNameValue::FullName(names)) = &dp.name {
+            let mut uris = vec_deep![];
+
+            let len = names.len();
+            for i in 0..len
+                invariant
+                    len == names@.len(),
+                    uris@ =~~= Seq::new(i as nat, |j| if let SpecGeneralNameValue::URI(uri) = names@[j] { seq![uri] } else { seq![] }),
+            {
+                if let GeneralNameValue::URI(uri) = names.get(i) {
+                    uris.push(vec_deep![*uri]);
+                } else {
+                    uris.push(vec_deep![]);
+                }
+            }
+
+            VecDeep::flatten(uris)
+        } else {
+            vec_deep![]
+        }
+    }
+
+    /// Get all URIs in a list of distribution points
+    pub closed spec fn spec_all_uris(dps: Seq<SpecDistributionPointValue>) -> Seq<Seq<char>>
+    {
+        Seq::new(dps.len(), |i| Self::spec_dist_point_uris(dps[i])).flatten()
+    }
+
+    /// Exec version of spec_all_uris
+    pub fn all_uris<'a, 'b>(dps: &'b VecDeep<DistributionPointValue<'a>>) -> (res: VecDeep<&'a str>)
+        ensures res@ =~~= Self::spec_all_uris(dps@)
+    {
+        let mut uris = vec_deep![];
+
+        let len = dps.len();
+        for i in 0..len
+            invariant
+                len == dps@.len(),
+                uris@ =~~= Seq::new(i as nat, |j| Self::spec_dist_point_uris(dps@[j])),
+        {
+            uris.push(Self::dist_point_uris(dps.get(i)));
+        }
+
+        VecDeep::flatten(uris)
+    }
+}
+
+impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtCRLDistributionPointsFacts {
+    closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
+        Some(if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(CRL_DISTRIBUTION_POINTS)) {
+            if let SpecExtensionParamValue::CRLDistributionPoints(dps) = ext.param {
+                seq![
+                    spec_fact!("crlDistributionPointsExt", t.spec_cert(), spec_bool!(true)),
+                    spec_fact!("crlDistributionPointsCritical", t.spec_cert(), spec_bool!(ext.critical)),
+                ] +
+                Self::spec_all_uris(dps).map_values(|uri: Seq<char>| spec_fact!("crlDistributionPoint", t.spec_cert(), spec_str!(uri)))
+            } else {
+                seq![
+                    spec_fact!("crlDistributionPointsExt", t.spec_cert(), spec_bool!(false)),
+                ]
+            }
+        } else {
+            seq![
+                spec_fact!("crlDistributionPointsExt", t.spec_cert(), spec_bool!(false)),
+            ]
+        })
+    }
+
+    fn facts(t: &CertIndexed<&'b CertificateValue<'a>>, out: &mut VecDeep<Rule>) -> (res: Result<(), ValidationError>) {
+        if let OptionDeep::Some(ext) = get_extension(t.x, &oid!(CRL_DISTRIBUTION_POINTS)) {
+            if let ExtensionParamValue::CRLDistributionPoints(dps) = &ext.param {
+                out.push(RuleX::fact("crlDistributionPointsExt", vec![ t.cert(), TermX::bool(true) ]));
+                out.push(RuleX::fact("crlDistributionPointsCritical", vec![ t.cert(), TermX::bool(ext.critical) ]));
+
+                let uris = Self::all_uris(dps);
+
+                // Push all URIs as facts
+                let ghost prev_out = out@;
+                let len = uris.len();
+                for i in 0..len
+                    invariant
+                        len == uris@.len(),
+                        uris@ == Self::spec_all_uris(dps@),
+                        out@ =~~= prev_out + Self::spec_all_uris(dps@)
+                            .map_values(|uri: Seq<char>| spec_fact!("crlDistributionPoint", t.view().spec_cert(), spec_str!(uri))).take(i as int),
+                {
+                    out.push(RuleX::fact("crlDistributionPoint", vec![ t.cert(), TermX::str(uris.get(i)) ]));
+                }
+
+                return Ok(());
+            }
+        }
+
+        out.push(RuleX::fact("crlDistributionPointsExt", vec![ t.cert(), TermX::bool(false) ]));
+        Ok(())
+    }
+}
+
+
+impl ExtAuthorityInfoAccessFacts {
+    /// Generate ocspResponder(cert, URI) or caIssuers(cert, URI)
+    /// for an access description with a URI location
+    pub closed spec fn spec_access_desc_facts(t: CertIndexed<SpecCertificateValue>, desc: SpecAccessDescriptionValue) -> Seq<SpecRule>
+    {
+        if let SpecGeneralNameValue::URI(uri) = desc.location {
+            if desc.method == spec_oid!(AD_OCSP) {
+                seq![ spec_fact!("ocspResponder", t.spec_cert(), spec_str!(uri)) ]
+            } else if desc.method == spec_oid!(AD_CA_ISSUERS) {
+                seq![ spec_fact!("caIssuers", t.spec_cert(), spec_str!(uri)) ]
+            } else {
+                seq![]
+            }
+        } else {
+            seq![]
+        }
+    }
+
+    /// Exec version of spec_access_desc_facts
+    pub fn access_desc_facts<'a, 'b>(t: &CertIndexed<&'b CertificateValue<'a>>, desc: &AccessDescriptionValue) -> (res: VecDeep<Rule>)
+        ensures res@ =~~= Self::spec_access_desc_facts(t@, desc@)
+    {
+        if let GeneralNameValue::URI(uri) = &desc.location {
+            if desc.method.polyfill_eq(&oid!(AD_OCSP)) {
+                return vec_deep![ RuleX::fact("ocspResponder", vec![ t.cert(), TermX::str(uri) ]) ];
+            } else if desc.method.polyfill_eq(&oid!(AD_CA_ISSUERS)) {
+                return vec_deep![ RuleX::fact("caIssuers", vec![ t.cert(), TermX::str(uri) ]) ];
+            }
+        }
+
+        vec_deep![]
+    }
+}
+
+impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtAuthorityInfoAccessFacts {
+    closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
+        Some(if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(AUTHORITY_INFO_ACCESS)) {
+            if let SpecExtensionParamValue::AuthorityInfoAccess(descs) = ext.param {
+                seq![
+                    spec_fact!("authorityInfoAccessExt", t.spec_cert(), spec_bool!(true)),
+                    spec_fact!("authorityInfoAccessCritical", t.spec_cert(), spec_bool!(ext.critical)),
+                ] +
+                Seq::new(descs.len(), |i| Self::spec_access_desc_facts(t, descs[i])).flatten()
+            } else {
+                seq![
+                    spec_fact!("authorityInfoAccessExt", t.spec_cert(), spec_bool!(false)),
+                ]
+            }
+        } else {
+            seq![
+                spec_fact!("authorityInfoAccessExt", t.spec_cert(), spec_bool!(false)),
+            ]
+        })
+    }
+
+    fn facts(t: &CertIndexed<&'b CertificateValue<'a>>, out: &mut VecDeep<Rule>) -> (res: Result<(), ValidationError>) {
+        if let OptionDeep::Some(ext) = get_extension(t.x, &oid!(AUTHORITY_INFO_ACCESS)) {
+            if let ExtensionParamValue::AuthorityInfoAccess(descs) = &ext.param {
+                out.push(RuleX::fact("authorityInfoAccessExt", vec![ t.cert(), TermX::bool(true) ]));
+                out.push(RuleX::fact("authorityInfoAccessCritical", vec![ t.cert(), TermX::bool(ext.critical) ]));
+
+                let mut desc_facts = vec_deep![];
+
+                let len = descs.len();
+                for i in 0..len
+                    invariant
+                        len == descs@.len(),
+                        desc_facts@ =~~= Seq::new(i as nat, |j| Self::spec_access_desc_facts(t@, descs@[j])),
+                {
+                    desc_facts.push(Self::access_desc_facts(t, descs.get(i)));
+                }
+
+                out.append_owned(VecDeep::flatten(desc_facts));
+
+                return Ok(());
+            }
+        }
+
+        out.push(RuleX::fact("authorityInfoAccessExt", vec![ t.cert(), TermX::bool(false) ]));
+        Ok(())
+    }
+}
+
+
+impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtPolicyMappingsFacts {
+    closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
+        Some(if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(POLICY_MAPPINGS)) {
+            if let SpecExtensionParamValue::PolicyMappings(mappings) = ext.param {
+                seq![
+                    spec_fact!("policyMappingsExt", t.spec_cert(), spec_bool!(true)),
+                    spec_fact!("policyMappingsCritical", t.spec_cert(), spec_bool!(ext.critical)),
+                ] + mappings.map_values(|mapping: SpecPolicyMappingValue|
+                    spec_fact!(
+                        "policyMapping",
+                        t.spec_cert(),
+                        spec_str!(BasicFacts::spec_oid_to_string(mapping.issuer_domain_policy)),
+                        spec_str!(BasicFacts::spec_oid_to_string(mapping.subject_domain_policy)),
+                    ))
+            } else {
+                seq![
+                    spec_fact!("policyMappingsExt", t.spec_cert(), spec_bool!(false)),
+                ]
+            }
+        } else {
+            seq![
+                spec_fact!("policyMappingsExt", t.spec_cert(), spec_bool!(false)),
+            ]
+        })
+    }
+
+    #[verifier::loop_isolation(false)]
+    fn facts(t: &CertIndexed<&'b CertificateValue<'a>>, out: &mut VecDeep<Rule>) -> (res: Result<(), ValidationError>) {
+        if let OptionDeep::Some(ext) = get_extension(t.x, &oid!(POLICY_MAPPINGS)) {
+            if let ExtensionParamValue::PolicyMappings(mappings) = &ext.param {
+                out.push(RuleX::fact("policyMappingsExt", vec![ t.cert(), TermX::bool(true) ]));
+                out.push(RuleX::fact("policyMappingsCritical", vec![ t.cert(), TermX::bool(ext.critical) ]));
+
+                let len = mappings.len();
+                for i in 0..len
+                    invariant
+                        len == mappings@.len(),
+                        out@ =~~= old(out)@ + Self::spec_facts(t@).unwrap().take(i + 2),
+                {
+                    let mapping = mappings.get(i);
+                    out.push(RuleX::fact("policyMapping", vec![
+                        t.cert(),
+                        TermX::str(BasicFacts::oid_to_string(&mapping.issuer_domain_policy).as_str()),
+                        TermX::str(BasicFacts::oid_to_string(&mapping.subject_domain_policy).as_str()),
+                    ]));
+                }
+
+                return Ok(());
+            }
+        }
+
+        out.push(RuleX::fact("policyMappingsExt", vec![ t.cert(), TermX::bool(false) ]));
+        Ok(())
+    }
+}
+
+impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtPolicyConstraintsFacts {
+    closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
+        Some(if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(POLICY_CONSTRAINTS)) {
+            if let SpecExtensionParamValue::PolicyConstraints(param) = ext.param {
+                seq![
+                    spec_fact!("policyConstraintsExt", t.spec_cert(), spec_bool!(true)),
+                    spec_fact!("policyConstraintsCritical", t.spec_cert(), spec_bool!(ext.critical)),
+
+                    if let OptionDeep::Some(n) = param.require_explicit_policy {
+                        spec_fact!("requireExplicitPolicy", t.spec_cert(), spec_int!(n as int))
+                    } else {
+                        spec_fact!("requireExplicitPolicy", t.spec_cert(), spec_atom!("none".view()))
+                    },
+
+                    if let OptionDeep::Some(n) = param.inhibit_policy_mapping {
+                        spec_fact!("inhibitPolicyMapping", t.spec_cert(), spec_int!(n as int))
+                    } else {
+                        spec_fact!("inhibitPolicyMapping", t.spec_cert(), spec_atom!("none".view()))
+                    },
+                ]
+            } else {
+                seq![
+                    spec_fact!("policyConstraintsExt", t.spec_cert(), spec_bool!(false)),
+                ]
+            }
+        } else {
+            seq![
+                spec_fact!("policyConstraintsExt", t.spec_cert(), spec_bool!(false)),
+            ]
+        })
+    }
+
+    fn facts(t: &CertIndexed<&'b CertificateValue<'a>>, out: &mut VecDeep<Rule>) -> (res: Result<(), ValidationError>) {
+        if let OptionDeep::Some(ext) = get_extension(t.x, &oid!(POLICY_CONSTRAINTS)) {
+            if let ExtensionParamValue::PolicyConstraints(param) = &ext.param {
+                out.push(RuleX::fact("policyConstraintsExt", vec![ t.cert(), TermX::bool(true) ]));
+                out.push(RuleX::fact("policyConstraintsCritical", vec![ t.cert(), TermX::bool(ext.critical) ]));
+
+                if let OptionDeep::Some(n) = param.require_explicit_policy {
+                    out.push(RuleX::fact("requireExplicitPolicy", vec![ t.cert(), TermX::int(n as LiteralInt) ]));
+                } else {
+                    out.push(RuleX::fact("requireExplicitPolicy", vec![ t.cert(), TermX::atom("none") ]));
+                }
+
+                if let OptionDeep::Some(n) = param.inhibit_policy_mapping {
+                    out.push(RuleX::fact("inhibitPolicyMapping", vec![ t.cert(), TermX::int(n as LiteralInt) ]));
+                } else {
+                    out.push(RuleX::fact("inhibitPolicyMapping", vec![ t.cert(), TermX::atom("none") ]));
+                }
+
+                return Ok(());
+            }
+        }
+
+        out.push(RuleX::fact("policyConstraintsExt", vec![ t.cert(), TermX::bool(false) ]));
+        Ok(())
+    }
+}
+
+impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtInhibitAnyPolicyFacts {
+    closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
+        Some(if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(INHIBIT_ANY_POLICY)) {
+            if let SpecExtensionParamValue::InhibitAnyPolicy(n) = ext.param {
+                seq![
+                    spec_fact!("inhibitAnyPolicyExt", t.spec_cert(), spec_bool!(true)),
+                    spec_fact!("inhibitAnyPolicyCritical", t.spec_cert(), spec_bool!(ext.critical)),
+                    spec_fact!("inhibitAnyPolicy", t.spec_cert(), spec_int!(n as int)),
+                ]
+            } else {
+                seq![
+                    spec_fact!("inhibitAnyPolicyExt", t.spec_cert(), spec_bool!(false)),
+                ]
+            }
+        } else {
+            seq![
+                spec_fact!("inhibitAnyPolicyExt", t.spec_cert(), spec_bool!(false)),
+            ]
+        })
+    }
+
+    fn facts(t: &CertIndexed<&'b CertificateValue<'a>>, out: &mut VecDeep<Rule>) -> (res: Result<(), ValidationError>) {
+        if let OptionDeep::Some(ext) = get_extension(t.x, &oid!(INHIBIT_ANY_POLICY)) {
+            if let ExtensionParamValue::InhibitAnyPolicy(n) = &ext.param {
+                out.push(RuleX::fact("inhibitAnyPolicyExt", vec![ t.cert(), TermX::bool(true) ]));
+                out.push(RuleX::fact("inhibitAnyPolicyCritical", vec![ t.cert(), TermX::bool(ext.critical) ]));
+                out.push(RuleX::fact("inhibitAnyPolicy", vec![ t.cert(), TermX::int(*n as LiteralInt) ]));
+
+                return Ok(());
+            }
+        }
+
+        out.push(RuleX::fact("inhibitAnyPolicyExt", vec![ t.cert(), TermX::bool(false) ]));
+        Ok(())
+    }
+}
+
+
+impl ExtSignedCertTimestampsFacts {
+    /// SCT timestamps are uint64, but only those that fit
+    /// in a LiteralInt can be represented as a term
+    pub open spec fn spec_timestamps_in_range(scts: Seq<SpecSignedCertTimestampValue>) -> bool
+    {
+        forall |i| 0 <= i < scts.len() ==> #[trigger] scts[i].timestamp as int <= LiteralInt::MAX as int
+    }
+}
+
+impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtSignedCertTimestampsFacts {
+    closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
+        if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(CT_SCT_LIST)) {
+            if let SpecExtensionParamValue::SignedCertTimestampList(scts) = ext.param {
+                if Self::spec_timestamps_in_range(scts) {
+                    Some(seq![
+                        spec_fact!("sctListExt", t.spec_cert(), spec_bool!(true)),
+                        spec_fact!("sctListCritical", t.spec_cert(), spec_bool!(ext.critical)),
+                    ] + scts.map_values(|sct: SpecSignedCertTimestampValue|
+                        spec_fact!(
+                            "sct",
+                            t.spec_cert(),
+                            spec_str!(hash::spec_to_hex_upper(sct.log_id)),
+                            spec_int!(sct.timestamp as int),
+                        )))
+                } else {
+                    None
+                }
+            } else {
+                Some(seq![
+                    spec_fact!("sctListExt", t.spec_cert(), spec_bool!(false)),
+                ])
+            }
+        } else {
+            Some(seq![
+                spec_fact!("sctListExt", t.spec_cert(), spec_bool!(false)),
+            ])
+        }
+    }
+
+    #[verifier::loop_isolation(false)]
+    fn facts(t: &CertIndexed<&'b CertificateValue<'a>>, out: &mut VecDeep<Rule>) -> (res: Result<(), ValidationError>) {
+        if let OptionDeep::Some(ext) = get_extension(t.x, &oid!(CT_SCT_LIST)) {
+            if let ExtensionParamValue::SignedCertTimestampList(scts) = &ext.param {
+                let len = scts.len();
+
+                // Reject timestamps that would wrap around as a LiteralInt
+                for i in 0..len
+                    invariant
+                        len == scts@.len(),
+                        forall |j| 0 <= j < i ==> #[trigger] scts@[j].timestamp as int <= LiteralInt::MAX as int,
+                {
+                    if scts.get(i).timestamp > LiteralInt::MAX as u64 {
+                        return Err(ValidationError::IntegerOverflow);
+                    }
+                }
+
+                out.push(RuleX::fact("sctListExt", vec![ t.cert(), TermX::bool(true) ]));
+                out.push(RuleX::fact("sctListCritical", vec![ t.cert(), TermX::bool(ext.critical) ]));
+
+                for i in 0..len
+                    invariant
+                        len == scts@.len(),
+                        out@ =~~= old(out)@ + Self::spec_facts(t@).unwrap().take(i + 2),
+                {
+                    let sct = scts.get(i);
+                    out.push(RuleX::fact("sct", vec![
+                        t.cert(),
+                        TermX::str(hash::to_hex_upper(sct.log_id).as_str()),
+                        TermX::int(sct.timestamp as LiteralInt),
+                    ]));
+                }
+
+                return Ok(());
+            }
+        }
+
+        out.push(RuleX::fact("sctListExt", vec![ t.cert(), TermX::bool(false) ]));
+        Ok(())
+    }
+}
+
+impl ExtQCStatementsFacts {
+    /// Convert a QcType to a term: esign/eseal/web for the
+    /// types in ETSI EN 319 412-5, and the OID string otherwise
+    pub closed spec fn spec_qc_type_to_term(typ: SpecObjectIdentifierValue) -> SpecTerm
+    {
+        if typ == spec_oid!(QCT_ESIGN) { spec_atom!("esign".view()) }
+        else if typ == spec_oid!(QCT_ESEAL) { spec_atom!("eseal".view()) }
+        else if typ == spec_oid!(QCT_WEB) { spec_atom!("web".view()) }
+        else { spec_str!(BasicFacts::spec_oid_to_string(typ)) }
+    }
+
+    /// Exec version of spec_qc_type_to_term
+    pub fn qc_type_to_term(typ: &ObjectIdentifierValue) -> (res: Term)
+        ensures res@ =~~= Self::spec_qc_type_to_term(typ@)
+    {
+        let id = oid!(QCT_ESIGN); assert(id@ == spec_oid!(QCT_ESIGN));
+        let id = oid!(QCT_ESEAL); assert(id@ == spec_oid!(QCT_ESEAL));
+        let id = oid!(QCT_WEB); assert(id@ == spec_oid!(QCT_WEB));
+
+        if typ.polyfill_eq(&oid!(QCT_ESIGN)) { TermX::atom("esign") }
+        else if typ.polyfill_eq(&oid!(QCT_ESEAL)) { TermX::atom("eseal") }
+        else if typ.polyfill_eq(&oid!(QCT_WEB)) { TermX::atom("web") }
+        else { TermX::str(BasicFacts::oid_to_string(typ).as_str()) }
+    }
+
+    /// Generate qcType(cert, type) for each type in a QcType statement
+    pub closed spec fn spec_qc_type_facts(t: CertIndexed<SpecCertificateValue>, stmt: SpecQCStatementValue) -> Seq<SpecRule>
+    {
+        if let SpecQCStatementParamValue::QcType(types) = stmt.param {
+            types.map_values(|typ: SpecObjectIdentifierValue|
+                spec_fact!("qcType", t.spec_cert(), Self::spec_qc_type_to_term(typ)))
+        } else {
+            seq![]
+        }
+    }
+
+    /// Exec version of spec_qc_type_facts
+    pub fn qc_type_facts<'a, 'b>(t: &CertIndexed<&'b CertificateValue<'a>>, stmt: &QCStatementValue) -> (res: VecDeep<Rule>)
+        ensures res@ =~~= Self::spec_qc_type_facts(t@, stmt@)
+    {
+        let mut facts = vec_deep![];
+
+        if let QCStatementParamValue::QcType(types) = &stmt.param {
+            let len = types.len();
+            for i in 0..len
+                invariant
+                    len == types@.len(),
+                    facts@ =~~= Self::spec_qc_type_facts(t@, stmt@).take(i as int),
+            {
+                facts.push(RuleX::fact("qcType", vec![ t.cert(), Self::qc_type_to_term(types.get(i)) ]));
+            }
+
+            assert(Self::spec_qc_type_facts(t@, stmt@).take(len as int) == Self::spec_qc_type_facts(t@, stmt@));
+        }
+
+        facts
+    }
+}
+
+impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtQCStatementsFacts {
+    closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
+        Some(if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(QC_STATEMENTS)) {
+            if let SpecExtensionParamValue::QCStatements(stmts) = ext.param {
+                seq![
+                    spec_fact!("qcStatementsExt", t.spec_cert(), spec_bool!(true)),
+                    spec_fact!("qcStatementsCritical", t.spec_cert(), spec_bool!(ext.critical)),
+                ] + stmts.map_values(|stmt: SpecQCStatementValue|
+                    spec_fact!("qcStatement", t.spec_cert(), spec_str!(BasicFacts::spec_oid_to_string(stmt.id))))
+                + Seq::new(stmts.len(), |i| Self::spec_qc_type_facts(t, stmts[i])).flatten()
+            } else {
+                seq![
+                    spec_fact!("qcStatementsExt", t.spec_cert(), spec_bool!(false)),
+                ]
+            }
+        } else {
+            seq![
+                spec_fact!("qcStatementsExt", t.spec_cert(), spec_bool!(false)),
+            ]
+        })
+    }
+
+    #[verifier::loop_isolation(false)]
+    fn facts(t: &CertIndexed<&'b CertificateValue<'a>>, out: &mut VecDeep<Rule>) -> (res: Result<(), ValidationError>) {
+        if let OptionDeep::Some(ext) = get_extension(t.x, &oid!(QC_STATEMENTS)) {
+            if let ExtensionParamValue::QCStatements(stmts) = &ext.param {
+                out.push(RuleX::fact("qcStatementsExt", vec![ t.cert(), TermX::bool(true) ]));
+                out.push(RuleX::fact("qcStatementsCritical", vec![ t.cert(), TermX::bool(ext.critical) ]));
+
+                let len = stmts.len();
+                for i in 0..len
+                    invariant
+                        len == stmts@.len(),
+                        out@ =~~= old(out)@ + Self::spec_facts(t@).unwrap().take(i + 2),
+                {
+                    out.push(RuleX::fact("qcStatement", vec![ t.cert(), TermX::str(BasicFacts::oid_to_string(&stmts.get(i).id).as_str()) ]));
+                }
+
+                // Add qcType facts for each QcType statement
+                let mut type_facts = vec_deep![];
+                for i in 0..len
+                    invariant
+                        len == stmts@.len(),
+                        out@ =~~= old(out)@ + Self::spec_facts(t@).unwrap().take(len + 2),
+                        type_facts@ =~~= Seq::new(i as nat, |j| Self::spec_qc_type_facts(t@, stmts@[j])),
+                {
+                    type_facts.push(Self::qc_type_facts(t, stmts.get(i)));
+                }
+
+                out.append_owned(VecDeep::flatten(type_facts));
+
+                return Ok(());
+            }
+        }
+
+        out.push(RuleX::fact("qcStatementsExt", vec![ t.cert(), TermX::bool(false) ]));
+        Ok(())
+    }
+}
+
+/// TLS feature for the status_request extension (RFC 7633, 4.2.3.1)
+pub const TLS_FEATURE_STATUS_REQUEST: i64 = 5;
+
+impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtTLSFeatureFacts {
+    closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
+        Some(if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(TLS_FEATURE)) {
+            if let SpecExtensionParamValue::TLSFeature(features) = ext.param {
+                seq![
+                    spec_fact!("tlsFeatureExt", t.spec_cert(), spec_bool!(true)),
+                    spec_fact!("tlsFeatureCritical", t.spec_cert(), spec_bool!(ext.critical)),
+                ] + features.map_values(|feature: i64| spec_fact!("tlsFeature", t.spec_cert(), spec_int!(feature as int)))
+                + if features.contains(TLS_FEATURE_STATUS_REQUEST) {
+                    seq![ spec_fact!("mustStaple", t.spec_cert()) ]
+                } else {
+                    seq![]
+                }
+            } else {
+                seq![
+                    spec_fact!("tlsFeatureExt", t.spec_cert(), spec_bool!(false)),
+                ]
+            }
+        } else {
+            seq![
+                spec_fact!("tlsFeatureExt", t.spec_cert(), spec_bool!(false)),
+            ]
+        })
+    }
+
+    #[verifier::loop_isolation(false)]
+    fn facts(t: &CertIndexed<&'b CertificateValue<'a>>, out: &mut VecDeep<Rule>) -> (res: Result<(), ValidationError>) {
+        if let OptionDeep::Some(ext) = get_extension(t.x, &oid!(TLS_FEATURE)) {
+            if let ExtensionParamValue::TLSFeature(features) = &ext.param {
+                out.push(RuleX::fact("tlsFeatureExt", vec![ t.cert(), TermX::bool(true) ]));
+                out.push(RuleX::fact("tlsFeatureCritical", vec![ t.cert(), TermX::bool(ext.critical) ]));
+
+                let mut must_staple = false;
+
+                let len = features.len();
+                for i in 0..len
+                    invariant
+                        len == features@.len(),
+                        must_staple == features@.take(i as int).contains(TLS_FEATURE_STATUS_REQUEST),
+                        out@ =~~= old(out)@ + Self::spec_facts(t@).unwrap().take(i + 2),
+                {
+                    let feature = *features.get(i);
+                    if feature == TLS_FEATURE_STATUS_REQUEST {
+                        must_staple = true;
+                    }
+
+                    assert(features@.take(i + 1) == features@.take(i as int).push(feature));
+                    out.push(RuleX::fact("tlsFeature", vec![ t.cert(), TermX::int(feature as LiteralInt) ]));
+                }
+
+                assert(features@.take(len as int) == features@);
+
+                if must_staple {
+                    out.push(RuleX::fact("mustStaple", vec![ t.cert() ]));
+                }
+
+                return Ok(());
+            }
+        }
+
+        out.push(RuleX::fact("tlsFeatureExt", vec![ t.cert(), TermX::bool(false) ]));
+        Ok(())
+    }
+}
+
+// NOTE: a non-critical poison extension is rejected by the parser
+impl<'a, 'b> Facts<CertIndexed<&'b CertificateValue<'a>>> for ExtCTPoisonFacts {
+    closed spec fn spec_facts(t: CertIndexed<SpecCertificateValue>) -> Option<Seq<SpecRule>> {
+        Some(if let OptionDeep::Some(ext) = spec_get_extension(t.x, spec_oid!(CT_POISON)) {
+            if let SpecExtensionParamValue::CTPoison(..) = ext.param {
+                seq![
+                    spec_fact!("isPrecertificate", t.spec_cert()),
+                ]
+            } else {
+                seq![]
+            }
+        } else {
+            seq![]
+        })
+    }
+
+    fn facts(t: &CertIndexed<&'b CertificateValue<'a>>, out: &mut VecDeep<Rule>) -> (res: Result<(), ValidationError>) {
+        if let OptionDeep::Some(ext) = get_extension(t.x, &oid!(CT_POISON)) {
+            if let ExtensionParamValue::CTPoison(..) = &ext.param {
+                out.push(RuleX::fact("isPrecertificate", vec![ t.cert() ]));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 }
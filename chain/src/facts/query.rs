@@ -32,6 +32,7 @@ pub struct QueryPoly<Roots, Chain, Domain> {
     pub chain: Chain,
     pub domain: Domain,
     pub now: i64, // current UNIX timestamp
+    pub allow_sha1: bool, // accept legacy SHA-1 RSA signatures
 }
 
 pub type SpecQuery = QueryPoly<Seq<SpecCertificateValue>, Seq<SpecCertificateValue>, SpecStringLiteral>;
@@ -102,7 +103,7 @@ impl<'a, 'b, 'c, 'd, 'e> Facts<Query<'a, 'b, 'c, 'd, 'e>> for ChainFacts {
         {
             if i > 0 &&
                 likely_issued(t.chain.get(i), t.chain.get(i - 1)) &&
-                verify_signature(t.chain.get(i), t.chain.get(i - 1)) {
+                verify_signature(t.chain.get(i), t.chain.get(i - 1), t.allow_sha1) {
                 out.push(RuleX::fact("issuer", vec![ t.get_chain(i - 1).cert(), t.get_chain(i).cert() ]));
             }
 
@@ -163,7 +164,7 @@ impl<'a, 'b, 'c, 'd, 'e> Facts<Query<'a, 'b, 'c, 'd, 'e>> for RootFacts {
                     },
             {
                 if likely_issued(t.roots.get(i), t.chain.get(j)) &&
-                   verify_signature(t.roots.get(i), t.chain.get(j)) {
+                   verify_signature(t.roots.get(i), t.chain.get(j), t.allow_sha1) {
                     used = true;
                     out.push(RuleX::fact("issuer", vec![ t.get_chain(j).cert(), t.get_root(i).cert() ]));
                 }
@@ -210,7 +211,7 @@ impl ChainFacts {
 
                 if i > 0 &&
                     spec_likely_issued(t.chain[i], t.chain[i - 1]) &&
-                    spec_verify_signature(t.chain[i], t.chain[i - 1]) {
+                    spec_verify_signature(t.chain[i], t.chain[i - 1], t.allow_sha1) {
                     // If the current chain cert issued the last one, add an issuer fact about it
                     Some(seq![
                         spec_fact!("issuer", t.get_chain(i - 1).spec_cert(), t.get_chain(i).spec_cert()),
@@ -233,7 +234,7 @@ impl RootFacts {
         } else {
             // Check if roots[i] issued chain[j]
             if spec_likely_issued(t.roots[i], t.chain[j]) &&
-               spec_verify_signature(t.roots[i], t.chain[j]) {
+               spec_verify_signature(t.roots[i], t.chain[j], t.allow_sha1) {
                 // If so add an issuer fact
                 if_let! {
                     let Some(rest) = Self::spec_facts_helper_inner(t, i, j + 1);
@@ -295,7 +296,7 @@ impl Query<'_, '_, '_, '_, '_> {
         // Check that for each i, cert[i + 1] issued cert[i]
         for i in 0..self.chain.len() - 1 {
             if likely_issued(self.chain.get(i + 1), self.chain.get(i)) {
-                if verify_signature(self.chain.get(i + 1), self.chain.get(i)) {
+                if verify_signature(self.chain.get(i + 1), self.chain.get(i), self.allow_sha1) {
                     eprintln!("cert {} issued cert {}", i + 1, i);
                 } else {
                     eprintln!("cert {} issued cert {} (but signature error)", i + 1, i);
@@ -313,7 +314,7 @@ impl Query<'_, '_, '_, '_, '_> {
                 if likely_issued(root, chain_cert) {
                     used = true;
 
-                    if verify_signature(root, chain_cert) {
+                    if verify_signature(root, chain_cert, self.allow_sha1) {
                         eprintln!("root cert {} issued cert {}", i, j);
                     } else {
                         eprintln!("root cert {} issued cert {} (but signature error)", i, j);
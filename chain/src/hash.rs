@@ -9,11 +9,22 @@ verus! {
 /// TODO: specify this
 pub closed spec fn spec_to_hex_upper(data: Seq<u8>) -> Seq<char>;
 
+pub closed spec fn spec_sha1_digest(data: Seq<u8>) -> Seq<u8>;
 pub closed spec fn spec_sha224_digest(data: Seq<u8>) -> Seq<u8>;
 pub closed spec fn spec_sha256_digest(data: Seq<u8>) -> Seq<u8>;
 pub closed spec fn spec_sha384_digest(data: Seq<u8>) -> Seq<u8>;
 pub closed spec fn spec_sha512_digest(data: Seq<u8>) -> Seq<u8>;
 
+/// SHA-1 is only provided for verifying legacy signatures
+/// (libcrux does not support it, so this goes through AWS-LC)
+#[verifier::external_body]
+pub fn sha1_digest(data: &[u8]) -> (res: [u8; 20])
+    ensures res@ == spec_sha1_digest(data@)
+{
+    let digest = aws_lc_rs::digest::digest(&aws_lc_rs::digest::SHA1_FOR_LEGACY_USE_ONLY, data);
+    digest.as_ref().try_into().unwrap()
+}
+
 #[verifier::external_body]
 pub fn sha224_digest(data: &[u8]) -> (res: [u8; 28])
     ensures res@ == spec_sha224_digest(data@)
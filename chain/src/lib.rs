@@ -5,4 +5,5 @@ pub mod hash;
 pub mod facts;
 pub mod rsa;
 pub mod ecdsa;
+pub mod eddsa;
 pub mod utils;
@@ -45,6 +45,10 @@ struct Args {
     /// Override the current time with the given timestamp
     #[clap(short = 't', long)]
     override_time: Option<i64>,
+
+    /// Accept legacy SHA-1 RSA signatures when checking issuers
+    #[arg(long, default_value_t = false)]
+    allow_sha1: bool,
 }
 
 fn main_args(args: Args) -> Result<(), Error> {
@@ -74,6 +78,7 @@ fn main_args(args: Args) -> Result<(), Error> {
         chain: &VecDeep::from_vec(chain),
         domain: &args.domain.to_lowercase(),
         now: args.override_time.unwrap_or(chrono::Utc::now().timestamp()),
+        allow_sha1: args.allow_sha1,
     };
 
     if args.debug {
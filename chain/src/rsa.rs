@@ -1,11 +1,10 @@
-// RSA PKCS#1 v1.5 signature verification
+// RSA PKCS#1 v1.5 and RSASSA-PSS signature verification
 
 use vstd::prelude::*;
 
 use polyfill::{slice_eq, slice_drop_first, slice_skip, usize_into_u32};
 
-use parser::PolyfillEq;
-use parser::Combinator;
+use parser::{PolyfillEq, Combinator, Either, OptionDeep};
 use parser::asn1::ASN1;
 use parser::x509::*;
 
@@ -19,39 +18,68 @@ pub enum RSAError {
     SizeOverflow,
     DecryptError,
     PKCS1PaddingError,
+    PSSEncodingError,
     AlgorithmMismatch,
     UnsupportedAlgorithm,
     HashMismatch,
 }
 
+/// A public key allocated by `Hacl_RSAPSS_new_rsapss_load_pkey`,
+/// freed when dropped
+#[verifier::external_body]
+struct HaclPublicKey(*mut u64);
+
+#[verifier::external]
+impl Drop for HaclPublicKey {
+    fn drop(&mut self) {
+        hacl_free_pkey(self.0);
+    }
+}
+
+/// HACL* allocates the key with KRML_HOST_CALLOC,
+/// i.e. the C `calloc`, so it is released with `free`
+#[verifier::external_body]
+fn hacl_free_pkey(pkey: *mut u64)
+{
+    extern "C" {
+        fn free(ptr: *mut std::ffi::c_void);
+    }
+
+    unsafe { free(pkey as _) }
+}
+
+/// Returns `None` if the key is rejected by HACL*
+/// (e.g. `nb` does not have exactly `mod_bits` bits)
 #[verifier::external_body]
 fn hacl_new_rsapss_load_pkey(
     mod_bits: u32,
     e_bits: u32,
     nb: &[u8],
     eb: &[u8],
-) -> *mut u64
+) -> Option<HaclPublicKey>
 {
-    unsafe {
+    let pkey = unsafe {
         libcrux_hacl::Hacl_RSAPSS_new_rsapss_load_pkey(
             mod_bits,
             e_bits,
             nb.as_ptr() as _,
             eb.as_ptr() as _,
         )
-    }
+    };
+
+    if pkey.is_null() { None } else { Some(HaclPublicKey(pkey)) }
 }
 
 #[verifier::external_body]
 fn hacl_rsa_decrypt(
     mod_bits: u32,
     e_bits: u32,
-    pkey: *mut u64,
+    pkey: &HaclPublicKey,
     sig_len: u32,
     sig: &[u8],
 ) -> Option<Vec<u8>>
 {
-    // `sig_len` should be equal to `ceil(mod_bits / 2)`
+    // `sig_len` should be equal to `ceil(mod_bits / 8)`
     // (also checked in Hacl_RSAPSS_rsa_decrypt)
     let len = sig_len.try_into().ok()?;
     let mut decoded: Vec<u8> = vec![0; len];
@@ -60,7 +88,7 @@ fn hacl_rsa_decrypt(
         libcrux_hacl::Hacl_RSAPSS_rsa_decrypt(
             mod_bits,
             e_bits,
-            pkey,
+            pkey.0,
             sig_len,
             sig.as_ptr() as _,
             decoded.as_mut_ptr() as _,
@@ -72,38 +100,65 @@ fn hacl_rsa_decrypt(
     }
 }
 
-pub closed spec fn spec_rsa_pkcs1_v1_5_verify(
-    alg: SpecAlgorithmIdentifierValue,
-    pub_key: Seq<u8>,
-    sig: Seq<u8>,
-    msg: Seq<u8>,
-) -> bool;
+// Hash algorithms accepted by `Hacl_RSAPSS_rsapss_verify`
+// (values of `Spec_Hash_Definitions_hash_alg`)
+const HACL_SHA2_224: u8 = 0;
+const HACL_SHA2_256: u8 = 1;
+const HACL_SHA2_384: u8 = 2;
+const HACL_SHA2_512: u8 = 3;
 
-/// Verify RSA PKCS#1 v1.5 signature
-///
-/// `alg` specifies the signature + digest combination to be used
-/// we check that the signature algorithm should be RSA, and hash
-/// the message according to the digest algorithm.
-///
-/// `pub_key` is an ASN.1 encoded public key:
-/// ```
-///     RSAPublicKey ::= SEQUENCE {
-///         modulus            INTEGER, -- n
-///         publicExponent     INTEGER  -- e --
-///     }
-/// ```
-///
-/// `sig` is the signature encoded in big-endian (expected to be the same length as the modulus)
-/// `msg` is the message expected to be signed
+/// RSASSA-PSS verification (RFC 8017, 8.1.2) with `hash` used
+/// both as the message digest and in MGF1
 #[verifier::external_body]
-pub fn rsa_pkcs1_v1_5_verify(
-    alg: &AlgorithmIdentifierValue,
-    pub_key: &[u8],
+fn hacl_rsapss_verify(
+    hash: u8,
+    mod_bits: u32,
+    e_bits: u32,
+    pkey: &HaclPublicKey,
+    salt_len: u32,
     sig: &[u8],
     msg: &[u8],
-) -> (res: Result<(), RSAError>)
-    ensures
-        res.is_ok() == spec_rsa_pkcs1_v1_5_verify(alg@, pub_key@, sig@, msg@),
+) -> bool
+{
+    if sig.len() > u32::MAX as usize || msg.len() > u32::MAX as usize {
+        return false;
+    }
+
+    unsafe {
+        libcrux_hacl::Hacl_RSAPSS_rsapss_verify(
+            hash as _,
+            mod_bits,
+            e_bits,
+            pkey.0,
+            salt_len,
+            sig.len() as u32,
+            sig.as_ptr() as _,
+            msg.len() as u32,
+            msg.as_ptr() as _,
+        )
+    }
+}
+
+/// Number of bits in a big-endian unsigned integer
+/// with no leading zero byte
+#[verifier::external_body]
+fn be_bit_len(n: &[u8]) -> Option<usize>
+{
+    if n.len() == 0 || n[0] == 0 {
+        return None;
+    }
+
+    let top_bits = 8 - n[0].leading_zeros() as usize;
+    (n.len() - 1).checked_mul(8)?.checked_add(top_bits)
+}
+
+/// Parse the ASN.1 encoded public key (see `rsa_pkcs1_v1_5_verify`)
+/// and load it into HACL*
+///
+/// Returns the exact bit lengths of the modulus and the exponent
+/// (e.g. modBits in RFC 8017, 8.1.2), and the loaded key
+#[verifier::external_body]
+fn load_public_key(pub_key: &[u8]) -> Result<(u32, u32, HaclPublicKey), RSAError>
 {
     let (len, pub_key_parsed) = ASN1(RSAPublicKey).parse(pub_key)
         .or(Err(RSAError::InvalidPublicKey))?;
@@ -122,33 +177,82 @@ pub fn rsa_pkcs1_v1_5_verify(
     let e = if e.len() != 0 && e[0] == 0 { slice_drop_first(e) } else { &e };
 
     // Lengths in bits
-    let n_len = n.len().checked_mul(8).ok_or(RSAError::SizeOverflow)?;
-    let e_len = e.len().checked_mul(8).ok_or(RSAError::SizeOverflow)?;
+    let n_len = be_bit_len(n).ok_or(RSAError::InvalidPublicKey)?;
+    let e_len = be_bit_len(e).ok_or(RSAError::InvalidPublicKey)?;
 
     if n_len > u32::MAX as usize || e_len > u32::MAX as usize {
         return Err(RSAError::SizeOverflow);
     }
 
-    // Load the public key into hacl*
-    let hacl_pub_key = hacl_new_rsapss_load_pkey(
-        usize_into_u32(n_len), usize_into_u32(e_len), n, e,
-    );
+    let n_len = usize_into_u32(n_len);
+    let e_len = usize_into_u32(e_len);
+
+    let hacl_pub_key = hacl_new_rsapss_load_pkey(n_len, e_len, n, e)
+        .ok_or(RSAError::InvalidPublicKey)?;
+
+    Ok((n_len, e_len, hacl_pub_key))
+}
+
+/// Parse the ASN.1 encoded public key (see `rsa_pkcs1_v1_5_verify`)
+/// and apply the RSA verification primitive to `sig`
+#[verifier::external_body]
+fn rsa_decrypt(pub_key: &[u8], sig: &[u8]) -> Result<Vec<u8>, RSAError>
+{
+    let (n_len, e_len, hacl_pub_key) = load_public_key(pub_key)?;
 
-    if sig.len() > usize::MAX as usize {
+    if sig.len() > u32::MAX as usize {
         return Err(RSAError::SizeOverflow);
     }
 
     // Decrypt the signature using hacl*
-    let decoded = match hacl_rsa_decrypt(
-            usize_into_u32(n_len),
-            usize_into_u32(e_len),
-            hacl_pub_key,
-            sig.len() as u32,
-            sig,
-        ) {
-            Some(decoded) => decoded,
-            None => return Err(RSAError::DecryptError),
-        };
+    hacl_rsa_decrypt(
+        n_len,
+        e_len,
+        &hacl_pub_key,
+        sig.len() as u32,
+        sig,
+    ).ok_or(RSAError::DecryptError)
+}
+
+pub closed spec fn spec_rsa_pkcs1_v1_5_verify(
+    alg: SpecAlgorithmIdentifierValue,
+    pub_key: Seq<u8>,
+    sig: Seq<u8>,
+    msg: Seq<u8>,
+    allow_sha1: bool,
+) -> bool;
+
+/// Verify RSA PKCS#1 v1.5 signature
+///
+/// `alg` specifies the signature + digest combination to be used
+/// we check that the signature algorithm should be RSA, and hash
+/// the message according to the digest algorithm.
+///
+/// `pub_key` is an ASN.1 encoded public key:
+/// ```
+///     RSAPublicKey ::= SEQUENCE {
+///         modulus            INTEGER, -- n
+///         publicExponent     INTEGER  -- e --
+///     }
+/// ```
+///
+/// `sig` is the signature encoded in big-endian (expected to be the same length as the modulus)
+/// `msg` is the message expected to be signed
+///
+/// SHA-1 (`sha1WithRSAEncryption`) is rejected unless `allow_sha1` is set;
+/// MD2 and MD5 are never supported
+#[verifier::external_body]
+pub fn rsa_pkcs1_v1_5_verify(
+    alg: &AlgorithmIdentifierValue,
+    pub_key: &[u8],
+    sig: &[u8],
+    msg: &[u8],
+    allow_sha1: bool,
+) -> (res: Result<(), RSAError>)
+    ensures
+        res.is_ok() == spec_rsa_pkcs1_v1_5_verify(alg@, pub_key@, sig@, msg@, allow_sha1),
+{
+    let decoded = rsa_decrypt(pub_key, sig)?;
 
     // PKCS#1 v1.5 padding
     //     msg = 0x00 || 0x01 || PS || 0x00 || T
@@ -196,6 +300,8 @@ pub fn rsa_pkcs1_v1_5_verify(
         slice_eq(&digest_info_parsed.digest, &hash::sha384_digest(msg))
     } else if alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA512)) {
         slice_eq(&digest_info_parsed.digest, &hash::sha512_digest(msg))
+    } else if allow_sha1 && alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA1)) {
+        slice_eq(&digest_info_parsed.digest, &hash::sha1_digest(msg))
     } else {
         return Err(RSAError::UnsupportedAlgorithm);
     };
@@ -207,4 +313,70 @@ pub fn rsa_pkcs1_v1_5_verify(
     Ok(())
 }
 
+pub closed spec fn spec_rsa_pss_verify(
+    alg: SpecAlgorithmIdentifierValue,
+    pub_key: Seq<u8>,
+    sig: Seq<u8>,
+    msg: Seq<u8>,
+) -> bool;
+
+/// Verify RSASSA-PSS signature (RFC 8017, 8.1.2)
+///
+/// `alg` should be id-RSASSA-PSS with parameters specifying one of
+/// SHA-224, SHA-256, SHA-384 or SHA-512 as the hash function, MGF1
+/// with the same hash function, and the trailer field 1 (i.e. 0xbc).
+/// The default hash function SHA-1 is not supported.
+///
+/// `pub_key`, `sig`, and `msg` are the same as in `rsa_pkcs1_v1_5_verify`
+#[verifier::external_body]
+pub fn rsa_pss_verify(
+    alg: &AlgorithmIdentifierValue,
+    pub_key: &[u8],
+    sig: &[u8],
+    msg: &[u8],
+) -> (res: Result<(), RSAError>)
+    ensures
+        res.is_ok() == spec_rsa_pss_verify(alg@, pub_key@, sig@, msg@),
+{
+    let param = match &alg.param {
+        AlgorithmParamValue::RSASSAPSS(Either::Left(param)) => param,
+        _ => return Err(RSAError::AlgorithmMismatch),
+    };
+
+    let hash_id = match &param.hash_alg {
+        OptionDeep::Some(hash_alg) => &hash_alg.id,
+        OptionDeep::None => return Err(RSAError::UnsupportedAlgorithm),
+    };
+
+    match &param.mask_gen_alg {
+        OptionDeep::Some(mgf) if mgf.id.polyfill_eq(&oid!(MGF1)) && mgf.param.id.polyfill_eq(hash_id) => {}
+        _ => return Err(RSAError::UnsupportedAlgorithm),
+    }
+
+    if param.trailer_field != 1 {
+        return Err(RSAError::UnsupportedAlgorithm);
+    }
+
+    let hash = if hash_id.polyfill_eq(&oid!(SHA224)) {
+        HACL_SHA2_224
+    } else if hash_id.polyfill_eq(&oid!(SHA256)) {
+        HACL_SHA2_256
+    } else if hash_id.polyfill_eq(&oid!(SHA384)) {
+        HACL_SHA2_384
+    } else if hash_id.polyfill_eq(&oid!(SHA512)) {
+        HACL_SHA2_512
+    } else {
+        return Err(RSAError::UnsupportedAlgorithm);
+    };
+
+    let salt_len: u32 = param.salt_len.try_into().or(Err(RSAError::PSSEncodingError))?;
+    let (n_len, e_len, hacl_pub_key) = load_public_key(pub_key)?;
+
+    if !hacl_rsapss_verify(hash, n_len, e_len, &hacl_pub_key, salt_len, sig, msg) {
+        return Err(RSAError::PSSEncodingError);
+    }
+
+    Ok(())
+}
+
 }
@@ -11,6 +11,7 @@ use parser::OptionDeep::*;
 use crate::facts::*;
 use crate::rsa;
 use crate::ecdsa;
+use crate::eddsa;
 
 verus! {
 
@@ -54,9 +55,20 @@ pub open spec fn spec_same_name(a: SpecNameValue, b: SpecNameValue) -> bool {
 }
 
 /// Continuing the spec of same_name
+///
+/// RDNs are sets, so two RDNs match if they have the same number of
+/// attributes, each attribute in `a` matches some attribute in `b`,
+/// and vice versa (RFC 5280, 7.1). Checking both directions is needed
+/// in case of duplicate attributes, e.g. {x, x, y} vs {x, y, z}
 pub open spec fn spec_same_rdn(a: SpecRDNValue, b: SpecRDNValue) -> bool {
     &&& a.len() == b.len()
-    &&& forall |i| #![auto] 0 <= i < a.len() ==> spec_same_attr(a[i], b[i])
+    &&& forall |i| #![auto] 0 <= i < a.len() ==> spec_rdn_has_attr(b, a[i])
+    &&& forall |i| #![auto] 0 <= i < b.len() ==> spec_rdn_has_attr(a, b[i])
+}
+
+/// Some attribute in the RDN matches the given one
+pub open spec fn spec_rdn_has_attr(rdn: SpecRDNValue, attr: SpecAttributeTypeAndValueValue) -> bool {
+    exists |j| 0 <= j < rdn.len() && spec_same_attr(attr, #[trigger] rdn[j])
 }
 
 /// Continuing the spec of same_name
@@ -75,7 +87,8 @@ pub open spec fn spec_same_attr(a: SpecAttributeTypeAndValueValue, b: SpecAttrib
 /// we compare that:
 /// 1. subject.akid.key_id matches issuer.skid
 /// 2. (if exists) subject.akit.serial matches issuer.serial
-/// 3. TODO: subject.akid.auth_cert_issuer matches
+/// 3. (if exists) the first directory name in subject.akid.auth_cert_issuer
+///    matches issuer.issuer
 ///
 /// References:
 /// - RFC 2459, 4.2.1.1
@@ -86,12 +99,30 @@ pub open spec fn spec_check_auth_key_id(issuer: SpecCertificateValue, subject: S
             ==> spec_get_subject_key_id(issuer) matches Some(skid)
             ==> id =~= skid
         &&& akid.auth_cert_serial matches OptionDeep::Some(serial) ==> serial =~= issuer.cert.serial
-        // TODO auth_cert_issuer
+        &&& akid.auth_cert_issuer matches OptionDeep::Some(names)
+            ==> spec_get_first_dir_name(names) matches OptionDeep::Some(name)
+            ==> spec_same_name(name, issuer.cert.issuer)
     } else {
         true
     }
 }
 
+/// Get the first directory name in a list of GeneralNames
+/// (similar to what OpenSSL's X509_check_akid does)
+pub open spec fn spec_get_first_dir_name(names: Seq<SpecGeneralNameValue>) -> OptionDeep<SpecNameValue>
+    decreases names.len()
+{
+    if names.len() == 0 {
+        None
+    } else {
+        if let SpecGeneralNameValue::Directory(name) = names[0] {
+            Some(name)
+        } else {
+            spec_get_first_dir_name(names.drop_first())
+        }
+    }
+}
+
 /// Get the first extension with the given OID
 /// return (critical, param)
 pub open spec fn spec_get_extension(cert: SpecCertificateValue, oid: SpecObjectIdentifierValue) -> OptionDeep<SpecExtensionValue>
@@ -146,64 +177,139 @@ pub open spec fn spec_get_subject_key_id(cert: SpecCertificateValue) -> OptionDe
 }
 
 /// Verify the subject cert's signature using issuer's public key
-pub open spec fn spec_verify_signature(issuer: SpecCertificateValue, subject: SpecCertificateValue) -> bool
+/// (legacy SHA-1 RSA signatures are only accepted if `allow_sha1` is set)
+pub open spec fn spec_verify_signature(issuer: SpecCertificateValue, subject: SpecCertificateValue, allow_sha1: bool) -> bool
 {
     // Signature algorithm is consistent in the subject cert
     &&& subject.sig_alg =~= subject.cert.signature
 
     &&& ASN1(TBSCertificate)@.spec_serialize(subject.cert) matches Ok(tbs_cert)
 
+    &&& spec_verify_signature_with_key(
+        issuer.cert.subject_key,
+        subject.sig_alg,
+        BitStringValue::spec_bytes(subject.sig),
+        tbs_cert,
+        allow_sha1,
+    )
+}
+
+/// Verify the self-signature of a PKCS#10 certificate signing request
+/// using the public key in the request
+pub open spec fn spec_verify_csr_signature(csr: SpecCertificationRequestValue) -> bool
+{
+    &&& ASN1(CertificationRequestInfo)@.spec_serialize(csr.info) matches Ok(info)
+
+    &&& spec_verify_signature_with_key(
+        csr.info.subject_key,
+        csr.sig_alg,
+        BitStringValue::spec_bytes(csr.sig),
+        info,
+        false,
+    )
+}
+
+/// Verify the signature `sig` on `msg` with algorithm `sig_alg`
+/// using the public key `pub_key`
+pub open spec fn spec_verify_signature_with_key(
+    pub_key: SpecPublicKeyInfoValue,
+    sig_alg: SpecAlgorithmIdentifierValue,
+    sig: Seq<u8>,
+    msg: Seq<u8>,
+    allow_sha1: bool,
+) -> bool
+{
     // TODO: support more algorithms
-    &&& {
-        // RSA
-        ||| {
-            &&& issuer.cert.subject_key.alg.param is RSAEncryption
-            &&& {
-                ||| subject.sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA224)
-                ||| subject.sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA256)
-                ||| subject.sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA384)
-                ||| subject.sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA512)
-            }
-            &&& rsa::spec_rsa_pkcs1_v1_5_verify(
-                subject.sig_alg,
-                BitStringValue::spec_bytes(issuer.cert.subject_key.pub_key),
-                BitStringValue::spec_bytes(subject.sig),
-                tbs_cert,
-            )
+    // RSA
+    ||| {
+        &&& pub_key.alg.param is RSAEncryption
+        &&& {
+            ||| sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA224)
+            ||| sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA256)
+            ||| sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA384)
+            ||| sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA512)
+            ||| allow_sha1 && sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA1)
         }
+        &&& rsa::spec_rsa_pkcs1_v1_5_verify(
+            sig_alg,
+            BitStringValue::spec_bytes(pub_key.pub_key),
+            sig,
+            msg,
+            allow_sha1,
+        )
+    }
 
-        // ECDSA P-256
-        ||| {
-            &&& issuer.cert.subject_key.alg.param matches SpecAlgorithmParamValue::ECPublicKey(curve)
-            &&& curve == spec_oid!(EC_P_256)
-            &&& {
-                ||| subject.sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA256)
-                ||| subject.sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA384)
-                ||| subject.sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA512)
-            }
-            &&& ecdsa::spec_ecdsa_p256_verify(
-                subject.sig_alg,
-                BitStringValue::spec_bytes(issuer.cert.subject_key.pub_key),
-                BitStringValue::spec_bytes(subject.sig),
-                tbs_cert,
-            )
+    // RSASSA-PSS
+    ||| {
+        &&& {
+            ||| pub_key.alg.param is RSAEncryption
+            ||| pub_key.alg.param is RSASSAPSS
         }
+        &&& sig_alg.id == spec_oid!(RSASSA_PSS)
+        &&& rsa::spec_rsa_pss_verify(
+            sig_alg,
+            BitStringValue::spec_bytes(pub_key.pub_key),
+            sig,
+            msg,
+        )
+    }
 
-        // ECDSA P-384
-        ||| {
-            &&& issuer.cert.subject_key.alg.param matches SpecAlgorithmParamValue::ECPublicKey(curve)
-            &&& curve == spec_oid!(EC_P_384)
-            &&& {
-                ||| subject.sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA256)
-                ||| subject.sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA384)
-            }
-            &&& ecdsa::spec_ecdsa_p384_verify(
-                subject.sig_alg,
-                BitStringValue::spec_bytes(issuer.cert.subject_key.pub_key),
-                BitStringValue::spec_bytes(subject.sig),
-                tbs_cert,
-            )
+    // ECDSA P-256
+    ||| {
+        &&& pub_key.alg.param matches SpecAlgorithmParamValue::ECPublicKey(curve)
+        &&& curve == spec_oid!(EC_P_256)
+        &&& {
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA256)
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA384)
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA512)
         }
+        &&& ecdsa::spec_ecdsa_p256_verify(
+            sig_alg,
+            BitStringValue::spec_bytes(pub_key.pub_key),
+            sig,
+            msg,
+        )
+    }
+
+    // ECDSA P-384
+    ||| {
+        &&& pub_key.alg.param matches SpecAlgorithmParamValue::ECPublicKey(curve)
+        &&& curve == spec_oid!(EC_P_384)
+        &&& {
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA256)
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA384)
+        }
+        &&& ecdsa::spec_ecdsa_p384_verify(
+            sig_alg,
+            BitStringValue::spec_bytes(pub_key.pub_key),
+            sig,
+            msg,
+        )
+    }
+
+    // ECDSA P-521
+    ||| {
+        &&& pub_key.alg.param matches SpecAlgorithmParamValue::ECPublicKey(curve)
+        &&& curve == spec_oid!(EC_P_521)
+        &&& sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA512)
+        &&& ecdsa::spec_ecdsa_p521_verify(
+            sig_alg,
+            BitStringValue::spec_bytes(pub_key.pub_key),
+            sig,
+            msg,
+        )
+    }
+
+    // EdDSA (Ed25519)
+    ||| {
+        &&& pub_key.alg.param is Ed25519
+        &&& sig_alg.id == spec_oid!(ED25519)
+        &&& eddsa::spec_ed25519_verify(
+            sig_alg,
+            BitStringValue::spec_bytes(pub_key.pub_key),
+            sig,
+            msg,
+        )
     }
 }
 
@@ -5,7 +5,7 @@ use std::io::{BufRead, BufReader};
 
 use base64::{Engine, prelude::BASE64_STANDARD};
 
-use parser::{x509, ParseError, Combinator};
+use parser::{asn1::ASN1, x509, ParseError, Combinator};
 use crate::error::Error;
 
 verus! {
@@ -16,6 +16,38 @@ verus! {
         }
         Ok(cert)
     }
+
+    pub fn parse_x509_crl<'a>(bytes: &'a [u8]) -> Result<x509::CertificateListValue<'a>, ParseError> {
+        let (n, crl) = x509::CertificateList.parse(bytes)?;
+        if n != bytes.len() {
+            return Err(ParseError::Other("trailing bytes in CRL".to_string()));
+        }
+        Ok(crl)
+    }
+
+    pub fn parse_x509_csr<'a>(bytes: &'a [u8]) -> Result<x509::CertificationRequestValue<'a>, ParseError> {
+        let (n, csr) = x509::CertificationRequest.parse(bytes)?;
+        if n != bytes.len() {
+            return Err(ParseError::Other("trailing bytes in CSR".to_string()));
+        }
+        Ok(csr)
+    }
+
+    pub fn parse_x509_attr_cert<'a>(bytes: &'a [u8]) -> Result<x509::AttributeCertificateValue<'a>, ParseError> {
+        let (n, ac) = x509::AttributeCertificate.parse(bytes)?;
+        if n != bytes.len() {
+            return Err(ParseError::Other("trailing bytes in attribute certificate".to_string()));
+        }
+        Ok(ac)
+    }
+
+    pub fn parse_ocsp_response<'a>(bytes: &'a [u8]) -> Result<x509::OCSPResponseValue<'a>, ParseError> {
+        let (n, resp) = ASN1(x509::OCSPResponse).parse(bytes)?;
+        if n != bytes.len() {
+            return Err(ParseError::Other("trailing bytes in OCSP response".to_string()));
+        }
+        Ok(resp)
+    }
 }
 
 pub fn read_pem_file_as_bytes(path: &str) -> Result<Vec<Vec<u8>>, Error> {
@@ -10,6 +10,7 @@ use crate::facts::*;
 use crate::error::*;
 use crate::rsa;
 use crate::ecdsa;
+use crate::eddsa;
 
 verus! {
 
@@ -43,6 +44,15 @@ pub fn check_auth_key_id(issuer: &CertificateValue, subject: &CertificateValue)
             }
         }
 
+        // Check issuer name
+        if let Some(names) = &akid.auth_cert_issuer {
+            if let Some(name) = get_first_dir_name(names) {
+                if !same_name(name, &issuer.get().cert.get().issuer) {
+                    return false;
+                }
+            }
+        }
+
         return true;
     }
 
@@ -77,6 +87,29 @@ pub fn get_extension<'a, 'b>(cert: &'b CertificateValue<'a>, oid: &ObjectIdentif
     }
 }
 
+pub fn get_first_dir_name<'a, 'b>(names: &'b GeneralNamesValue<'a>) -> (res: OptionDeep<&'b NameValue<'a>>)
+    ensures res@ == spec_get_first_dir_name(names@)
+{
+    let len = names.len();
+
+    assert(names@.skip(0) == names@);
+
+    for i in 0..len
+        invariant
+            len == names@.len(),
+            spec_get_first_dir_name(names@)
+                == spec_get_first_dir_name(names@.skip(i as int)),
+    {
+        if let GeneralNameValue::Directory(name) = names.get(i) {
+            return Some(name);
+        }
+
+        assert(names@.skip(i as int).drop_first() == names@.skip(i + 1));
+    }
+
+    None
+}
+
 pub fn get_auth_key_id<'a, 'b>(cert: &'b CertificateValue<'a>) -> (res: OptionDeep<&'b AuthorityKeyIdentifierValue<'a>>)
     ensures res@ == spec_get_auth_key_id(cert@)
 {
@@ -135,9 +168,21 @@ pub fn same_rdn(a: &RDNValue, b: &RDNValue) -> (res: bool)
         invariant
             len == a@.len(),
             a@.len() == b@.len(),
-            forall |j| #![auto] 0 <= j < i ==> spec_same_attr(a@[j], b@[j]),
+            forall |j| #![auto] 0 <= j < i ==> spec_rdn_has_attr(b@, a@[j]),
     {
-        if !same_attr(a.get(i), b.get(i)) {
+        if !rdn_has_attr(b, a.get(i)) {
+            return false;
+        }
+    }
+
+    for i in 0..len
+        invariant
+            len == b@.len(),
+            a@.len() == b@.len(),
+            forall |j| #![auto] 0 <= j < a@.len() ==> spec_rdn_has_attr(b@, a@[j]),
+            forall |j| #![auto] 0 <= j < i ==> spec_rdn_has_attr(a@, b@[j]),
+    {
+        if !rdn_has_attr(a, b.get(i)) {
             return false;
         }
     }
@@ -145,19 +190,38 @@ pub fn same_rdn(a: &RDNValue, b: &RDNValue) -> (res: bool)
     true
 }
 
+/// Exec version of spec_rdn_has_attr
+pub fn rdn_has_attr(rdn: &RDNValue, attr: &AttributeTypeAndValueValue) -> (res: bool)
+    ensures res == spec_rdn_has_attr(rdn@, attr@)
+{
+    let len = rdn.len();
+    for i in 0..len
+        invariant
+            len == rdn@.len(),
+            forall |j| 0 <= j < i ==> !spec_same_attr(attr@, #[trigger] rdn@[j]),
+    {
+        if same_attr(attr, rdn.get(i)) {
+            return true;
+        }
+    }
+
+    false
+}
+
 pub fn same_attr(a: &AttributeTypeAndValueValue, b: &AttributeTypeAndValueValue) -> (res: bool)
     ensures res == spec_same_attr(a@, b@)
 {
     a.typ.polyfill_eq(&b.typ) && match (&a.value, &b.value) {
         (DirectoryStringValue::PrintableString(a), DirectoryStringValue::PrintableString(b)) |
         (DirectoryStringValue::UTF8String(a), DirectoryStringValue::UTF8String(b)) |
-        (DirectoryStringValue::IA5String(a), DirectoryStringValue::IA5String(b)) =>
+        (DirectoryStringValue::IA5String(a), DirectoryStringValue::IA5String(b)) |
+        (DirectoryStringValue::NumericString(a), DirectoryStringValue::NumericString(b)) =>
             str_eq_str(a, b),
 
         (DirectoryStringValue::TeletexString(a), DirectoryStringValue::TeletexString(b)) |
         (DirectoryStringValue::BMPString(a), DirectoryStringValue::BMPString(b)) |
         (DirectoryStringValue::UniversalString(a), DirectoryStringValue::UniversalString(b)) =>
-            a.polyfill_eq(b),
+            str_eq_str(a.as_str(), b.as_str()),
 
         (DirectoryStringValue::Unreachable, DirectoryStringValue::Unreachable) => true,
 
@@ -165,8 +229,8 @@ pub fn same_attr(a: &AttributeTypeAndValueValue, b: &AttributeTypeAndValueValue)
     }
 }
 
-pub fn verify_signature(issuer: &CertificateValue, subject: &CertificateValue) -> (res: bool)
-    ensures res == spec_verify_signature(issuer@, subject@)
+pub fn verify_signature(issuer: &CertificateValue, subject: &CertificateValue, allow_sha1: bool) -> (res: bool)
+    ensures res == spec_verify_signature(issuer@, subject@, allow_sha1)
 {
     if !subject.get().sig_alg.polyfill_eq(&subject.get().cert.get().signature) {
         return false;
@@ -174,36 +238,90 @@ pub fn verify_signature(issuer: &CertificateValue, subject: &CertificateValue) -
 
     let tbs_cert = subject.get().cert.serialize();
 
-    let sig_alg = &subject.get().sig_alg;
-    let pub_key = issuer.get().cert.get().subject_key.pub_key.bytes();
-    let sig = subject.get().sig.bytes();
+    verify_signature_with_key(
+        &issuer.get().cert.get().subject_key,
+        &subject.get().sig_alg,
+        subject.get().sig.bytes(),
+        tbs_cert,
+        allow_sha1,
+    )
+}
+
+pub fn verify_csr_signature(csr: &CertificationRequestValue) -> (res: bool)
+    ensures res == spec_verify_csr_signature(csr@)
+{
+    let info = csr.get().info.serialize();
+
+    verify_signature_with_key(
+        &csr.get().info.get().subject_key,
+        &csr.get().sig_alg,
+        csr.get().sig.bytes(),
+        info,
+        false,
+    )
+}
 
-    match &issuer.get().cert.get().subject_key.alg.param {
-        // RSA PKCS#1 v1.5
+pub fn verify_signature_with_key(
+    pub_key: &PublicKeyInfoValue,
+    sig_alg: &AlgorithmIdentifierValue,
+    sig: &[u8],
+    msg: &[u8],
+    allow_sha1: bool,
+) -> (res: bool)
+    ensures res == spec_verify_signature_with_key(pub_key@, sig_alg@, sig@, msg@, allow_sha1)
+{
+    let pub_key_bytes = pub_key.pub_key.bytes();
+
+    match &pub_key.alg.param {
+        // RSA PKCS#1 v1.5 and RSASSA-PSS
         AlgorithmParamValue::RSAEncryption(..) => {
-            if  subject.get().sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA224)) ||
-                subject.get().sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA256)) ||
-                subject.get().sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA384)) ||
-                subject.get().sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA512)) {
-                return rsa::rsa_pkcs1_v1_5_verify(sig_alg, pub_key, sig, tbs_cert).is_ok();
+            if  sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA224)) ||
+                sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA256)) ||
+                sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA384)) ||
+                sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA512)) ||
+                (allow_sha1 && sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA1))) {
+                return rsa::rsa_pkcs1_v1_5_verify(sig_alg, pub_key_bytes, sig, msg, allow_sha1).is_ok();
+            }
+
+            if sig_alg.id.polyfill_eq(&oid!(RSASSA_PSS)) {
+                return rsa::rsa_pss_verify(sig_alg, pub_key_bytes, sig, msg).is_ok();
             }
         }
 
-        // ECDSA P-256 and P-384
+        // RSA key restricted to RSASSA-PSS
+        AlgorithmParamValue::RSASSAPSS(..) => {
+            if sig_alg.id.polyfill_eq(&oid!(RSASSA_PSS)) {
+                return rsa::rsa_pss_verify(sig_alg, pub_key_bytes, sig, msg).is_ok();
+            }
+        }
+
+        // ECDSA P-256, P-384 and P-521
         AlgorithmParamValue::ECPublicKey(curve) => {
             if curve.polyfill_eq(&oid!(EC_P_256)) && (
-                subject.get().sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA256)) ||
-                subject.get().sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA384)) ||
-                subject.get().sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA512))
+                sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA256)) ||
+                sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA384)) ||
+                sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA512))
             ) {
-                return ecdsa::ecdsa_p256_verify(sig_alg, pub_key, sig, tbs_cert).is_ok();
+                return ecdsa::ecdsa_p256_verify(sig_alg, pub_key_bytes, sig, msg).is_ok();
             }
 
             if curve.polyfill_eq(&oid!(EC_P_384)) && (
-                subject.get().sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA256)) ||
-                subject.get().sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA384))
+                sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA256)) ||
+                sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA384))
             ) {
-                return ecdsa::ecdsa_p384_verify(sig_alg, pub_key, sig, tbs_cert).is_ok();
+                return ecdsa::ecdsa_p384_verify(sig_alg, pub_key_bytes, sig, msg).is_ok();
+            }
+
+            if curve.polyfill_eq(&oid!(EC_P_521)) &&
+                sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA512)) {
+                return ecdsa::ecdsa_p521_verify(sig_alg, pub_key_bytes, sig, msg).is_ok();
+            }
+        }
+
+        // EdDSA (Ed25519)
+        AlgorithmParamValue::Ed25519(..) => {
+            if sig_alg.id.polyfill_eq(&oid!(ED25519)) {
+                return eddsa::ed25519_verify(sig_alg, pub_key_bytes, sig, msg).is_ok();
             }
         }
 
@@ -257,3 +375,82 @@ pub fn valid_domain<'a, 'b, 'c, 'd, 'e, C: Compiled, E>(
 }
 
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::*;
+
+    /// Encode an RDN with the given commonName's
+    fn rdn_bytes(cns: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0x31, (cns.len() * 10) as u8];
+        for cn in cns {
+            bytes.extend_from_slice(&[0x30, 0x08, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x01, *cn]);
+        }
+        bytes
+    }
+
+    #[test]
+    fn same_rdn_duplicates() {
+        let a = rdn_bytes(b"xxy");
+        let b = rdn_bytes(b"xyz");
+        let c = rdn_bytes(b"xyy");
+
+        let a = ASN1(RDN).parse(&a).unwrap().1;
+        let b = ASN1(RDN).parse(&b).unwrap().1;
+        let c = ASN1(RDN).parse(&c).unwrap().1;
+
+        assert!(same_rdn(&a, &a));
+        assert!(!same_rdn(&a, &b));
+        assert!(!same_rdn(&b, &a));
+
+        // Equal as sets
+        assert!(same_rdn(&a, &c));
+    }
+
+    #[test]
+    fn rsa_pss_signature() {
+        // Leaf signed with RSASSA-PSS (SHA-256, salt length 32) by a CA
+        // with a 2049-bit modulus, self-signed with RSASSA-PSS (SHA-384,
+        // salt length 48), so that emLen is one byte shorter than the signature
+        let chain = read_pem_file_as_bytes("tests/chains/rsa-pss.pem").unwrap();
+        let leaf = parse_x509_certificate(&chain[0]).unwrap();
+        let ca = parse_x509_certificate(&chain[1]).unwrap();
+
+        assert!(verify_signature(&ca, &ca, false));
+        assert!(verify_signature(&ca, &leaf, false));
+        assert!(!verify_signature(&leaf, &leaf, false));
+
+        // Flip the last bit of the signature
+        let mut tampered = chain[0].clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+
+        let tampered = parse_x509_certificate(&tampered).unwrap();
+        assert!(!verify_signature(&ca, &tampered, false));
+    }
+
+    #[test]
+    fn csr_signature() {
+        // ECDSA P-256 with SHA-256, CN=test.example
+        let bytes = std::fs::read("tests/csrs/test-example.der").unwrap();
+        let csr = parse_x509_csr(&bytes).unwrap();
+        assert!(verify_csr_signature(&csr));
+
+        // Flip the last bit of the signature
+        let mut tampered = bytes.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+
+        let csr = parse_x509_csr(&tampered).unwrap();
+        assert!(!verify_csr_signature(&csr));
+
+        // Change the subject to CN=uest.example
+        let mut tampered = bytes.clone();
+        let pos = tampered.windows(12).position(|w| w == b"test.example").unwrap();
+        tampered[pos] = b'u';
+
+        let csr = parse_x509_csr(&tampered).unwrap();
+        assert!(!verify_csr_signature(&csr));
+    }
+}
@@ -0,0 +1,427 @@
+use vstd::prelude::*;
+
+use polyfill::*;
+
+use crate::common::*;
+
+use super::len::*;
+use super::octet_string::*;
+use super::tag::*;
+
+verus! {
+
+/// Combinator for an arbitrary DER TLV tuple: a tag followed by
+/// length-prefixed content octets, which are not interpreted
+///
+/// The content of a constructed value is itself a sequence of
+/// TLVs, so a tree of the entire value can be obtained by applying
+/// AnyTLV again to the content (see TLVNode)
+#[derive(Debug, View)]
+pub struct AnyTLV;
+
+pub type SpecAnyTLVValue = (TagValue, Seq<u8>);
+pub type AnyTLVValue<'a> = (TagValue, &'a [u8]);
+pub type AnyTLVValueOwned = (TagValue, Vec<u8>);
+
+impl SpecCombinator for AnyTLV {
+    type SpecResult = SpecAnyTLVValue;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        (ASN1Tag, OctetString).spec_parse(s)
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        (ASN1Tag, OctetString).spec_parse_wf(s)
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        (ASN1Tag, OctetString).spec_serialize(v)
+    }
+}
+
+impl AnyTLV {
+    /// The content octets are the suffix of the parsed
+    /// octets, after a non-empty header (tag and length)
+    pub proof fn lemma_parse_content(&self, s: Seq<u8>)
+        requires self.spec_parse(s) is Ok
+        ensures
+            self.spec_parse(s) matches Ok((n, (_, content))) ==> {
+                &&& content.len() < n <= s.len()
+                &&& content == s.subrange(n - content.len(), n as int)
+            },
+    {
+        ASN1Tag.spec_parse_wf(s);
+
+        if let Ok((n1, _)) = ASN1Tag.spec_parse(s) {
+            let rest = s.subrange(n1 as int, s.len() as int);
+            Length.spec_parse_wf(rest);
+
+            if let Ok((n2, len)) = Length.spec_parse(rest) {
+                assert(rest.subrange(n2 as int, rest.len() as int).subrange(0, len as int)
+                    =~= s.subrange(n1 + n2, n1 + n2 + len));
+            }
+        }
+    }
+}
+
+impl SecureSpecCombinator for AnyTLV {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        (ASN1Tag, OctetString).theorem_serialize_parse_roundtrip(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        (ASN1Tag, OctetString).theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        (ASN1Tag, OctetString).lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl Combinator for AnyTLV {
+    type Result<'a> = AnyTLVValue<'a>;
+    type Owned = AnyTLVValueOwned;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        (ASN1Tag, OctetString).parse(s)
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        (ASN1Tag, OctetString).serialize(v, data, pos)
+    }
+}
+
+/// Maximum nesting depth when building a TLVNode tree
+pub const TLV_MAX_DEPTH: usize = 64;
+
+/// A node in an untyped DER tree, mainly for inspecting
+/// values that fail to parse or are kept opaque (e.g. Tail)
+///
+/// Each node is built by applying AnyTLV to (a suffix of) the
+/// content of its parent, see TLVNode::wf for the exact relation
+#[derive(Debug)]
+pub struct TLVNode<'a> {
+    /// Offset of the TLV relative to the root
+    pub offset: usize,
+
+    /// Length of the tag and length octets
+    pub header_len: usize,
+
+    pub tag: TagValue,
+
+    /// The entire TLV encoding
+    pub raw: &'a [u8],
+
+    /// Content octets
+    pub content: &'a [u8],
+
+    /// Sub-TLVs of a constructed value, or of a primitive OCTET STRING
+    /// or BIT STRING whose content happens to be valid DER
+    /// (e.g. extension values), in which case encapsulated is set
+    pub children: Vec<TLVNode<'a>>,
+    pub encapsulated: bool,
+}
+
+impl<'a> TLVNode<'a> {
+    /// Concatenation of the encodings of a sequence of nodes
+    pub open spec fn spec_concat_raw(nodes: Seq<TLVNode<'a>>) -> Seq<u8>
+        decreases nodes.len()
+    {
+        if nodes.len() == 0 {
+            seq![]
+        } else {
+            Self::spec_concat_raw(nodes.drop_last()) + nodes.last().raw@
+        }
+    }
+
+    /// Offset of the children in the content octets
+    /// (skipping the unused bits octet of a BIT STRING)
+    pub open spec fn spec_children_start(&self) -> usize
+    {
+        if self.encapsulated && self.tag == tag_of!(BIT_STRING) { 1 } else { 0 }
+    }
+
+    /// The tree rooted at this node is well-formed at the given depth:
+    /// - raw is the AnyTLV encoding of the tag and content octets,
+    ///   and the content follows a header of header_len octets
+    /// - the encodings of the children concatenate to exactly the content
+    ///   of a constructed value, or to the content (after the unused bits
+    ///   octet of a BIT STRING) of an encapsulating OCTET STRING or BIT STRING
+    /// - the children are well-formed one level deeper, at consecutive offsets
+    /// - no node is deeper than TLV_MAX_DEPTH
+    pub open spec fn wf(&self, depth: usize) -> bool
+        decreases TLV_MAX_DEPTH + 1 - depth
+    {
+        if depth > TLV_MAX_DEPTH {
+            false
+        } else {
+            &&& AnyTLV.spec_serialize((self.tag, self.content@)) == Ok::<_, ()>(self.raw@)
+            &&& 0 < self.header_len
+            &&& self.header_len + self.content@.len() == self.raw@.len()
+            &&& self.content@ == self.raw@.skip(self.header_len as int)
+
+            &&& if self.tag.form is Constructed {
+                &&& !self.encapsulated
+                &&& Self::spec_concat_raw(self.children@) == self.content@
+            } else if self.encapsulated {
+                &&& self.tag == tag_of!(OCTET_STRING) ||
+                    (self.tag == tag_of!(BIT_STRING) && self.content@.len() > 0 && self.content@[0] == 0)
+                &&& self.children@.len() != 0
+                &&& Self::spec_concat_raw(self.children@) == self.content@.skip(self.spec_children_start() as int)
+            } else {
+                self.children@.len() == 0
+            }
+
+            &&& forall |i: int| 0 <= i < self.children@.len() ==> {
+                &&& #[trigger] self.children@[i].wf((depth + 1) as usize)
+                &&& self.children@[i].offset ==
+                    self.offset + self.header_len + self.spec_children_start() +
+                    Self::spec_concat_raw(self.children@.take(i)).len()
+            }
+        }
+    }
+
+    /// Parse a single TLV (with no trailing bytes) into a tree
+    pub fn parse(bytes: &'a [u8]) -> (res: Result<TLVNode<'a>, ParseError>)
+        ensures
+            res matches Ok(node) ==> {
+                &&& node.wf(0)
+                &&& node.offset == 0
+                &&& node.raw@ == bytes@
+            },
+    {
+        let (n, node) = Self::parse_at(bytes, 0, 0)?;
+        if n != bytes.len() {
+            return Err(ParseError::Other("trailing bytes after TLV".to_string()));
+        }
+
+        assert(bytes@.take(n as int) =~= bytes@);
+
+        Ok(node)
+    }
+
+    fn parse_at(bytes: &'a [u8], offset: usize, depth: usize) -> (res: Result<(usize, TLVNode<'a>), ParseError>)
+        requires
+            offset + bytes@.len() <= usize::MAX,
+            depth <= TLV_MAX_DEPTH + 1,
+        ensures
+            res matches Ok((n, node)) ==> {
+                &&& 0 < n <= bytes@.len()
+                &&& node.wf(depth)
+                &&& node.offset == offset
+                &&& node.raw@ == bytes@.take(n as int)
+            },
+        decreases TLV_MAX_DEPTH + 1 - depth, 0int
+    {
+        if depth > TLV_MAX_DEPTH {
+            return Err(ParseError::Other("TLV nesting too deep".to_string()));
+        }
+
+        let (n, (tag, content)) = AnyTLV.parse(bytes)?;
+
+        proof {
+            AnyTLV.lemma_parse_content(bytes@);
+            AnyTLV.theorem_parse_serialize_roundtrip(bytes@);
+        }
+
+        let header_len = n - content.len();
+
+        let mut node = TLVNode {
+            offset,
+            header_len,
+            tag,
+            raw: slice_take(bytes, n),
+            content,
+            children: Vec::new(),
+            encapsulated: false,
+        };
+
+        assert(node.raw@.skip(header_len as int) =~= node.content@);
+
+        match node.tag.form {
+            TagForm::Constructed => {
+                node.children = Self::parse_all(content, offset + header_len, depth + 1)?;
+            }
+
+            TagForm::Primitive => {
+                // Try to decode encapsulated DER, but fall back to
+                // a leaf if the content is not valid DER
+                let is_octet_string = node.tag.clone().eq(tag_of!(OCTET_STRING));
+                let is_bit_string = node.tag.clone().eq(tag_of!(BIT_STRING)) && content.len() > 0 && content[0] == 0;
+
+                if is_octet_string || is_bit_string {
+                    let start: usize = if is_bit_string { 1 } else { 0 };
+
+                    if let Ok(children) = Self::parse_all(slice_skip(content, start), offset + header_len + start, depth + 1) {
+                        if children.len() != 0 {
+                            node.children = children;
+                            node.encapsulated = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok((n, node))
+    }
+
+    /// Parse a sequence of TLVs filling the entire buffer
+    fn parse_all(bytes: &'a [u8], offset: usize, depth: usize) -> (res: Result<Vec<TLVNode<'a>>, ParseError>)
+        requires
+            offset + bytes@.len() <= usize::MAX,
+            depth <= TLV_MAX_DEPTH + 1,
+        ensures
+            res matches Ok(children) ==> {
+                &&& Self::spec_concat_raw(children@) == bytes@
+                &&& forall |i: int| 0 <= i < children@.len() ==> {
+                    &&& #[trigger] children@[i].wf(depth)
+                    &&& children@[i].offset == offset + Self::spec_concat_raw(children@.take(i)).len()
+                }
+            },
+        decreases TLV_MAX_DEPTH + 1 - depth, 1int
+    {
+        let mut children: Vec<TLVNode<'a>> = Vec::new();
+        let mut i = 0;
+
+        assert(bytes@.take(0) =~= Self::spec_concat_raw(children@));
+
+        while i < bytes.len()
+            invariant
+                i <= bytes@.len(),
+                offset + bytes@.len() <= usize::MAX,
+                depth <= TLV_MAX_DEPTH + 1,
+                Self::spec_concat_raw(children@) == bytes@.take(i as int),
+                forall |j: int| 0 <= j < children@.len() ==> {
+                    &&& #[trigger] children@[j].wf(depth)
+                    &&& children@[j].offset == offset + Self::spec_concat_raw(children@.take(j)).len()
+                },
+            decreases bytes@.len() - i
+        {
+            let (n, child) = Self::parse_at(slice_skip(bytes, i), offset + i, depth)?;
+
+            let ghost prev = children@;
+            children.push(child);
+
+            proof {
+                assert(children@.drop_last() =~= prev);
+                assert(bytes@.take(i as int) + bytes@.skip(i as int).take(n as int) =~= bytes@.take(i + n));
+
+                assert forall |j: int| 0 <= j < children@.len() implies {
+                    &&& #[trigger] children@[j].wf(depth)
+                    &&& children@[j].offset == offset + Self::spec_concat_raw(children@.take(j)).len()
+                } by {
+                    if j < prev.len() {
+                        assert(children@.take(j) =~= prev.take(j));
+                    } else {
+                        assert(children@.take(j) =~= prev);
+                    }
+                }
+            }
+
+            i += n;
+        }
+
+        assert(bytes@.take(bytes@.len() as int) =~= bytes@);
+
+        Ok(children)
+    }
+}
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    verus! {
+        /// Check that all trait bounds and preconditions are satisfied
+        #[test]
+        fn is_combinator() {
+            let _ = AnyTLV.parse(&[]);
+        }
+    }
+
+    #[test]
+    fn any_tlv() {
+        let (n, (tag, content)) = AnyTLV.parse(&[ 0x9f, 0x64, 0x02, 0x01, 0x02, 0xff ]).unwrap();
+        assert_eq!(n, 5);
+        assert!(tag.eq(tag_of!(IMPLICIT 100)));
+        assert_eq!(content, [ 0x01, 0x02 ]);
+
+        // Non-minimal length
+        assert!(AnyTLV.parse(&[ 0x04, 0x81, 0x01, 0x00 ]).is_err());
+    }
+
+    #[test]
+    fn tree() {
+        // SEQUENCE { INTEGER 1, OCTET STRING { NULL }, [0] { BOOLEAN TRUE } }
+        let bytes = [
+            0x30, 0x0c,
+                0x02, 0x01, 0x01,
+                0x04, 0x02, 0x05, 0x00,
+                0xa0, 0x03, 0x01, 0x01, 0xff,
+        ];
+        let root = TLVNode::parse(&bytes).unwrap();
+
+        assert!(root.tag.clone().eq(tag_of!(SEQUENCE)));
+        assert_eq!(root.children.len(), 3);
+
+        assert_eq!(root.children[0].offset, 2);
+        assert!(root.children[0].children.is_empty());
+
+        assert_eq!(root.children[1].offset, 5);
+        assert!(root.children[1].encapsulated);
+        assert_eq!(root.children[1].children[0].offset, 7);
+
+        assert_eq!(root.children[2].header_len, 2);
+        assert_eq!(root.children[2].children[0].content, [ 0xff ]);
+    }
+
+    #[test]
+    fn encapsulated_bit_string() {
+        // BIT STRING { NULL }, and a BIT STRING with unused bits (not decoded)
+        let root = TLVNode::parse(&[ 0x03, 0x03, 0x00, 0x05, 0x00 ]).unwrap();
+        assert!(root.encapsulated);
+        assert_eq!(root.children[0].offset, 3);
+
+        let root = TLVNode::parse(&[ 0x03, 0x03, 0x01, 0x05, 0x00 ]).unwrap();
+        assert!(!root.encapsulated);
+        assert!(root.children.is_empty());
+    }
+
+    #[test]
+    fn depth() {
+        // A chain of n nested empty SEQUENCEs
+        fn nested(n: usize) -> Vec<u8> {
+            let mut bytes = vec![];
+            for _ in 0..n {
+                let mut outer = if bytes.len() < 0x80 {
+                    vec![ 0x30, bytes.len() as u8 ]
+                } else {
+                    vec![ 0x30, 0x81, bytes.len() as u8 ]
+                };
+                outer.extend(bytes);
+                bytes = outer;
+            }
+            bytes
+        }
+
+        assert!(TLVNode::parse(&nested(TLV_MAX_DEPTH + 1)).is_ok());
+        assert!(TLVNode::parse(&nested(TLV_MAX_DEPTH + 2)).is_err());
+
+        // Trailing bytes
+        assert!(TLVNode::parse(&[ 0x05, 0x00, 0x00 ]).is_err());
+    }
+}
@@ -138,6 +138,14 @@ impl Base128UInt {
         }
     }
 
+    /// The encoding of a UInt takes at most 10 bytes
+    pub proof fn lemma_spec_serialize_len_bound(v: UInt)
+        ensures Self::spec_serialize_helper(v, true).len() <= 10
+    {
+        reveal_with_fuel(Base128UInt::spec_serialize_helper, 11);
+        assert(v >> 7 >> 7 >> 7 >> 7 >> 7 >> 7 >> 7 >> 7 >> 7 >> 7 == 0) by (bit_vector);
+    }
+
     pub open spec fn find_first_arc(s: Seq<u8>) -> Option<int>
         decreases s.len()
     {
@@ -0,0 +1,794 @@
+// A lenient BER profile implemented as a BER-to-DER transcoder.
+//
+// The combinators in this module tree only accept DER. To handle
+// inputs that are valid BER but not DER (e.g. some certificates in
+// CT logs), we first rewrite the input into DER, recording every
+// relaxation that was needed, and then run the usual (verified) DER
+// combinators on the result.
+//
+// The relaxed profile itself is specified by spec_ber_to_der (and the
+// spec_transcode_* functions it uses), which gives, for a BER input,
+// the transcoded DER output and the BERReport of relaxations used.
+// ber_to_der is verified against it: if it succeeds, its output and
+// report are exactly those given by the spec. Every TLV in the output
+// is written with the DER length of the verified Length combinator.
+//
+// The spec accepts exactly the following deviations from DER
+// (X.690, 10 and 11), and nothing else:
+//
+// 1. Length octets in the long form that are not minimal, i.e. with
+//    leading zero octets, or in the long form for lengths < 128
+//    (X.690, 8.1.3.5 vs 10.1), with at most 8 subsequent octets
+// 2. Indefinite lengths (0x80) for constructed encodings, terminated
+//    by end-of-contents octets 0x00 0x00 (X.690, 8.1.3.6 vs 10.1)
+// 3. A universal BOOLEAN whose content octet is neither 0x00 nor 0xff,
+//    which is read as TRUE (X.690, 8.2.2 vs 11.1)
+// 4. A universal INTEGER or ENUMERATED with redundant leading 0x00
+//    or 0xff octets (X.690, 8.3.2 is already required in BER, but
+//    commonly violated in practice)
+// 5. A universal BIT STRING with non-zero unused bits in the last
+//    octet (X.690, 11.2.1)
+//
+// Tags are not relaxed. In particular, the relaxations 3 to 5 only
+// apply to universal tags, since we do not know the underlying type
+// of implicitly tagged values at this level. For the same reason,
+// the elements of a SET or SET OF are left in their original order:
+// DER orders a SET OF by encoding but a SET by tag (X.690, 10.3 and
+// 11.6), and the two are indistinguishable without the schema.
+//
+// NOTE: what is not verified is that each relaxation preserves the
+// meaning of the input, i.e. that the DER output is the value that
+// a BER decoder would read from the input.
+//
+// NOTE: since the output differs from the input whenever a relaxation
+// is used, signatures computed over the original encoding (e.g. the
+// TBSCertificate) will not verify against the transcoded bytes.
+
+use std::fmt::{self, Display};
+
+use vstd::prelude::*;
+
+use polyfill::*;
+
+use crate::common::*;
+
+use super::bounds::*;
+use super::len::*;
+use super::tag::*;
+use super::var_int::*;
+
+verus! {
+
+/// Maximum nesting depth of TLVs accepted by the transcoder
+pub const BER_MAX_DEPTH: usize = 64;
+
+/// The relaxations allowed by the BER profile (see the module comment)
+#[derive(Debug, PartialEq, Eq)]
+pub enum BERRelaxation {
+    NonMinimalLength,
+    IndefiniteLength,
+    NonCanonicalBoolean,
+    NonMinimalInteger,
+    NonZeroUnusedBits,
+}
+
+/// Number of times each relaxation was used when transcoding
+/// (the counters saturate instead of overflowing)
+#[derive(Debug, PartialEq, Eq)]
+pub struct BERReport {
+    pub non_minimal_length: usize,
+    pub indefinite_length: usize,
+    pub non_canonical_boolean: usize,
+    pub non_minimal_integer: usize,
+    pub non_zero_unused_bits: usize,
+}
+
+pub open spec fn spec_saturating_inc(n: usize) -> usize
+{
+    if n == usize::MAX { n } else { (n + 1) as usize }
+}
+
+fn saturating_inc(n: usize) -> (res: usize)
+    ensures res == spec_saturating_inc(n)
+{
+    if n == usize::MAX { n } else { n + 1 }
+}
+
+impl BERReport {
+    pub open spec fn spec_new() -> BERReport
+    {
+        BERReport {
+            non_minimal_length: 0,
+            indefinite_length: 0,
+            non_canonical_boolean: 0,
+            non_minimal_integer: 0,
+            non_zero_unused_bits: 0,
+        }
+    }
+
+    pub fn new() -> (res: BERReport)
+        ensures res == Self::spec_new()
+    {
+        BERReport {
+            non_minimal_length: 0,
+            indefinite_length: 0,
+            non_canonical_boolean: 0,
+            non_minimal_integer: 0,
+            non_zero_unused_bits: 0,
+        }
+    }
+
+    pub open spec fn spec_record(self, r: BERRelaxation) -> BERReport
+    {
+        match r {
+            BERRelaxation::NonMinimalLength =>
+                BERReport { non_minimal_length: spec_saturating_inc(self.non_minimal_length), ..self },
+            BERRelaxation::IndefiniteLength =>
+                BERReport { indefinite_length: spec_saturating_inc(self.indefinite_length), ..self },
+            BERRelaxation::NonCanonicalBoolean =>
+                BERReport { non_canonical_boolean: spec_saturating_inc(self.non_canonical_boolean), ..self },
+            BERRelaxation::NonMinimalInteger =>
+                BERReport { non_minimal_integer: spec_saturating_inc(self.non_minimal_integer), ..self },
+            BERRelaxation::NonZeroUnusedBits =>
+                BERReport { non_zero_unused_bits: spec_saturating_inc(self.non_zero_unused_bits), ..self },
+        }
+    }
+
+    fn record(&mut self, r: BERRelaxation)
+        ensures *self == old(self).spec_record(r)
+    {
+        match r {
+            BERRelaxation::NonMinimalLength =>
+                self.non_minimal_length = saturating_inc(self.non_minimal_length),
+            BERRelaxation::IndefiniteLength =>
+                self.indefinite_length = saturating_inc(self.indefinite_length),
+            BERRelaxation::NonCanonicalBoolean =>
+                self.non_canonical_boolean = saturating_inc(self.non_canonical_boolean),
+            BERRelaxation::NonMinimalInteger =>
+                self.non_minimal_integer = saturating_inc(self.non_minimal_integer),
+            BERRelaxation::NonZeroUnusedBits =>
+                self.non_zero_unused_bits = saturating_inc(self.non_zero_unused_bits),
+        }
+    }
+}
+
+/// Length octets in BER
+#[derive(Debug)]
+pub enum BERLength {
+    Definite(usize),
+    Indefinite,
+}
+
+/// Parse BER length octets (X.690, 8.1.3), returning the number
+/// of bytes consumed, the length, and whether the encoding is
+/// definite but rejected by the DER Length combinator
+pub open spec fn spec_parse_ber_length(s: Seq<u8>) -> Option<(usize, BERLength, bool)>
+{
+    if s.len() == 0 {
+        None
+    } else if s[0] < 0x80 {
+        Some((1, BERLength::Definite(s[0] as usize), false))
+    } else if s[0] == 0x80 {
+        Some((1, BERLength::Indefinite, false))
+    } else if s[0] == 0xff {
+        // Reserved (X.690, 8.1.3.5 c)
+        None
+    } else {
+        let num_bytes = (s[0] - 0x80) as usize;
+
+        match VarUInt(num_bytes).spec_parse(s.drop_first()) {
+            Ok((_, v)) => if v <= LengthValue::MAX {
+                // Same condition as in Length, negated
+                let non_minimal = v <= 0x7f || fits_n_bytes_unsigned!(v, num_bytes - 1);
+                Some(((1 + num_bytes) as usize, BERLength::Definite(v as usize), non_minimal))
+            } else {
+                None
+            },
+            Err(..) => None,
+        }
+    }
+}
+
+/// Number of redundant leading octets in the content of an INTEGER,
+/// i.e. 0x00 followed by a 0 bit, or 0xff followed by a 1 bit
+pub open spec fn spec_num_redundant_octets(content: Seq<u8>) -> nat
+    decreases content.len()
+{
+    if content.len() >= 2 && (
+        (content[0] == 0x00 && content[1] & 0x80 == 0) ||
+        (content[0] == 0xff && content[1] & 0x80 != 0)
+    ) {
+        1 + spec_num_redundant_octets(content.drop_first())
+    } else {
+        0
+    }
+}
+
+/// Mask of the unused bits in the last octet of a BIT STRING
+pub open spec fn spec_unused_bits_mask(unused: u8) -> u8
+{
+    ((1u8 << unused) - 1) as u8
+}
+
+/// Rewrite the content octets of universal primitive types with a canonical DER form
+pub open spec fn spec_transcode_primitive(tag: TagValue, content: Seq<u8>, report: BERReport) -> (Seq<u8>, BERReport)
+{
+    if tag == tag_of!(BOOLEAN) &&
+        content.len() == 1 && content[0] != 0x00 && content[0] != 0xff {
+        (seq![0xff], report.spec_record(BERRelaxation::NonCanonicalBoolean))
+    } else if (tag == tag_of!(INTEGER) || tag == tag_of!(ENUMERATED)) &&
+        spec_num_redundant_octets(content) != 0 {
+        (content.skip(spec_num_redundant_octets(content) as int), report.spec_record(BERRelaxation::NonMinimalInteger))
+    } else if tag == tag_of!(BIT_STRING) &&
+        content.len() >= 2 && content[0] < 8 && content.last() & spec_unused_bits_mask(content[0]) != 0 {
+        (
+            content.drop_last().push(content.last() & !spec_unused_bits_mask(content[0])),
+            report.spec_record(BERRelaxation::NonZeroUnusedBits),
+        )
+    } else {
+        (content, report)
+    }
+}
+
+/// Transcode one TLV at the beginning of s, returning the number
+/// of bytes consumed, the DER encoding, and the updated report
+pub open spec fn spec_transcode_tlv(s: Seq<u8>, report: BERReport, depth: usize) -> Option<(usize, Seq<u8>, BERReport)>
+    decreases s.len(), 0int
+{
+    if depth > BER_MAX_DEPTH {
+        None
+    } else if let Ok((tag_len, tag)) = ASN1Tag.spec_parse(s) {
+        if let Some((len_len, len, non_minimal)) = spec_parse_ber_length(s.skip(tag_len as int)) {
+            let report = if non_minimal { report.spec_record(BERRelaxation::NonMinimalLength) } else { report };
+            let header_len = tag_len + len_len;
+            let rest = s.skip(header_len);
+
+            // Content octets consumed, transcoded content octets, and the updated report
+            let content = if header_len > s.len() {
+                None
+            } else {
+                match (tag.form, len) {
+                    (TagForm::Constructed, BERLength::Definite(len)) =>
+                        if len <= rest.len() {
+                            match spec_transcode_tlvs(rest.take(len as int), report, (depth + 1) as usize) {
+                                Some((content, report)) => Some((len as nat, content, report)),
+                                None => None,
+                            }
+                        } else {
+                            None
+                        },
+
+                    (TagForm::Constructed, BERLength::Indefinite) =>
+                        spec_transcode_tlvs_indefinite(
+                            rest,
+                            report.spec_record(BERRelaxation::IndefiniteLength),
+                            (depth + 1) as usize,
+                        ),
+
+                    (TagForm::Primitive, BERLength::Definite(len)) =>
+                        if len <= rest.len() {
+                            let (content, report) = spec_transcode_primitive(tag, rest.take(len as int), report);
+                            Some((len as nat, content, report))
+                        } else {
+                            None
+                        },
+
+                    (TagForm::Primitive, BERLength::Indefinite) => None,
+                }
+            };
+
+            match content {
+                Some((consumed, content, report)) =>
+                    match Length.spec_serialize(content.len() as usize) {
+                        Ok(der_len) => Some((
+                            (header_len + consumed) as usize,
+                            s.take(tag_len as int) + der_len + content,
+                            report,
+                        )),
+                        Err(..) => None,
+                    },
+                None => None,
+            }
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+/// Transcode a sequence of TLVs filling the entire s
+pub open spec fn spec_transcode_tlvs(s: Seq<u8>, report: BERReport, depth: usize) -> Option<(Seq<u8>, BERReport)>
+    decreases s.len(), 1int
+{
+    if s.len() == 0 {
+        Some((seq![], report))
+    } else if let Some((n, child, report)) = spec_transcode_tlv(s, report, depth) {
+        // 0 < n always holds, but is needed for termination
+        if 0 < n <= s.len() {
+            match spec_transcode_tlvs(s.skip(n as int), report, depth) {
+                Some((rest, report)) => Some((child + rest, report)),
+                None => None,
+            }
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+/// Transcode a sequence of TLVs terminated by end-of-contents octets,
+/// returning the number of bytes consumed (including end-of-contents)
+pub open spec fn spec_transcode_tlvs_indefinite(s: Seq<u8>, report: BERReport, depth: usize) -> Option<(nat, Seq<u8>, BERReport)>
+    decreases s.len(), 1int
+{
+    if s.len() < 2 {
+        None
+    } else if s[0] == 0 && s[1] == 0 {
+        Some((2, seq![], report))
+    } else if let Some((n, child, report)) = spec_transcode_tlv(s, report, depth) {
+        // 0 < n always holds, but is needed for termination
+        if 0 < n <= s.len() {
+            match spec_transcode_tlvs_indefinite(s.skip(n as int), report, depth) {
+                Some((m, rest, report)) => Some(((n + m) as nat, child + rest, report)),
+                None => None,
+            }
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+/// Transcode a single BER TLV (with no trailing bytes) into DER
+pub open spec fn spec_ber_to_der(s: Seq<u8>) -> Option<(Seq<u8>, BERReport)>
+{
+    match spec_transcode_tlv(s, BERReport::spec_new(), 0) {
+        Some((n, der, report)) => if n == s.len() { Some((der, report)) } else { None },
+        None => None,
+    }
+}
+
+/// Transcode a single BER TLV (with no trailing bytes) into DER
+pub fn ber_to_der(bytes: &[u8]) -> (res: Result<(Vec<u8>, BERReport), ParseError>)
+    ensures
+        res matches Ok((der, report)) ==> spec_ber_to_der(bytes@) == Some((der@, report)),
+{
+    let mut report = BERReport::new();
+
+    let (n, der) = transcode_tlv(bytes, &mut report, 0)?;
+    if n != bytes.len() {
+        return Err(ParseError::Other("trailing bytes after BER value".to_string()));
+    }
+
+    Ok((der, report))
+}
+
+/// Exec version of spec_parse_ber_length
+fn parse_ber_length(s: &[u8]) -> (res: Result<(usize, BERLength, bool), ParseError>)
+    ensures
+        res matches Ok((n, len, non_minimal)) ==> {
+            &&& spec_parse_ber_length(s@) == Some((n, len, non_minimal))
+            &&& 0 < n <= s@.len()
+        },
+{
+    if s.len() == 0 {
+        return Err(ParseError::UnexpectedEndOfInput);
+    }
+
+    if s[0] < 0x80 {
+        return Ok((1, BERLength::Definite(s[0] as usize), false));
+    }
+
+    if s[0] == 0x80 {
+        return Ok((1, BERLength::Indefinite, false));
+    }
+
+    if s[0] == 0xff {
+        return Err(ParseError::Other("reserved length octet".to_string()));
+    }
+
+    let num_bytes = (s[0] - 0x80) as usize;
+    let (n, v) = VarUInt(num_bytes).parse(slice_drop_first(s))?;
+
+    if v > LengthValue::MAX as VarUIntResult {
+        return Err(ParseError::SizeOverflow);
+    }
+
+    let non_minimal = v <= 0x7f || fits_n_bytes_unsigned!(v, num_bytes - 1);
+
+    Ok((n + 1, BERLength::Definite(v as usize), non_minimal))
+}
+
+/// Append the DER length octets of len (see Length)
+fn push_der_length(out: &mut Vec<u8>, len: usize) -> (res: Result<(), ParseError>)
+    ensures
+        res is Ok ==> {
+            &&& Length.spec_serialize(len) is Ok
+            &&& out@ == old(out)@ + Length.spec_serialize(len).unwrap()
+        },
+{
+    // At most 1 + uint_size!() octets
+    let mut buf: Vec<u8> = Vec::new();
+    while buf.len() < 9
+        invariant buf.len() <= 9
+        decreases 9 - buf.len()
+    {
+        buf.push(0);
+    }
+
+    let n = match Length.serialize(len, &mut buf, 0) {
+        Ok(n) => n,
+        Err(..) => return Err(ParseError::Other("length too large".to_string())),
+    };
+
+    proof {
+        if len >= 0x80 {
+            let bytes = min_num_bytes_unsigned(len as VarUIntResult);
+            lemma_min_num_bytes_unsigned(len as VarUIntResult);
+            VarUInt(bytes as usize).lemma_serialize_ok_len(len as VarUIntResult);
+        }
+        assert(buf@.take(n as int) =~= Length.spec_serialize(len).unwrap());
+    }
+
+    vec_extend_from_slice(out, slice_take(buf.as_slice(), n));
+
+    Ok(())
+}
+
+/// Exec version of spec_transcode_tlv
+fn transcode_tlv(s: &[u8], report: &mut BERReport, depth: usize) -> (res: Result<(usize, Vec<u8>), ParseError>)
+    ensures
+        res matches Ok((n, der)) ==> {
+            &&& spec_transcode_tlv(s@, *old(report), depth) == Some((n, der@, *report))
+            &&& 0 < n <= s@.len()
+        },
+    decreases s@.len()
+{
+    if depth > BER_MAX_DEPTH {
+        return Err(ParseError::Other("BER nesting too deep".to_string()));
+    }
+
+    let (tag_len, tag) = ASN1Tag.parse(s)?;
+    let (len_len, len, non_minimal) = parse_ber_length(slice_skip(s, tag_len))?;
+
+    if non_minimal {
+        report.record(BERRelaxation::NonMinimalLength);
+    }
+
+    let ghost report_header = *report;
+
+    let header_len = tag_len + len_len;
+    let rest = slice_skip(s, header_len);
+
+    let (consumed, content) = match tag.form {
+        TagForm::Constructed => match len {
+            BERLength::Definite(len) => {
+                if rest.len() < len {
+                    return Err(ParseError::UnexpectedEndOfInput);
+                }
+
+                let children = slice_take(rest, len);
+                let mut content: Vec<u8> = Vec::new();
+                let mut i = 0;
+
+                proof {
+                    assert(children@.skip(0) =~= children@);
+                    if let Some((tail, _)) = spec_transcode_tlvs(children@, report_header, (depth + 1) as usize) {
+                        assert(content@ + tail =~= tail);
+                    }
+                }
+
+                while i < len
+                    invariant
+                        i <= len,
+                        len == children@.len(),
+                        children@.len() < s@.len(),
+                        depth <= BER_MAX_DEPTH,
+                        spec_transcode_tlvs(children@, report_header, (depth + 1) as usize) ==
+                            match spec_transcode_tlvs(children@.skip(i as int), *report, (depth + 1) as usize) {
+                                Some((tail, report)) => Some((content@ + tail, report)),
+                                None => None,
+                            },
+                    decreases len - i
+                {
+                    let ghost prev_content = content@;
+
+                    let (n, child) = transcode_tlv(slice_skip(children, i), report, depth + 1)?;
+
+                    proof {
+                        assert(children@.skip(i as int).skip(n as int) =~= children@.skip(i + n));
+
+                        if let Some((tail, _)) = spec_transcode_tlvs(children@.skip(i + n), *report, (depth + 1) as usize) {
+                            assert(prev_content + child@ + tail =~= prev_content + (child@ + tail));
+                        }
+                    }
+
+                    vec_extend_from_slice(&mut content, child.as_slice());
+                    i += n;
+                }
+
+                assert(content@ + seq![] =~= content@);
+
+                (len, content)
+            }
+
+            BERLength::Indefinite => {
+                report.record(BERRelaxation::IndefiniteLength);
+
+                let ghost report_children = *report;
+                let mut content: Vec<u8> = Vec::new();
+                let mut i = 0;
+
+                proof {
+                    assert(rest@.skip(0) =~= rest@);
+                    if let Some((_, tail, _)) = spec_transcode_tlvs_indefinite(rest@, report_children, (depth + 1) as usize) {
+                        assert(content@ + tail =~= tail);
+                    }
+                }
+
+                loop
+                    invariant
+                        i <= rest@.len() < s@.len(),
+                        depth <= BER_MAX_DEPTH,
+                        spec_transcode_tlvs_indefinite(rest@, report_children, (depth + 1) as usize) ==
+                            match spec_transcode_tlvs_indefinite(rest@.skip(i as int), *report, (depth + 1) as usize) {
+                                Some((m, tail, report)) => Some(((i + m) as nat, content@ + tail, report)),
+                                None => None,
+                            },
+                    ensures
+                        i + 2 <= rest@.len(),
+                        rest@[i as int] == 0 && rest@[i + 1] == 0,
+                    decreases rest@.len() - i
+                {
+                    if rest.len() - i < 2 {
+                        return Err(ParseError::UnexpectedEndOfInput);
+                    }
+
+                    // End-of-contents octets
+                    if rest[i] == 0 && rest[i + 1] == 0 {
+                        break;
+                    }
+
+                    let ghost prev_content = content@;
+
+                    let (n, child) = transcode_tlv(slice_skip(rest, i), report, depth + 1)?;
+
+                    proof {
+                        assert(rest@.skip(i as int).skip(n as int) =~= rest@.skip(i + n));
+
+                        if let Some((_, tail, _)) = spec_transcode_tlvs_indefinite(rest@.skip(i + n), *report, (depth + 1) as usize) {
+                            assert(prev_content + child@ + tail =~= prev_content + (child@ + tail));
+                        }
+                    }
+
+                    vec_extend_from_slice(&mut content, child.as_slice());
+                    i += n;
+                }
+
+                assert(content@ + seq![] =~= content@);
+
+                (i + 2, content)
+            }
+        },
+
+        TagForm::Primitive => match len {
+            BERLength::Definite(len) => {
+                if rest.len() < len {
+                    return Err(ParseError::UnexpectedEndOfInput);
+                }
+
+                (len, transcode_primitive(&tag, slice_take(rest, len), report))
+            }
+
+            BERLength::Indefinite =>
+                return Err(ParseError::Other("indefinite length for primitive encoding".to_string())),
+        },
+    };
+
+    let mut der: Vec<u8> = Vec::new();
+    vec_extend_from_slice(&mut der, slice_take(s, tag_len));
+    push_der_length(&mut der, content.len())?;
+    vec_extend_from_slice(&mut der, content.as_slice());
+
+    assert(der@ =~= s@.take(tag_len as int) + Length.spec_serialize(content@.len() as usize).unwrap() + content@);
+
+    Ok((header_len + consumed, der))
+}
+
+/// Exec version of spec_transcode_primitive
+fn transcode_primitive(tag: &TagValue, content: &[u8], report: &mut BERReport) -> (res: Vec<u8>)
+    ensures (res@, *report) == spec_transcode_primitive(*tag, content@, *old(report))
+{
+    let is_boolean = tag.clone().eq(tag_of!(BOOLEAN));
+    let is_integer = tag.clone().eq(tag_of!(INTEGER)) || tag.clone().eq(tag_of!(ENUMERATED));
+    let is_bit_string = tag.clone().eq(tag_of!(BIT_STRING));
+
+    let mut fixed: Vec<u8> = Vec::new();
+
+    if is_boolean && content.len() == 1 && content[0] != 0x00 && content[0] != 0xff {
+        report.record(BERRelaxation::NonCanonicalBoolean);
+        fixed.push(0xff);
+
+        assert(fixed@ =~= seq![0xff]);
+        return fixed;
+    }
+
+    // Count redundant leading octets
+    let mut i = 0;
+    while i + 1 < content.len() && (
+        (content[i] == 0x00 && content[i + 1] & 0x80 == 0) ||
+        (content[i] == 0xff && content[i + 1] & 0x80 != 0)
+    )
+        invariant
+            i == 0 || i < content@.len(),
+            spec_num_redundant_octets(content@) == i + spec_num_redundant_octets(content@.skip(i as int)),
+        decreases content@.len() - i
+    {
+        assert(content@.skip(i as int).drop_first() =~= content@.skip(i + 1));
+        i += 1;
+    }
+
+    if is_integer && i != 0 {
+        report.record(BERRelaxation::NonMinimalInteger);
+        vec_extend_from_slice(&mut fixed, slice_skip(content, i));
+
+        assert(fixed@ =~= content@.skip(i as int));
+        return fixed;
+    }
+
+    if is_bit_string && content.len() >= 2 && content[0] < 8 {
+        let unused = content[0];
+        assert(1u8 << unused >= 1) by (bit_vector)
+            requires unused < 8;
+
+        let mask = (1u8 << unused) - 1;
+        let last = content[content.len() - 1];
+
+        if last & mask != 0 {
+            report.record(BERRelaxation::NonZeroUnusedBits);
+            vec_extend_from_slice(&mut fixed, slice_take(content, content.len() - 1));
+            fixed.push(last & !mask);
+
+            assert(fixed@ =~= content@.drop_last().push(content@.last() & !spec_unused_bits_mask(content@[0])));
+            return fixed;
+        }
+    }
+
+    vec_extend_from_slice(&mut fixed, content);
+    assert(fixed@ =~= content@);
+
+    fixed
+}
+
+}
+
+impl BERReport {
+    /// Relaxations used at least once
+    pub fn used(&self) -> Vec<BERRelaxation> {
+        [
+            (BERRelaxation::NonMinimalLength, self.non_minimal_length),
+            (BERRelaxation::IndefiniteLength, self.indefinite_length),
+            (BERRelaxation::NonCanonicalBoolean, self.non_canonical_boolean),
+            (BERRelaxation::NonMinimalInteger, self.non_minimal_integer),
+            (BERRelaxation::NonZeroUnusedBits, self.non_zero_unused_bits),
+        ].into_iter().filter(|(_, n)| *n != 0).map(|(r, _)| r).collect()
+    }
+
+    /// True iff the input was already DER (w.r.t. the relaxations above)
+    pub fn is_der(&self) -> bool {
+        self.used().is_empty()
+    }
+
+    /// Accumulate the counts of another report
+    pub fn merge(&mut self, other: &BERReport) {
+        self.non_minimal_length = self.non_minimal_length.saturating_add(other.non_minimal_length);
+        self.indefinite_length = self.indefinite_length.saturating_add(other.indefinite_length);
+        self.non_canonical_boolean = self.non_canonical_boolean.saturating_add(other.non_canonical_boolean);
+        self.non_minimal_integer = self.non_minimal_integer.saturating_add(other.non_minimal_integer);
+        self.non_zero_unused_bits = self.non_zero_unused_bits.saturating_add(other.non_zero_unused_bits);
+    }
+}
+
+impl Default for BERReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for BERRelaxation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BERRelaxation::NonMinimalLength => write!(f, "non-minimal length"),
+            BERRelaxation::IndefiniteLength => write!(f, "indefinite length"),
+            BERRelaxation::NonCanonicalBoolean => write!(f, "non-canonical boolean"),
+            BERRelaxation::NonMinimalInteger => write!(f, "non-minimal integer"),
+            BERRelaxation::NonZeroUnusedBits => write!(f, "non-zero unused bits"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::asn1::*;
+
+    #[test]
+    fn der_unchanged() {
+        let bytes = [ 0x30, 0x06, 0x01, 0x01, 0xff, 0x02, 0x01, 0x05 ];
+        let (der, report) = ber_to_der(&bytes).unwrap();
+        assert_eq!(der, bytes);
+        assert!(report.is_der());
+    }
+
+    #[test]
+    fn relaxations() {
+        // SEQUENCE (indefinite) {
+        //     BOOLEAN 0x01,
+        //     INTEGER 0x00 0x05 (with non-minimal length 0x81 0x02),
+        //     BIT STRING with 1 unused bit set,
+        // }
+        let bytes = [
+            0x30, 0x80,
+                0x01, 0x01, 0x01,
+                0x02, 0x81, 0x02, 0x00, 0x05,
+                0x03, 0x02, 0x01, 0x81,
+            0x00, 0x00,
+        ];
+        let (der, report) = ber_to_der(&bytes).unwrap();
+        assert_eq!(der, [ 0x30, 0x0a, 0x01, 0x01, 0xff, 0x02, 0x01, 0x05, 0x03, 0x02, 0x01, 0x80 ]);
+        assert_eq!(report, BERReport {
+            non_minimal_length: 1,
+            indefinite_length: 1,
+            non_canonical_boolean: 1,
+            non_minimal_integer: 1,
+            non_zero_unused_bits: 1,
+        });
+
+        let (_, v) = ASN1(Integer).parse(&der[5..8]).unwrap();
+        assert_eq!(v, 5);
+    }
+
+    #[test]
+    fn nested() {
+        // SEQUENCE (indefinite) { SEQUENCE (indefinite) { NULL }, OCTET STRING (length 0x82 0x00 0x01) }
+        let bytes = [
+            0x30, 0x80,
+                0x30, 0x80, 0x05, 0x00, 0x00, 0x00,
+                0x04, 0x82, 0x00, 0x01, 0xab,
+            0x00, 0x00,
+        ];
+        let (der, report) = ber_to_der(&bytes).unwrap();
+        assert_eq!(der, [ 0x30, 0x07, 0x30, 0x02, 0x05, 0x00, 0x04, 0x01, 0xab ]);
+        assert_eq!(report.indefinite_length, 2);
+        assert_eq!(report.non_minimal_length, 1);
+    }
+
+    #[test]
+    fn set_unchanged() {
+        // SET bodies are not reordered, since a SET and a SET OF
+        // have different DER orders
+        let bytes = [ 0x31, 0x06, 0x02, 0x01, 0x02, 0x02, 0x01, 0x01 ];
+        let (der, report) = ber_to_der(&bytes).unwrap();
+        assert_eq!(der, bytes);
+        assert!(report.is_der());
+    }
+
+    #[test]
+    fn rejected() {
+        // Indefinite length for a primitive encoding
+        assert!(ber_to_der(&[ 0x04, 0x80, 0x01, 0x00, 0x00 ]).is_err());
+
+        // Missing end-of-contents
+        assert!(ber_to_der(&[ 0x30, 0x80, 0x05, 0x00 ]).is_err());
+
+        // Trailing bytes
+        assert!(ber_to_der(&[ 0x05, 0x00, 0x00 ]).is_err());
+
+        // Reserved length octet, and more than 8 length octets
+        assert!(ber_to_der(&[ 0x04, 0xff, 0x00 ]).is_err());
+        assert!(ber_to_der(&[ 0x04, 0x89, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00 ]).is_err());
+    }
+}
@@ -1,5 +1,8 @@
 use std::fmt::{self, Debug, Formatter};
 use vstd::prelude::*;
+use vstd::arithmetic::power2::*;
+use vstd::arithmetic::div_mod::lemma_fundamental_div_mod_converse;
+use polyfill::*;
 
 use crate::common::*;
 
@@ -99,7 +102,954 @@ impl<'a> BigIntValue<'a> {
         self.0
     }
 
-    // TODO: add more methods to interpret BigIntValue as an integer
+    /// Interpret the bytes (big-endian, two's complement) as an integer
+    pub open spec fn spec_to_int(bytes: Seq<u8>) -> int
+        decreases bytes.len()
+    {
+        if bytes.len() == 0 {
+            0
+        } else if bytes.len() == 1 {
+            if bytes[0] >= 0x80 { bytes[0] - 0x100 } else { bytes[0] as int }
+        } else {
+            Self::spec_to_int(bytes.drop_last()) * 0x100 + bytes.last()
+        }
+    }
+
+    /// Number of bits needed to represent a natural number
+    pub open spec fn spec_bit_len(n: nat) -> nat
+        decreases n
+    {
+        if n == 0 { 0 } else { 1 + Self::spec_bit_len(n / 2) }
+    }
+
+    pub open spec fn spec_digit_chars() -> Seq<char>
+    {
+        seq!['0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F']
+    }
+
+    /// Digits of a natural number in the given base (2 to 16),
+    /// without leading zeros, and in upper case
+    pub open spec fn spec_nat_to_digits(n: nat, base: nat) -> Seq<char>
+        decreases n
+    {
+        if base < 2 || base > 16 {
+            seq![]
+        } else if n < base {
+            seq![Self::spec_digit_chars()[n as int]]
+        } else {
+            Self::spec_nat_to_digits(n / base, base) + seq![Self::spec_digit_chars()[(n % base) as int]]
+        }
+    }
+
+    /// Render an integer in the given base, with a "-" if negative
+    pub open spec fn spec_int_to_string(v: int, base: nat) -> Seq<char>
+    {
+        if v < 0 {
+            seq!['-'] + Self::spec_nat_to_digits((-v) as nat, base)
+        } else {
+            Self::spec_nat_to_digits(v as nat, base)
+        }
+    }
+
+    /// Interpret the bytes (big-endian) as a natural number
+    pub open spec fn spec_be_to_nat(bytes: Seq<u8>) -> nat
+        decreases bytes.len()
+    {
+        if bytes.len() == 0 {
+            0
+        } else {
+            Self::spec_be_to_nat(bytes.drop_last()) * 0x100 + bytes.last()
+        }
+    }
+
+    pub open spec fn spec_pow256(n: nat) -> nat
+        decreases n
+    {
+        if n == 0 { 1 } else { 0x100 * Self::spec_pow256((n - 1) as nat) }
+    }
+
+    /// Bitwise complement of each byte
+    pub open spec fn spec_complement(bytes: Seq<u8>) -> Seq<u8>
+    {
+        bytes.map_values(|b: u8| (0xff - b) as u8)
+    }
+
+    /// e.g. 0x00ff => "FF", 0x80 => "-80"
+    pub open spec fn spec_to_hex(bytes: Seq<u8>) -> Seq<char>
+    {
+        Self::spec_int_to_string(Self::spec_to_int(bytes), 16)
+    }
+
+    pub open spec fn spec_to_decimal(bytes: Seq<u8>) -> Seq<char>
+    {
+        Self::spec_int_to_string(Self::spec_to_int(bytes), 10)
+    }
+
+    /// The sign is determined by the highest bit of the first byte
+    pub proof fn lemma_sign(bytes: Seq<u8>)
+        requires bytes.len() != 0
+        ensures (Self::spec_to_int(bytes) < 0) == (bytes[0] >= 0x80)
+        decreases bytes.len()
+    {
+        if bytes.len() > 1 {
+            let prefix = bytes.drop_last();
+            let x = Self::spec_to_int(prefix);
+            let b = bytes.last() as int;
+
+            Self::lemma_sign(prefix);
+            assert(prefix[0] == bytes[0]);
+
+            assert(x < 0 ==> x * 0x100 + b < 0) by (nonlinear_arith)
+                requires 0 <= b < 0x100 {}
+            assert(x >= 0 ==> x * 0x100 + b >= 0) by (nonlinear_arith)
+                requires 0 <= b {}
+        }
+    }
+
+    /// Non-empty prefixes of a non-negative (resp. negative)
+    /// integer are closer to 0
+    pub proof fn lemma_prefix_bound(bytes: Seq<u8>, i: int)
+        requires 1 <= i <= bytes.len()
+        ensures
+            bytes[0] < 0x80 ==> 0 <= Self::spec_to_int(bytes.take(i)) <= Self::spec_to_int(bytes),
+            bytes[0] >= 0x80 ==> Self::spec_to_int(bytes) <= Self::spec_to_int(bytes.take(i)) < 0,
+        decreases bytes.len()
+    {
+        assert(bytes.take(i)[0] == bytes[0]);
+        Self::lemma_sign(bytes.take(i));
+        Self::lemma_sign(bytes);
+
+        if i < bytes.len() {
+            let prefix = bytes.drop_last();
+            let x = Self::spec_to_int(prefix);
+            let b = bytes.last() as int;
+
+            Self::lemma_prefix_bound(prefix, i);
+            assert(prefix.take(i) =~= bytes.take(i));
+            assert(prefix[0] == bytes[0]);
+            Self::lemma_sign(prefix);
+
+            assert(x >= 0 ==> x <= x * 0x100 + b) by (nonlinear_arith)
+                requires 0 <= b {}
+            assert(x < 0 ==> x * 0x100 + b <= x) by (nonlinear_arith)
+                requires b < 0x100 {}
+        } else {
+            assert(bytes.take(i) =~= bytes);
+        }
+    }
+
+    /// Appending j low bits to a positive number adds j to the bit length
+    proof fn lemma_bit_len_shift(x: nat, r: nat, j: nat)
+        requires
+            x > 0,
+            r < pow2(j),
+        ensures
+            Self::spec_bit_len(x * pow2(j) + r) == Self::spec_bit_len(x) + j
+        decreases j
+    {
+        if j == 0 {
+            lemma_pow2_unfold(0);
+            assert(pow2(0) == 1) by (compute);
+        } else {
+            let m = pow2((j - 1) as nat);
+            let n = x * pow2(j) + r;
+
+            lemma_pow2_unfold(j);
+            assert(n / 2 == x * m + r / 2 && n > 0) by (nonlinear_arith)
+                requires n == x * pow2(j) + r, pow2(j) == 2 * m, x > 0 {}
+
+            Self::lemma_bit_len_shift(x, r / 2, (j - 1) as nat);
+        }
+    }
+
+    /// Bit length of a minimally encoded non-negative integer
+    /// in terms of its first non-zero byte (or the only byte)
+    proof fn lemma_bit_len(bytes: Seq<u8>, k: int)
+        requires
+            0 <= k <= 1,
+            k < bytes.len(),
+            bytes[0] < 0x80,
+            k == 1 ==> bytes[0] == 0,
+            bytes[k] != 0 || k == bytes.len() - 1,
+        ensures
+            Self::spec_to_int(bytes.take(k + 1)) == bytes[k] as int,
+            Self::spec_bit_len(Self::spec_to_int(bytes) as nat) ==
+                Self::spec_bit_len(bytes[k] as nat) + 8 * (bytes.len() - k - 1),
+        decreases bytes.len()
+    {
+        assert(bytes.take(k + 1).drop_last() =~= bytes.take(k));
+        assert(k == 1 ==> bytes.take(1) =~= seq![0u8]);
+        Self::lemma_sign(bytes);
+
+        if bytes.len() == k + 1 {
+            assert(bytes.take(k + 1) =~= bytes);
+        } else {
+            let prefix = bytes.drop_last();
+            let x = Self::spec_to_int(prefix);
+            let b = bytes.last();
+
+            Self::lemma_bit_len(prefix, k);
+            assert(prefix.take(k + 1) =~= bytes.take(k + 1));
+            Self::lemma_prefix_bound(prefix, k + 1);
+
+            lemma2_to64();
+            Self::lemma_bit_len_shift(x as nat, b as nat, 8);
+        }
+    }
+
+    /// Whether the integer is negative
+    pub fn is_negative(&self) -> (res: bool)
+        ensures res == (Self::spec_to_int(self@) < 0)
+    {
+        proof {
+            use_type_invariant(self);
+            Self::lemma_sign(self@);
+        }
+        self.0[0] >= 0x80
+    }
+
+    /// Bit length of the integer (excluding the leading zeros)
+    /// if it is non-negative, e.g. 0x00ff => 8
+    pub fn bit_len(&self) -> (res: Option<usize>)
+        ensures
+            res matches Some(n) ==> {
+                &&& Self::spec_to_int(self@) >= 0
+                &&& n == Self::spec_bit_len(Self::spec_to_int(self@) as nat)
+            },
+            res is None ==> Self::spec_to_int(self@) < 0 || self@.len() > usize::MAX / 8,
+    {
+        proof {
+            use_type_invariant(self);
+            Self::lemma_sign(self@);
+        }
+
+        let bytes = self.0;
+
+        if bytes[0] >= 0x80 || bytes.len() > usize::MAX / 8 {
+            return None;
+        }
+
+        // By minimality, the first byte is zero only if
+        // it is the only byte or the next byte is >= 0x80
+        let k = if bytes[0] == 0 && bytes.len() > 1 { 1 } else { 0 };
+
+        proof {
+            Self::lemma_bit_len(self@, k as int);
+        }
+
+        Some((bytes.len() - k - 1) * 8 + Self::byte_bit_len(bytes[k]))
+    }
+
+    fn byte_bit_len(b: u8) -> (res: usize)
+        ensures res == Self::spec_bit_len(b as nat)
+    {
+        proof {
+            reveal_with_fuel(BigIntValue::spec_bit_len, 9);
+        }
+
+        if b >= 0x80 { 8 }
+        else if b >= 0x40 { 7 }
+        else if b >= 0x20 { 6 }
+        else if b >= 0x10 { 5 }
+        else if b >= 0x08 { 4 }
+        else if b >= 0x04 { 3 }
+        else if b >= 0x02 { 2 }
+        else if b >= 0x01 { 1 }
+        else { 0 }
+    }
+
+    /// Convert to u64 if the integer is in range
+    pub fn to_u64(&self) -> (res: Option<u64>)
+        ensures
+            res matches Some(v) ==> v as int == Self::spec_to_int(self@),
+            res is None ==> !(0 <= Self::spec_to_int(self@) <= u64::MAX),
+    {
+        proof {
+            use_type_invariant(self);
+            Self::lemma_sign(self@);
+        }
+
+        let bytes = self.0;
+
+        if bytes[0] >= 0x80 {
+            return None;
+        }
+
+        let mut acc = bytes[0] as u64;
+        let mut i = 1;
+
+        assert(bytes@.take(1) =~= seq![bytes@[0]]);
+
+        while i < bytes.len()
+            invariant
+                bytes@ == self@,
+                bytes@[0] < 0x80,
+                1 <= i <= bytes.len(),
+                acc as int == Self::spec_to_int(bytes@.take(i as int)),
+            decreases bytes.len() - i
+        {
+            let b = bytes[i] as u64;
+            let ghost next = bytes@.take(i + 1);
+
+            assert(next.drop_last() =~= bytes@.take(i as int));
+
+            if acc > (u64::MAX - b) / 0x100 {
+                proof {
+                    // The next prefix overflows, and the entire integer
+                    // is at least as large as the prefix
+                    assert(acc * 0x100 + b > u64::MAX) by (nonlinear_arith)
+                        requires acc > (u64::MAX - b) / 0x100 {}
+                    Self::lemma_prefix_bound(bytes@, i + 1);
+                }
+                return None;
+            }
+
+            assert(acc * 0x100 + b <= u64::MAX) by (nonlinear_arith)
+                requires acc <= (u64::MAX - b) / 0x100 {}
+
+            acc = acc * 0x100 + b;
+            i += 1;
+        }
+
+        assert(bytes@.take(i as int) =~= bytes@);
+        Some(acc)
+    }
+
+    /// Convert to i128 if the integer is in range
+    pub fn to_i128(&self) -> (res: Option<i128>)
+        ensures
+            res matches Some(v) ==> v as int == Self::spec_to_int(self@),
+            res is None ==> !(i128::MIN <= Self::spec_to_int(self@) <= i128::MAX),
+    {
+        proof {
+            use_type_invariant(self);
+        }
+
+        let bytes = self.0;
+        let mut acc = if bytes[0] >= 0x80 { bytes[0] as i128 - 0x100 } else { bytes[0] as i128 };
+        let mut i = 1;
+
+        assert(bytes@.take(1) =~= seq![bytes@[0]]);
+
+        while i < bytes.len()
+            invariant
+                bytes@ == self@,
+                1 <= i <= bytes.len(),
+                acc as int == Self::spec_to_int(bytes@.take(i as int)),
+            decreases bytes.len() - i
+        {
+            let b = bytes[i] as i128;
+            let ghost next = bytes@.take(i + 1);
+
+            assert(next.drop_last() =~= bytes@.take(i as int));
+
+            if acc > (i128::MAX - b) / 0x100 || acc < i128::MIN / 0x100 {
+                proof {
+                    // The next prefix overflows in the direction of the sign,
+                    // and the entire integer is even further from 0
+                    assert(acc > (i128::MAX - b) / 0x100 ==> acc * 0x100 + b > i128::MAX) by (nonlinear_arith)
+                        requires 0 <= b < 0x100 {}
+                    assert(acc < i128::MIN / 0x100 ==> acc * 0x100 + b < i128::MIN) by (nonlinear_arith)
+                        requires 0 <= b < 0x100 {}
+
+                    assert(bytes@.take(i as int)[0] == bytes@[0]);
+                    Self::lemma_sign(bytes@.take(i as int));
+                    Self::lemma_prefix_bound(bytes@, i + 1);
+                }
+                return None;
+            }
+
+            assert(i128::MIN <= acc * 0x100 + b <= i128::MAX) by (nonlinear_arith)
+                requires
+                    0 <= b < 0x100,
+                    i128::MIN / 0x100 <= acc <= (i128::MAX - b) / 0x100 {}
+
+            acc = acc * 0x100 + b;
+            i += 1;
+        }
+
+        assert(bytes@.take(i as int) =~= bytes@);
+        Some(acc)
+    }
+
+    proof fn lemma_pow256_pos(n: nat)
+        ensures Self::spec_pow256(n) >= 1
+        decreases n
+    {
+        if n > 0 {
+            Self::lemma_pow256_pos((n - 1) as nat);
+        }
+    }
+
+    proof fn lemma_be_single(b: u8)
+        ensures Self::spec_be_to_nat(seq![b]) == b
+    {
+        assert(seq![b].drop_last() =~= Seq::<u8>::empty());
+    }
+
+    proof fn lemma_be_zeros(n: nat)
+        ensures Self::spec_be_to_nat(Seq::new(n, |_i: int| 0u8)) == 0
+        decreases n
+    {
+        if n > 0 {
+            assert(Seq::new(n, |_i: int| 0u8).drop_last() =~= Seq::new((n - 1) as nat, |_i: int| 0u8));
+            Self::lemma_be_zeros((n - 1) as nat);
+        }
+    }
+
+    /// be(x + y) == be(x) * 256^|y| + be(y)
+    proof fn lemma_be_concat(x: Seq<u8>, y: Seq<u8>)
+        ensures
+            Self::spec_be_to_nat(x + y) ==
+                Self::spec_be_to_nat(x) * Self::spec_pow256(y.len()) + Self::spec_be_to_nat(y),
+        decreases y.len()
+    {
+        if y.len() == 0 {
+            assert(x + y =~= x);
+        } else {
+            let y0 = y.drop_last();
+            let bx = Self::spec_be_to_nat(x);
+            let by0 = Self::spec_be_to_nat(y0);
+            let p = Self::spec_pow256(y0.len());
+
+            Self::lemma_be_concat(x, y0);
+            assert((x + y).drop_last() =~= x + y0);
+            assert((x + y).last() == y.last());
+
+            assert((bx * p + by0) * 0x100 + y.last() == bx * (0x100 * p) + (by0 * 0x100 + y.last()))
+                by (nonlinear_arith);
+        }
+    }
+
+    /// be(x) < 256^|x|
+    proof fn lemma_be_bound(x: Seq<u8>)
+        ensures Self::spec_be_to_nat(x) < Self::spec_pow256(x.len())
+        decreases x.len()
+    {
+        if x.len() > 0 {
+            let b = Self::spec_be_to_nat(x.drop_last());
+            let p = Self::spec_pow256((x.len() - 1) as nat);
+            let l = x.last();
+
+            Self::lemma_be_bound(x.drop_last());
+            assert(b * 0x100 + l < 0x100 * p) by (nonlinear_arith)
+                requires b < p, l < 0x100 {}
+        }
+    }
+
+    /// Relate the two's complement interpretation to the unsigned one
+    proof fn lemma_to_int_be(bytes: Seq<u8>)
+        requires bytes.len() != 0
+        ensures
+            bytes[0] < 0x80 ==> Self::spec_to_int(bytes) == Self::spec_be_to_nat(bytes),
+            bytes[0] >= 0x80 ==> Self::spec_to_int(bytes) == Self::spec_be_to_nat(bytes) - Self::spec_pow256(bytes.len()),
+        decreases bytes.len()
+    {
+        if bytes.len() == 1 {
+            reveal_with_fuel(BigIntValue::spec_pow256, 2);
+            assert(bytes.drop_last() =~= Seq::<u8>::empty());
+            assert(bytes.last() == bytes[0]);
+        } else {
+            Self::lemma_to_int_be(bytes.drop_last());
+            assert(bytes.drop_last()[0] == bytes[0]);
+        }
+    }
+
+    /// be(x) + be(complement(x)) == 256^|x| - 1
+    proof fn lemma_be_complement(x: Seq<u8>)
+        ensures
+            Self::spec_be_to_nat(x) + Self::spec_be_to_nat(Self::spec_complement(x)) + 1 == Self::spec_pow256(x.len()),
+        decreases x.len()
+    {
+        if x.len() > 0 {
+            let c = Self::spec_complement(x);
+
+            Self::lemma_be_complement(x.drop_last());
+            assert(c.drop_last() =~= Self::spec_complement(x.drop_last()));
+            assert(c.last() == 0xff - x.last());
+        }
+    }
+
+    /// Big-endian encodings of the same length are ordered lexicographically
+    proof fn lemma_be_lex(a: Seq<u8>, b: Seq<u8>, i: int)
+        requires
+            a.len() == b.len(),
+            0 <= i < a.len(),
+            a.take(i) == b.take(i),
+            a[i] < b[i],
+        ensures
+            Self::spec_be_to_nat(a) < Self::spec_be_to_nat(b),
+    {
+        Self::lemma_be_concat(a.take(i + 1), a.skip(i + 1));
+        Self::lemma_be_concat(b.take(i + 1), b.skip(i + 1));
+        Self::lemma_be_bound(a.skip(i + 1));
+
+        assert(a.take(i + 1) + a.skip(i + 1) =~= a);
+        assert(b.take(i + 1) + b.skip(i + 1) =~= b);
+        assert(a.take(i + 1).drop_last() =~= a.take(i));
+        assert(b.take(i + 1).drop_last() =~= b.take(i));
+        assert(a.take(i + 1).last() == a[i]);
+        assert(b.take(i + 1).last() == b[i]);
+
+        let t = Self::spec_be_to_nat(a.take(i));
+        let p = Self::spec_pow256(a.skip(i + 1).len());
+        let ra = Self::spec_be_to_nat(a.skip(i + 1));
+        let rb = Self::spec_be_to_nat(b.skip(i + 1));
+        let x = a[i] as int;
+        let y = b[i] as int;
+
+        assert((t * 0x100 + x) * p + ra < (t * 0x100 + y) * p + rb) by (nonlinear_arith)
+            requires ra < p, x < y, 0 <= rb {}
+    }
+
+    /// Sign and the absolute value (big-endian, with leading zeros)
+    fn magnitude(&self) -> (res: (bool, Vec<u8>))
+        ensures
+            res.0 == (Self::spec_to_int(self@) < 0),
+            Self::spec_be_to_nat(res.1@) ==
+                if res.0 { -Self::spec_to_int(self@) } else { Self::spec_to_int(self@) },
+    {
+        proof {
+            use_type_invariant(self);
+            Self::lemma_sign(self@);
+            Self::lemma_to_int_be(self@);
+        }
+
+        let bytes = self.0;
+        let neg = bytes[0] >= 0x80;
+
+        // The absolute value of a negative integer is its complement plus one,
+        // and the extra leading zero ensures that the increment does not overflow
+        let mut mag = vec![0u8];
+        let mut i = 0;
+
+        while i < bytes.len()
+            invariant
+                0 <= i <= bytes.len(),
+                neg == (bytes@[0] >= 0x80),
+                mag@ == seq![0u8] + if neg { Self::spec_complement(bytes@.take(i as int)) } else { bytes@.take(i as int) },
+            decreases bytes.len() - i
+        {
+            mag.push(if neg { 0xff - bytes[i] } else { bytes[i] });
+
+            assert(bytes@.take(i + 1) =~= bytes@.take(i as int).push(bytes@[i as int]));
+            assert(Self::spec_complement(bytes@.take(i + 1)) =~=
+                Self::spec_complement(bytes@.take(i as int)).push((0xff - bytes@[i as int]) as u8));
+
+            i += 1;
+        }
+
+        proof {
+            let body = if neg { Self::spec_complement(bytes@) } else { bytes@ };
+
+            assert(bytes@.take(i as int) =~= bytes@);
+            Self::lemma_be_single(0);
+            Self::lemma_be_concat(seq![0u8], body);
+            Self::lemma_be_complement(bytes@);
+            Self::lemma_pow256_pos(bytes@.len());
+            assert(Self::spec_complement(bytes@).len() == bytes@.len());
+        }
+
+        if neg {
+            Self::be_increment(&mut mag);
+        }
+
+        (neg, mag)
+    }
+
+    /// Add one to a big-endian natural number in place
+    fn be_increment(v: &mut Vec<u8>)
+        requires
+            Self::spec_be_to_nat(old(v)@) + 1 < Self::spec_pow256(old(v)@.len()),
+        ensures
+            v@.len() == old(v)@.len(),
+            Self::spec_be_to_nat(v@) == Self::spec_be_to_nat(old(v)@) + 1,
+    {
+        let ghost orig = v@;
+        let mut j = v.len();
+        let mut carry = true;
+
+        assert(v@.skip(j as int) =~= Seq::<u8>::empty());
+        assert(orig.skip(j as int) =~= Seq::<u8>::empty());
+
+        // v[j..] is orig[j..] plus one, minus the carry into v[j - 1]
+        while carry && j > 0
+            invariant
+                0 <= j <= orig.len(),
+                v@.len() == orig.len(),
+                v@.take(j as int) == orig.take(j as int),
+                Self::spec_be_to_nat(v@.skip(j as int)) +
+                    (if carry { Self::spec_pow256((orig.len() - j) as nat) } else { 0 }) ==
+                    Self::spec_be_to_nat(orig.skip(j as int)) + 1,
+            decreases j
+        {
+            j -= 1;
+
+            let ghost prev = v@;
+            let b = v[j];
+
+            assert(orig.take(j + 1)[j as int] == prev.take(j + 1)[j as int]);
+
+            if b == 0xff {
+                vec_set(v, j, 0);
+            } else {
+                vec_set(v, j, b + 1);
+                carry = false;
+            }
+
+            proof {
+                let p = Self::spec_pow256((orig.len() - j - 1) as nat);
+                let rest = Self::spec_be_to_nat(prev.skip(j + 1));
+
+                assert(v@.skip(j + 1) =~= prev.skip(j + 1));
+                assert(v@.take(j as int) =~= orig.take(j as int));
+                assert(v@.skip(j as int) =~= seq![v@[j as int]] + v@.skip(j + 1));
+                assert(orig.skip(j as int) =~= seq![orig[j as int]] + orig.skip(j + 1));
+
+                Self::lemma_be_single(v@[j as int]);
+                Self::lemma_be_single(orig[j as int]);
+                Self::lemma_be_concat(seq![v@[j as int]], v@.skip(j + 1));
+                Self::lemma_be_concat(seq![orig[j as int]], orig.skip(j + 1));
+
+                assert((b as int + 1) * p == b * p + p) by (nonlinear_arith);
+            }
+        }
+
+        proof {
+            if carry {
+                // Otherwise the result would not fit in the same length
+                assert(v@.skip(0) =~= v@);
+                assert(orig.skip(0) =~= orig);
+            }
+
+            assert(v@.take(j as int) + v@.skip(j as int) =~= v@);
+            assert(orig.take(j as int) + orig.skip(j as int) =~= orig);
+            Self::lemma_be_concat(v@.take(j as int), v@.skip(j as int));
+            Self::lemma_be_concat(orig.take(j as int), orig.skip(j as int));
+        }
+    }
+
+    /// Divide a big-endian natural number in place by a
+    /// non-zero divisor, and return the remainder
+    fn be_div_rem(v: &mut Vec<u8>, d: u8) -> (rem: u8)
+        requires d > 0
+        ensures
+            v@.len() == old(v)@.len(),
+            Self::spec_be_to_nat(v@) == Self::spec_be_to_nat(old(v)@) / (d as nat),
+            rem as nat == Self::spec_be_to_nat(old(v)@) % (d as nat),
+    {
+        let ghost orig = v@;
+        let mut rem: u8 = 0;
+        let mut i = 0;
+
+        assert(v@.take(0) =~= Seq::<u8>::empty());
+        assert(orig.take(0) =~= Seq::<u8>::empty());
+
+        // Long division from the most significant byte
+        while i < v.len()
+            invariant
+                0 <= i <= v.len(),
+                v@.len() == orig.len(),
+                v@.skip(i as int) == orig.skip(i as int),
+                d > 0,
+                rem < d,
+                Self::spec_be_to_nat(orig.take(i as int)) ==
+                    Self::spec_be_to_nat(v@.take(i as int)) * (d as nat) + rem,
+            decreases v.len() - i
+        {
+            let ghost prev = v@;
+            let b = v[i];
+
+            assert(b == orig[i as int]) by {
+                assert(prev.skip(i as int)[0] == orig.skip(i as int)[0]);
+            }
+
+            let cur = rem as u16 * 0x100 + b as u16;
+
+            assert(cur / (d as u16) < 0x100) by (nonlinear_arith)
+                requires cur == rem * 0x100 + b, rem < d, b < 0x100, d > 0 {}
+
+            let q = cur / d as u16;
+            let r = cur % d as u16;
+
+            vec_set(v, i, q as u8);
+
+            proof {
+                let t = Self::spec_be_to_nat(prev.take(i as int));
+
+                assert(v@.take(i as int) =~= prev.take(i as int));
+                assert(v@.skip(i + 1) =~= orig.skip(i + 1));
+                assert(v@.take(i + 1).drop_last() =~= v@.take(i as int));
+                assert(orig.take(i + 1).drop_last() =~= orig.take(i as int));
+                assert(v@.take(i + 1).last() == q);
+                assert(orig.take(i + 1).last() == b);
+
+                assert(cur == q * d + r && r < d) by (nonlinear_arith)
+                    requires q == cur / (d as int), r == cur % (d as int), d > 0 {}
+
+                assert((t * d + rem) * 0x100 + b == (t * 0x100 + q) * d + r) by (nonlinear_arith)
+                    requires cur == rem * 0x100 + b, cur == q * d + r {}
+            }
+
+            rem = r as u8;
+            i += 1;
+        }
+
+        proof {
+            assert(v@.take(i as int) =~= v@);
+            assert(orig.take(i as int) =~= orig);
+            lemma_fundamental_div_mod_converse(
+                Self::spec_be_to_nat(orig) as int,
+                d as int,
+                Self::spec_be_to_nat(v@) as int,
+                rem as int,
+            );
+        }
+
+        rem
+    }
+
+    fn be_is_zero(v: &Vec<u8>) -> (res: bool)
+        ensures res == (Self::spec_be_to_nat(v@) == 0)
+    {
+        let mut i = 0;
+
+        assert(v@.take(0) =~= Seq::<u8>::empty());
+
+        while i < v.len()
+            invariant
+                0 <= i <= v.len(),
+                Self::spec_be_to_nat(v@.take(i as int)) == 0,
+            decreases v.len() - i
+        {
+            assert(v@.take(i + 1).drop_last() =~= v@.take(i as int));
+            assert(v@.take(i + 1).last() == v@[i as int]);
+
+            if v[i] != 0 {
+                proof {
+                    let t = Self::spec_be_to_nat(v@.take(i + 1));
+                    let p = Self::spec_pow256(v@.skip(i + 1).len());
+
+                    assert(v@.take(i + 1) + v@.skip(i + 1) =~= v@);
+                    Self::lemma_be_concat(v@.take(i + 1), v@.skip(i + 1));
+                    Self::lemma_pow256_pos(v@.skip(i + 1).len());
+
+                    assert(t * p >= 1) by (nonlinear_arith)
+                        requires t >= 1, p >= 1 {}
+                }
+                return false;
+            }
+
+            i += 1;
+        }
+
+        assert(v@.take(i as int) =~= v@);
+        true
+    }
+
+    /// Prepend zeros to a big-endian natural number up to the given length
+    fn be_pad(v: &Vec<u8>, len: usize) -> (res: Vec<u8>)
+        requires v@.len() <= len
+        ensures
+            res@.len() == len,
+            Self::spec_be_to_nat(res@) == Self::spec_be_to_nat(v@),
+    {
+        let ghost zeros = Seq::new((len - v.len()) as nat, |_i: int| 0u8);
+        let mut res = Vec::new();
+        let mut i = 0;
+
+        while i < len - v.len()
+            invariant
+                v@.len() <= len,
+                0 <= i <= len - v.len(),
+                res@ == Seq::new(i as nat, |_i: int| 0u8),
+            decreases len - v.len() - i
+        {
+            res.push(0);
+            assert(res@ =~= Seq::new((i + 1) as nat, |_i: int| 0u8));
+            i += 1;
+        }
+
+        let mut j = 0;
+
+        assert(v@.take(0) =~= Seq::<u8>::empty());
+        assert(res@ =~= zeros + v@.take(0));
+
+        while j < v.len()
+            invariant
+                0 <= j <= v.len(),
+                res@ == zeros + v@.take(j as int),
+            decreases v.len() - j
+        {
+            res.push(v[j]);
+            assert(zeros + v@.take(j + 1) =~= (zeros + v@.take(j as int)).push(v@[j as int]));
+            j += 1;
+        }
+
+        proof {
+            assert(v@.take(j as int) =~= v@);
+            Self::lemma_be_zeros(zeros.len());
+            Self::lemma_be_concat(zeros, v@);
+        }
+
+        res
+    }
+
+    /// Compare two big-endian natural numbers
+    fn be_lt(a: &Vec<u8>, b: &Vec<u8>) -> (res: bool)
+        ensures res == (Self::spec_be_to_nat(a@) < Self::spec_be_to_nat(b@))
+    {
+        let len = if a.len() > b.len() { a.len() } else { b.len() };
+        let a = Self::be_pad(a, len);
+        let b = Self::be_pad(b, len);
+        let mut i = 0;
+
+        assert(a@.take(0) =~= b@.take(0));
+
+        while i < len
+            invariant
+                a@.len() == len,
+                b@.len() == len,
+                0 <= i <= len,
+                a@.take(i as int) == b@.take(i as int),
+            decreases len - i
+        {
+            if a[i] != b[i] {
+                proof {
+                    if a[i as int] < b[i as int] {
+                        Self::lemma_be_lex(a@, b@, i as int);
+                    } else {
+                        Self::lemma_be_lex(b@, a@, i as int);
+                    }
+                }
+                return a[i] < b[i];
+            }
+
+            assert(a@.take(i + 1) =~= a@.take(i as int).push(a@[i as int]));
+            assert(b@.take(i + 1) =~= b@.take(i as int).push(b@[i as int]));
+            i += 1;
+        }
+
+        assert(a@.take(i as int) =~= a@);
+        assert(b@.take(i as int) =~= b@);
+        false
+    }
+
+    /// Append the digits of a big-endian natural number to out
+    fn be_to_digits(mag: Vec<u8>, base: u8, out: &mut Vec<char>)
+        requires 2 <= base <= 16
+        ensures out@ == old(out)@ + Self::spec_nat_to_digits(Self::spec_be_to_nat(mag@), base as nat)
+    {
+        let ghost n = Self::spec_be_to_nat(mag@);
+        let ghost prefix = out@;
+        let digit_chars = [ '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F' ];
+
+        assert(digit_chars@ =~= Self::spec_digit_chars());
+
+        let mut mag = mag;
+        let mut rev = Vec::new();
+        let mut done = false;
+
+        assert(rev@.reverse() =~= Seq::<char>::empty());
+
+        // Collect digits from the least significant one
+        while !done
+            invariant
+                2 <= base <= 16,
+                digit_chars@ == Self::spec_digit_chars(),
+                done ==> Self::spec_nat_to_digits(n, base as nat) == rev@.reverse(),
+                !done ==> Self::spec_nat_to_digits(n, base as nat) ==
+                    Self::spec_nat_to_digits(Self::spec_be_to_nat(mag@), base as nat) + rev@.reverse(),
+            decreases if done { 0 } else { Self::spec_be_to_nat(mag@) + 1 }
+        {
+            let ghost m = Self::spec_be_to_nat(mag@);
+            let ghost prev = rev@;
+            let r = Self::be_div_rem(&mut mag, base);
+
+            rev.push(digit_chars[r as usize]);
+            assert(rev@.reverse() =~= seq![digit_chars@[r as int]] + prev.reverse());
+
+            if Self::be_is_zero(&mag) {
+                assert(m < base) by (nonlinear_arith)
+                    requires m / (base as nat) == 0, base > 0 {}
+                assert(m % (base as nat) == m) by (nonlinear_arith)
+                    requires m < base {}
+                assert(Self::spec_nat_to_digits(m, base as nat) == seq![digit_chars@[r as int]]);
+                done = true;
+            } else {
+                assert(m >= base && m / (base as nat) < m) by (nonlinear_arith)
+                    requires m / (base as nat) > 0, base >= 2 {}
+                assert(Self::spec_nat_to_digits(m, base as nat) + prev.reverse() =~=
+                    Self::spec_nat_to_digits(Self::spec_be_to_nat(mag@), base as nat) + rev@.reverse());
+            }
+        }
+
+        let mut i = 0;
+
+        assert(rev@.reverse().take(0) =~= Seq::<char>::empty());
+        assert(out@ =~= prefix + rev@.reverse().take(0));
+
+        while i < rev.len()
+            invariant
+                0 <= i <= rev.len(),
+                out@ == prefix + rev@.reverse().take(i as int),
+            decreases rev.len() - i
+        {
+            out.push(rev[rev.len() - 1 - i]);
+            assert(rev@.reverse().take(i + 1) =~= rev@.reverse().take(i as int).push(rev@[rev.len() - 1 - i]));
+            i += 1;
+        }
+
+        assert(rev@.reverse().take(i as int) =~= rev@.reverse());
+    }
+
+    /// Render the integer in the given base (see spec_int_to_string)
+    fn to_string_in_base(&self, base: u8) -> (res: String)
+        requires 2 <= base <= 16
+        ensures res@ == Self::spec_int_to_string(Self::spec_to_int(self@), base as nat)
+    {
+        let (neg, mag) = self.magnitude();
+        let mut chars = Vec::new();
+
+        if neg {
+            chars.push('-');
+        }
+
+        Self::be_to_digits(mag, base, &mut chars);
+        chars_to_string(chars.as_slice())
+    }
+
+    /// Compare two integers (together with polyfill_eq,
+    /// which coincides with integer equality by minimality)
+    pub fn lt(&self, other: &Self) -> (res: bool)
+        ensures res == (Self::spec_to_int(self@) < Self::spec_to_int(other@))
+    {
+        let (neg1, mag1) = self.magnitude();
+        let (neg2, mag2) = other.magnitude();
+
+        if neg1 != neg2 {
+            neg1
+        } else if neg1 {
+            // A larger absolute value means a smaller negative integer
+            Self::be_lt(&mag2, &mag1)
+        } else {
+            Self::be_lt(&mag1, &mag2)
+        }
+    }
+
+    /// Upper case hexadecimal rendering, e.g. 0x00ff => "FF"
+    pub fn to_hex(&self) -> (res: String)
+        ensures res@ == Self::spec_to_hex(self@)
+    {
+        self.to_string_in_base(16)
+    }
+
+    /// Decimal rendering
+    pub fn to_decimal(&self) -> (res: String)
+        ensures res@ == Self::spec_to_decimal(self@)
+    {
+        self.to_string_in_base(10)
+    }
 }
 
 impl SpecCombinator for BigInt {
@@ -209,3 +1159,79 @@ impl<'a> Debug for BigIntValue<'a> {
         write!(f, ")")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(bytes: &[u8]) -> BigIntValue {
+        let (_, v) = BigInt.parse(bytes).unwrap();
+        v
+    }
+
+    #[test]
+    fn to_int() {
+        assert_eq!(parse(&[ 0x01, 0x00 ]).to_i128(), Some(0));
+        assert_eq!(parse(&[ 0x01, 0x80 ]).to_i128(), Some(-128));
+        assert_eq!(parse(&[ 0x02, 0x00, 0xff ]).to_u64(), Some(255));
+        assert_eq!(parse(&[ 0x02, 0xff, 0x00 ]).to_i128(), Some(-256));
+        assert_eq!(parse(&[ 0x02, 0xff, 0x00 ]).to_u64(), None);
+        assert_eq!(parse(&[ 0x03, 0x01, 0x00, 0x01 ]).to_u64(), Some(65537));
+
+        // u64::MAX and u64::MAX + 1
+        assert_eq!(parse(&[ 0x09, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff ]).to_u64(), Some(u64::MAX));
+        assert_eq!(parse(&[ 0x09, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ]).to_u64(), None);
+
+        // i128::MIN and i128::MIN - 1
+        let mut min = vec![ 0x10, 0x80 ];
+        min.extend([0x00; 15]);
+        assert_eq!(parse(&min).to_i128(), Some(i128::MIN));
+
+        let mut below_min = vec![ 0x11, 0xff, 0x7f ];
+        below_min.extend([0xff; 15]);
+        assert_eq!(parse(&below_min).to_i128(), None);
+    }
+
+    #[test]
+    fn bit_len() {
+        assert_eq!(parse(&[ 0x01, 0x00 ]).bit_len(), Some(0));
+        assert_eq!(parse(&[ 0x01, 0x01 ]).bit_len(), Some(1));
+        assert_eq!(parse(&[ 0x02, 0x00, 0x80 ]).bit_len(), Some(8));
+        assert_eq!(parse(&[ 0x02, 0x01, 0x00 ]).bit_len(), Some(9));
+        assert_eq!(parse(&[ 0x01, 0xff ]).bit_len(), None);
+    }
+
+    #[test]
+    fn compare() {
+        let values = [
+            parse(&[ 0x02, 0xff, 0x00 ]), // -256
+            parse(&[ 0x01, 0x80 ]), // -128
+            parse(&[ 0x01, 0xff ]), // -1
+            parse(&[ 0x01, 0x00 ]), // 0
+            parse(&[ 0x01, 0x7f ]), // 127
+            parse(&[ 0x02, 0x00, 0x80 ]), // 128
+            parse(&[ 0x02, 0x01, 0x00 ]), // 256
+        ];
+
+        for i in 0..values.len() {
+            for j in 0..values.len() {
+                assert_eq!(values[i].lt(&values[j]), i < j, "{:?} < {:?}", values[i], values[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn render() {
+        assert_eq!(parse(&[ 0x01, 0x00 ]).to_hex(), "0");
+        assert_eq!(parse(&[ 0x01, 0x00 ]).to_decimal(), "0");
+        assert_eq!(parse(&[ 0x02, 0x00, 0xff ]).to_hex(), "FF");
+        assert_eq!(parse(&[ 0x02, 0x00, 0xff ]).to_decimal(), "255");
+        assert_eq!(parse(&[ 0x03, 0x01, 0x00, 0x01 ]).to_hex(), "10001");
+        assert_eq!(parse(&[ 0x03, 0x01, 0x00, 0x01 ]).to_decimal(), "65537");
+        assert_eq!(parse(&[ 0x01, 0x80 ]).to_hex(), "-80");
+        assert_eq!(parse(&[ 0x02, 0xff, 0x00 ]).to_decimal(), "-256");
+
+        // 2^64
+        assert_eq!(parse(&[ 0x09, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ]).to_decimal(), "18446744073709551616");
+    }
+}
@@ -0,0 +1,116 @@
+use vstd::prelude::*;
+
+use crate::common::*;
+
+use super::fixed_width_string::*;
+use super::tag::*;
+
+verus! {
+
+/// Combinator for BMPString in ASN.1,
+/// which is encoded in UCS-2 (2-byte big-endian code points
+/// in the Basic Multilingual Plane, no surrogates)
+#[derive(Debug, View)]
+pub struct BMPString;
+
+asn1_tagged!(BMPString, tag_of!(BMP_STRING));
+
+pub type SpecBMPStringValue = Seq<char>;
+pub type BMPStringValue = String;
+pub type BMPStringValueOwned = String;
+
+impl SpecCombinator for BMPString {
+    type SpecResult = SpecBMPStringValue;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        FixedWidthString(2).spec_parse(s)
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        FixedWidthString(2).spec_parse_wf(s)
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        FixedWidthString(2).spec_serialize(v)
+    }
+}
+
+impl SecureSpecCombinator for BMPString {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        FixedWidthString(2).theorem_serialize_parse_roundtrip(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        FixedWidthString(2).theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        FixedWidthString(2).lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl Combinator for BMPString {
+    type Result<'a> = BMPStringValue;
+    type Owned = BMPStringValueOwned;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        FixedWidthString(2).parse(s)
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        FixedWidthString(2).serialize(v, data, pos)
+    }
+}
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use der::Encode;
+
+    fn serialize_bmp_string(v: &str) -> Result<Vec<u8>, SerializeError> {
+        let mut data = vec![0; v.len() * 2 + 10];
+        data[0] = 0x1e; // Prepend the tag byte
+        let len = BMPString.serialize(v.to_string(), &mut data, 1)?;
+        data.truncate(len + 1);
+        Ok(data)
+    }
+
+    #[test]
+    fn diff_with_der() {
+        let diff = |s: &str| {
+            let res1 = serialize_bmp_string(s).map_err(|_| ());
+            let res2 = der::asn1::BmpString::from_utf8(s).unwrap().to_der().map_err(|_| ());
+            assert_eq!(res1, res2);
+        };
+
+        diff("");
+        diff("asdsad");
+        diff("黑风雷");
+        diff("黑风雷".repeat(256).as_str());
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(BMPString.parse(&[ 0x04, 0x00, 0x41, 0x00, 0x42 ]).unwrap(), (5, "AB".to_string()));
+
+        // Odd length
+        assert!(BMPString.parse(&[ 0x03, 0x00, 0x41, 0x00 ]).is_err());
+
+        // Surrogate pair
+        assert!(BMPString.parse(&[ 0x04, 0xD8, 0x3D, 0xDC, 0x68 ]).is_err());
+    }
+}
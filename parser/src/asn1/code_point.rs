@@ -0,0 +1,139 @@
+use vstd::prelude::*;
+
+use polyfill::*;
+
+use crate::common::*;
+
+use super::bounds::*;
+use super::var_int::*;
+
+verus! {
+
+/// Combinator for a single Unicode scalar value encoded
+/// as a `self.0`-byte big-endian integer, e.g.,
+/// 1 byte for (the Latin-1 subset of) T.61, 2 bytes for UCS-2,
+/// and 4 bytes for UCS-4.
+///
+/// Surrogates (0xD800 - 0xDFFF) and values above 0x10FFFF are rejected
+#[derive(Debug, View)]
+pub struct CodePoint(pub usize);
+
+impl CodePoint {
+    pub open spec fn wf_code_point(v: VarUIntResult) -> bool {
+        &&& v <= u32::MAX as VarUIntResult
+        &&& spec_is_unicode_scalar(v as u32)
+    }
+}
+
+impl SpecCombinator for CodePoint {
+    type SpecResult = char;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        match VarUInt(self.0).spec_parse(s) {
+            Ok((n, v)) =>
+                if Self::wf_code_point(v) {
+                    Ok((n, spec_char_from_u32(v as u32)))
+                } else {
+                    Err(())
+                },
+            Err(..) => Err(()),
+        }
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        VarUInt(self.0).spec_parse_wf(s);
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        VarUInt(self.0).spec_serialize(v as u32 as VarUIntResult)
+    }
+}
+
+impl SecureSpecCombinator for CodePoint {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        VarUInt(self.0).theorem_serialize_parse_roundtrip(v as u32 as VarUIntResult);
+        lemma_char_to_u32(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        VarUInt(self.0).theorem_parse_serialize_roundtrip(buf);
+
+        if let Ok((n, v)) = VarUInt(self.0).spec_parse(buf) {
+            lemma_char_from_u32(v as u32);
+        }
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        VarUInt(self.0).lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl Combinator for CodePoint {
+    type Result<'a> = char;
+    type Owned = char;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        Some(self.0)
+    }
+
+    fn length(&self) -> Option<usize> {
+        Some(self.0)
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        let (n, v) = VarUInt(self.0).parse(s)?;
+
+        if v <= u32::MAX as VarUIntResult {
+            if let Some(c) = char_from_u32(v as u32) {
+                return Ok((n, c));
+            }
+        }
+
+        Err(ParseError::Other("Invalid code point".to_string()))
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        VarUInt(self.0).serialize(v as u32 as VarUIntResult, data, pos)
+    }
+}
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ucs2() {
+        assert_eq!(CodePoint(2).parse(&[ 0x00, 0x41 ]).unwrap(), (2, 'A'));
+        assert_eq!(CodePoint(2).parse(&[ 0x9E, 0xD1 ]).unwrap(), (2, '黑'));
+
+        // Surrogates
+        assert!(CodePoint(2).parse(&[ 0xD8, 0x3D ]).is_err());
+        assert!(CodePoint(2).parse(&[ 0xDF, 0xFF ]).is_err());
+
+        assert!(CodePoint(2).parse(&[ 0x00 ]).is_err());
+    }
+
+    #[test]
+    fn ucs4() {
+        assert_eq!(CodePoint(4).parse(&[ 0x00, 0x01, 0xF4, 0x68 ]).unwrap(), (4, '👨'));
+
+        assert!(CodePoint(4).parse(&[ 0x00, 0x00, 0xD8, 0x00 ]).is_err());
+        assert!(CodePoint(4).parse(&[ 0x00, 0x11, 0x00, 0x00 ]).is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        let mut data = vec![0; 4];
+        assert_eq!(CodePoint(2).serialize('黑', &mut data, 0).unwrap(), 2);
+        assert_eq!(&data[..2], &[ 0x9E, 0xD1 ]);
+
+        // Does not fit in UCS-2
+        assert!(CodePoint(2).serialize('👨', &mut data, 0).is_err());
+    }
+}
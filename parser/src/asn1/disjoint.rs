@@ -48,17 +48,20 @@ impl<T1, T2, S> DisjointFrom<Pair<ASN1<T1>, S>> for ASN1<T2> where
     proof fn parse_disjoint_on(&self, other: &Pair<ASN1<T1>, S>, buf: Seq<u8>) {}
 }
 
-/// The other direction of the above
-impl<T1, T2, S> DisjointFrom<ASN1<T2>> for Pair<ASN1<T1>, S> where
-    T1: ASN1Tagged + SecureSpecCombinator,
+/// The other direction of the above (more generally,
+/// (C, ...) is disjoint from T2 if C is disjoint from T2)
+impl<C, T2, S> DisjointFrom<ASN1<T2>> for Pair<C, S> where
+    C: SecureSpecCombinator + DisjointFrom<ASN1<T2>>,
     T2: ASN1Tagged + SecureSpecCombinator,
     S: SecureSpecCombinator,
 {
     open spec fn disjoint_from(&self, other: &ASN1<T2>) -> bool {
-        other.0.spec_tag() != self.0.0.spec_tag()
+        self.0.disjoint_from(other)
     }
 
-    proof fn parse_disjoint_on(&self, other: &ASN1<T2>, buf: Seq<u8>) {}
+    proof fn parse_disjoint_on(&self, other: &ASN1<T2>, buf: Seq<u8>) {
+        self.0.parse_disjoint_on(other, buf);
+    }
 }
 
 /// If T2 and T3 are both disjoint from T1, then
@@ -109,6 +112,108 @@ impl<T> DisjointFrom<ASN1<T>> for End where
     proof fn parse_disjoint_on(&self, other: &ASN1<T>, buf: Seq<u8>) {}
 }
 
+impl<T> DisjointFrom<Unreachable> for ASN1<T> where
+    T: ASN1Tagged + SpecCombinator,
+{
+    open spec fn disjoint_from(&self, other: &Unreachable) -> bool { true }
+    proof fn parse_disjoint_on(&self, other: &Unreachable, buf: Seq<u8>) {}
+}
+
+impl DisjointFrom<Unreachable> for End {
+    open spec fn disjoint_from(&self, other: &Unreachable) -> bool { true }
+    proof fn parse_disjoint_on(&self, other: &Unreachable, buf: Seq<u8>) {}
+}
+
+/// The following impls are used for untagged CHOICEs in optional fields,
+/// through the unwrapped combinators generated by asn1_choice!
+/// (e.g. TimeUnwrapped), whose parsers are transparent.
+///
+/// If T is disjoint from both S1 and S2, then T is disjoint from
+/// OrdChoice<S1, S2> (vest has the other direction)
+impl<T, S1, S2> DisjointFrom<OrdChoice<S1, S2>> for ASN1<T> where
+    T: ASN1Tagged + SpecCombinator,
+    S1: SpecCombinator,
+    S2: SpecCombinator + DisjointFrom<S1>,
+    ASN1<T>: DisjointFrom<S1> + DisjointFrom<S2>,
+{
+    open spec fn disjoint_from(&self, other: &OrdChoice<S1, S2>) -> bool {
+        self.disjoint_from(&other.0) && self.disjoint_from(&other.1)
+    }
+
+    proof fn parse_disjoint_on(&self, other: &OrdChoice<S1, S2>, buf: Seq<u8>) {
+        self.parse_disjoint_on(&other.0, buf);
+        self.parse_disjoint_on(&other.1, buf);
+    }
+}
+
+/// Same as above, but for End
+impl<S1, S2> DisjointFrom<OrdChoice<S1, S2>> for End where
+    S1: SpecCombinator,
+    S2: SpecCombinator + DisjointFrom<S1>,
+    End: DisjointFrom<S1> + DisjointFrom<S2>,
+{
+    open spec fn disjoint_from(&self, other: &OrdChoice<S1, S2>) -> bool {
+        self.disjoint_from(&other.0) && self.disjoint_from(&other.1)
+    }
+
+    proof fn parse_disjoint_on(&self, other: &OrdChoice<S1, S2>, buf: Seq<u8>) {
+        self.parse_disjoint_on(&other.0, buf);
+        self.parse_disjoint_on(&other.1, buf);
+    }
+}
+
+/// Mapping does not change which buffers are accepted
+impl<T, C, M> DisjointFrom<Mapped<C, M>> for ASN1<T> where
+    T: ASN1Tagged + SpecCombinator,
+    C: SpecCombinator,
+    M: SpecIso<Src = C::SpecResult>,
+    C::SpecResult: SpecFrom<M::Dst>,
+    M::Dst: SpecFrom<C::SpecResult>,
+    ASN1<T>: DisjointFrom<C>,
+{
+    open spec fn disjoint_from(&self, other: &Mapped<C, M>) -> bool {
+        self.disjoint_from(&other.inner)
+    }
+
+    proof fn parse_disjoint_on(&self, other: &Mapped<C, M>, buf: Seq<u8>) {
+        self.parse_disjoint_on(&other.inner, buf);
+    }
+}
+
+/// Same as above, but for End
+impl<C, M> DisjointFrom<Mapped<C, M>> for End where
+    C: SpecCombinator,
+    M: SpecIso<Src = C::SpecResult>,
+    C::SpecResult: SpecFrom<M::Dst>,
+    M::Dst: SpecFrom<C::SpecResult>,
+    End: DisjointFrom<C>,
+{
+    open spec fn disjoint_from(&self, other: &Mapped<C, M>) -> bool {
+        self.disjoint_from(&other.inner)
+    }
+
+    proof fn parse_disjoint_on(&self, other: &Mapped<C, M>, buf: Seq<u8>) {
+        self.parse_disjoint_on(&other.inner, buf);
+    }
+}
+
+/// The other direction of the above
+impl<T, C, M> DisjointFrom<ASN1<T>> for Mapped<C, M> where
+    T: ASN1Tagged + SpecCombinator,
+    C: SpecCombinator + DisjointFrom<ASN1<T>>,
+    M: SpecIso<Src = C::SpecResult>,
+    C::SpecResult: SpecFrom<M::Dst>,
+    M::Dst: SpecFrom<C::SpecResult>,
+{
+    open spec fn disjoint_from(&self, other: &ASN1<T>) -> bool {
+        self.inner.disjoint_from(other)
+    }
+
+    proof fn parse_disjoint_on(&self, other: &ASN1<T>, buf: Seq<u8>) {
+        self.inner.parse_disjoint_on(other, buf);
+    }
+}
+
 impl<T1, T2> DisjointFrom<ASN1<T1>> for Cond<ASN1<T2>> where
     T1: ASN1Tagged + SpecCombinator,
     T2: ASN1Tagged + SpecCombinator,
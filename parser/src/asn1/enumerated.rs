@@ -0,0 +1,92 @@
+use vstd::prelude::*;
+
+use crate::common::*;
+
+use super::integer::*;
+use super::tag::*;
+
+verus! {
+
+/// Combinator for ENUMERATED in ASN.1
+/// The encoding of the content is the same as INTEGER
+/// (X.690, 8.4), including the minimality requirement
+#[derive(Debug, View)]
+pub struct Enumerated;
+
+asn1_tagged!(Enumerated, tag_of!(ENUMERATED));
+
+pub type EnumeratedValue = IntegerValue;
+
+impl SpecCombinator for Enumerated {
+    type SpecResult = EnumeratedValue;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        Integer.spec_parse(s)
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        Integer.spec_parse_wf(s)
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        Integer.spec_serialize(v)
+    }
+}
+
+impl SecureSpecCombinator for Enumerated {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        Integer.theorem_serialize_parse_roundtrip(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        Integer.theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        Integer.lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl Combinator for Enumerated {
+    type Result<'a> = EnumeratedValue;
+    type Owned = EnumeratedValue;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        Integer.parse(s)
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        Integer.serialize(v, data, pos)
+    }
+}
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::asn1::*;
+
+    #[test]
+    fn enumerated() {
+        assert_eq!(ASN1(Enumerated).parse(&[ 0x0a, 0x01, 0x01 ]).unwrap(), (3, 1));
+
+        // Different tag from INTEGER
+        assert!(ASN1(Enumerated).parse(&[ 0x02, 0x01, 0x01 ]).is_err());
+
+        // Same minimality requirement as INTEGER
+        assert!(ASN1(Enumerated).parse(&[ 0x0a, 0x02, 0x00, 0x01 ]).is_err());
+    }
+}
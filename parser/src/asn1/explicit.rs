@@ -31,15 +31,15 @@ impl<T: SpecCombinator> SpecCombinator for ExplicitTag<T> {
     type SpecResult = T::SpecResult;
 
     open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
-        LengthWrapped(self.1).spec_parse(s)
+        LengthWrapped(self.1, Length).spec_parse(s)
     }
 
     proof fn spec_parse_wf(&self, s: Seq<u8>) {
-        LengthWrapped(self.1).spec_parse_wf(s)
+        LengthWrapped(self.1, Length).spec_parse_wf(s)
     }
 
     open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
-        LengthWrapped(self.1).spec_serialize(v)
+        LengthWrapped(self.1, Length).spec_serialize(v)
     }
 }
 
@@ -49,15 +49,15 @@ impl<T: SecureSpecCombinator> SecureSpecCombinator for ExplicitTag<T> {
     }
 
     proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
-        LengthWrapped(self.1).theorem_serialize_parse_roundtrip(v)
+        LengthWrapped(self.1, Length).theorem_serialize_parse_roundtrip(v)
     }
 
     proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
-        LengthWrapped(self.1).theorem_parse_serialize_roundtrip(buf)
+        LengthWrapped(self.1, Length).theorem_parse_serialize_roundtrip(buf)
     }
 
     proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
-        LengthWrapped(self.1).lemma_prefix_secure(s1, s2)
+        LengthWrapped(self.1, Length).lemma_prefix_secure(s1, s2)
     }
 }
 
@@ -81,7 +81,7 @@ impl<T: Combinator> Combinator for ExplicitTag<T> where
     }
 
     fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
-        LengthWrapped(&self.1).parse(s)
+        LengthWrapped(&self.1, Length).parse(s)
     }
 
     open spec fn serialize_requires(&self) -> bool {
@@ -89,7 +89,7 @@ impl<T: Combinator> Combinator for ExplicitTag<T> where
     }
 
     fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
-        LengthWrapped(&self.1).serialize(v, data, pos)
+        LengthWrapped(&self.1, Length).serialize(v, data, pos)
     }
 }
 
@@ -0,0 +1,106 @@
+use vstd::prelude::*;
+
+use polyfill::*;
+
+use crate::common::*;
+
+use super::code_point::*;
+use super::len::*;
+use super::len_wrapped::*;
+
+verus! {
+
+/// Combinator for a length-prefixed string in which each character
+/// is encoded as a fixed-width (`self.0` bytes) big-endian code point.
+/// The result is decoded into a String.
+///
+/// Used to implement BMPString (UCS-2), UniversalString (UCS-4)
+/// and TeletexString (1 byte per character)
+#[derive(Debug, View)]
+pub struct FixedWidthString(pub usize);
+
+pub type SpecFixedWidthStringValue = Seq<char>;
+pub type FixedWidthStringValue = String;
+pub type FixedWidthStringValueOwned = String;
+
+impl FixedWidthString {
+    pub open spec fn spec_inner(&self) -> LengthWrapped<Repeat<CodePoint>> {
+        LengthWrapped(Repeat(CodePoint(self.0)), Length)
+    }
+}
+
+impl SpecCombinator for FixedWidthString {
+    type SpecResult = SpecFixedWidthStringValue;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        self.spec_inner().spec_parse(s)
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        self.spec_inner().spec_parse_wf(s)
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        self.spec_inner().spec_serialize(v)
+    }
+}
+
+impl SecureSpecCombinator for FixedWidthString {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        self.spec_inner().theorem_serialize_parse_roundtrip(v)
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        self.spec_inner().theorem_parse_serialize_roundtrip(buf)
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        self.spec_inner().lemma_prefix_secure(s1, s2)
+    }
+}
+
+impl Combinator for FixedWidthString {
+    type Result<'a> = FixedWidthStringValue;
+    type Owned = FixedWidthStringValueOwned;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        let (n, chars) = LengthWrapped(Repeat(CodePoint(self.0)), Length).parse(s)?;
+        let chars = chars.to_vec();
+
+        assert(chars@ =~= VecDeep(*chars)@);
+
+        Ok((n, chars_to_string(chars.as_slice())))
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        let s = v.as_str();
+        let len = s.unicode_len();
+        let mut chars = VecDeep::new();
+
+        for i in 0..len
+            invariant
+                len == s@.len(),
+                chars@ =~= s@.take(i as int),
+        {
+            chars.push(s.get_char(i));
+        }
+
+        assert(chars@ =~= v@);
+
+        LengthWrapped(Repeat(CodePoint(self.0)), Length).serialize(chars, data, pos)
+    }
+}
+
+}
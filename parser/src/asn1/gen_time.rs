@@ -1,6 +1,7 @@
 use vstd::prelude::*;
 
 use crate::common::*;
+use super::len::*;
 use super::len_wrapped::*;
 use super::tag::*;
 use super::utc_time::*;
@@ -54,13 +55,13 @@ impl SpecCombinator for GeneralizedTime {
     type SpecResult = GeneralizedTimeValueInner;
 
     open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
-        LengthWrapped(GeneralizedTimeInner).spec_parse(s)
+        LengthWrapped(GeneralizedTimeInner, Length).spec_parse(s)
     }
 
     proof fn spec_parse_wf(&self, s: Seq<u8>) {}
 
     open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
-        LengthWrapped(GeneralizedTimeInner).spec_serialize(v)
+        LengthWrapped(GeneralizedTimeInner, Length).spec_serialize(v)
     }
 }
 
@@ -70,15 +71,15 @@ impl SecureSpecCombinator for GeneralizedTime {
     }
 
     proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
-        LengthWrapped(GeneralizedTimeInner).theorem_serialize_parse_roundtrip(v);
+        LengthWrapped(GeneralizedTimeInner, Length).theorem_serialize_parse_roundtrip(v);
     }
 
     proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
-        LengthWrapped(GeneralizedTimeInner).theorem_parse_serialize_roundtrip(buf);
+        LengthWrapped(GeneralizedTimeInner, Length).theorem_parse_serialize_roundtrip(buf);
     }
 
     proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
-        LengthWrapped(GeneralizedTimeInner).lemma_prefix_secure(s1, s2);
+        LengthWrapped(GeneralizedTimeInner, Length).lemma_prefix_secure(s1, s2);
     }
 }
 
@@ -95,11 +96,11 @@ impl Combinator for GeneralizedTime {
     }
 
     fn parse<'a>(&self, v: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
-        LengthWrapped(GeneralizedTimeInner).parse(v)
+        LengthWrapped(GeneralizedTimeInner, Length).parse(v)
     }
 
     fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
-        LengthWrapped(GeneralizedTimeInner).serialize(v, data, pos)
+        LengthWrapped(GeneralizedTimeInner, Length).serialize(v, data, pos)
     }
 }
 
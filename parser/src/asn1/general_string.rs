@@ -0,0 +1,185 @@
+use std::fmt::{self, Debug, Formatter};
+use vstd::prelude::*;
+
+use polyfill::*;
+
+use crate::common::*;
+
+use super::utf8_string::*;
+use super::tag::*;
+
+verus! {
+
+/// Combinator for GeneralString in ASN.1
+/// Essentially a wrapper around UTF8String
+/// that checks that each character is printable ASCII
+///
+/// NOTE: GeneralString allows switching character sets
+/// with ISO 2022 escape sequences (ESC, SO, SI), which we
+/// do not interpret; only the printable characters of the
+/// default (ASCII) G0 set are accepted
+#[derive(Debug, View)]
+pub struct GeneralString;
+
+asn1_tagged!(GeneralString, tag_of!(GENERAL_STRING));
+
+pub type SpecGeneralStringValue = Seq<char>;
+pub type GeneralStringValue<'a> = &'a str;
+pub type GeneralStringValueOwned = String;
+
+impl SpecCombinator for GeneralString {
+    type SpecResult = SpecGeneralStringValue;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        Refined {
+            inner: UTF8String,
+            predicate: GeneralStringPred,
+        }.spec_parse(s)
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        Refined {
+            inner: UTF8String,
+            predicate: GeneralStringPred,
+        }.spec_parse_wf(s)
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        Refined {
+            inner: UTF8String,
+            predicate: GeneralStringPred,
+        }.spec_serialize(v)
+    }
+}
+
+impl SecureSpecCombinator for GeneralString {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        Refined {
+            inner: UTF8String,
+            predicate: GeneralStringPred,
+        }.theorem_serialize_parse_roundtrip(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        Refined {
+            inner: UTF8String,
+            predicate: GeneralStringPred,
+        }.theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        Refined {
+            inner: UTF8String,
+            predicate: GeneralStringPred,
+        }.lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl Combinator for GeneralString {
+    type Result<'a> = GeneralStringValue<'a>;
+    type Owned = GeneralStringValueOwned;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        Refined {
+            inner: UTF8String,
+            predicate: GeneralStringPred,
+        }.parse(s)
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        Refined {
+            inner: UTF8String,
+            predicate: GeneralStringPred,
+        }.serialize(v, data, pos)
+    }
+}
+
+/// Characters allowed in a GeneralString: printable ASCII of the
+/// default G0 set, without ISO 2022 escape sequences
+#[derive(View)]
+pub struct GeneralStringPred;
+
+impl GeneralStringPred {
+    pub open spec fn wf_char(c: char) -> bool {
+        ' ' <= c && c <= '~'
+    }
+
+    fn exec_wf_char(c: char) -> (res: bool)
+        ensures res == Self::wf_char(c)
+    {
+        ' ' <= c && c <= '~'
+    }
+}
+
+impl SpecPred for GeneralStringPred {
+    type Input = Seq<char>;
+
+    open spec fn spec_apply(&self, s: &Self::Input) -> bool {
+        forall |i| 0 <= i < s.len() ==> #[trigger] Self::wf_char(s[i])
+    }
+}
+
+impl Pred for GeneralStringPred {
+    type Input<'a> = &'a str;
+    type InputOwned = String;
+
+    fn apply(&self, s: &Self::Input<'_>) -> (res: bool)
+    {
+        let len = s.unicode_len();
+        for i in 0..len
+            invariant
+                len == s@.len(),
+                forall |j| 0 <= j < i ==> #[trigger] Self::wf_char(s@[j]),
+        {
+            if !Self::exec_wf_char(s.get_char(i)) {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(GeneralString.parse(&[ 0x05, 0x68, 0x65, 0x6c, 0x6c, 0x6f ]).unwrap(), (6, "hello"));
+        assert_eq!(GeneralString.parse(&[ 0x00 ]).unwrap(), (1, ""));
+
+        // ISO 2022 escape sequence (ESC $ B)
+        assert!(GeneralString.parse(&[ 0x03, 0x1b, 0x24, 0x42 ]).is_err());
+
+        // Shift out/in
+        assert!(GeneralString.parse(&[ 0x01, 0x0e ]).is_err());
+        assert!(GeneralString.parse(&[ 0x01, 0x0f ]).is_err());
+
+        // DEL and non-ASCII
+        assert!(GeneralString.parse(&[ 0x01, 0x7f ]).is_err());
+        assert!(GeneralString.parse(&[ 0x02, 0xc3, 0xbc ]).is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        let mut data = vec![0; 10];
+        assert_eq!(GeneralString.serialize("a b", &mut data, 0).unwrap(), 4);
+        assert_eq!(&data[..4], &[ 0x03, 0x61, 0x20, 0x62 ]);
+
+        assert!(GeneralString.serialize("\x1b", &mut data, 0).is_err());
+    }
+}
@@ -7,64 +7,68 @@ use super::len::*;
 
 verus! {
 
-/// Wrap the inner combinator in a Length-Buffer pair
+/// Wrap the inner combinator in a Length-Buffer pair, where the length
+/// prefix is parsed by L (the DER Length by default; see crate::tls for
+/// a fixed-width length)
 #[derive(Debug)]
-pub struct LengthWrapped<T>(pub T);
+pub struct LengthWrapped<T, L = Length>(pub T, pub L);
 
-impl<T: View> View for LengthWrapped<T> {
-    type V = LengthWrapped<T::V>;
+impl<T: View, L: View> View for LengthWrapped<T, L> {
+    type V = LengthWrapped<T::V, L::V>;
 
     open spec fn view(&self) -> Self::V {
-        LengthWrapped(self.0@)
+        LengthWrapped(self.0@, self.1@)
     }
 }
 
-impl<T: SpecCombinator> SpecCombinator for LengthWrapped<T> {
+impl<T: SpecCombinator, L: SecureSpecCombinator<SpecResult = LengthValue>> SpecCombinator for LengthWrapped<T, L> {
     type SpecResult = T::SpecResult;
 
     open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
-        match new_spec_length_wrapped_inner(self.0).spec_parse(s) {
+        match new_spec_length_wrapped_inner(self.0, self.1).spec_parse(s) {
             Ok((len, (_, v))) => Ok((len, v)),
             Err(..) => Err(()),
         }
     }
 
     proof fn spec_parse_wf(&self, s: Seq<u8>) {
-        new_spec_length_wrapped_inner(self.0).spec_parse_wf(s)
+        new_spec_length_wrapped_inner(self.0, self.1).spec_parse_wf(s)
     }
 
     open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
         match self.0.spec_serialize(v) {
             // Need to compute the inner serialized length first
-            Ok(buf) => new_spec_length_wrapped_inner(self.0).spec_serialize((buf.len() as LengthValue, v)),
+            Ok(buf) => new_spec_length_wrapped_inner(self.0, self.1).spec_serialize((buf.len() as LengthValue, v)),
             Err(..) => Err(()),
         }
     }
 }
 
-impl<T: SecureSpecCombinator> SecureSpecCombinator for LengthWrapped<T> {
+impl<T: SecureSpecCombinator, L: SecureSpecCombinator<SpecResult = LengthValue>> SecureSpecCombinator for LengthWrapped<T, L> {
     open spec fn is_prefix_secure() -> bool {
-        true
+        L::is_prefix_secure()
     }
 
     proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
         if let Ok(buf) = self.0.spec_serialize(v) {
-            new_spec_length_wrapped_inner(self.0).theorem_serialize_parse_roundtrip((buf.len() as LengthValue, v))
+            new_spec_length_wrapped_inner(self.0, self.1).theorem_serialize_parse_roundtrip((buf.len() as LengthValue, v))
         }
     }
 
     proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
-        new_spec_length_wrapped_inner(self.0).theorem_parse_serialize_roundtrip(buf)
+        new_spec_length_wrapped_inner(self.0, self.1).theorem_parse_serialize_roundtrip(buf)
     }
 
     proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
-        new_spec_length_wrapped_inner(self.0).lemma_prefix_secure(s1, s2)
+        new_spec_length_wrapped_inner(self.0, self.1).lemma_prefix_secure(s1, s2)
     }
 }
 
-impl<T: Combinator> Combinator for LengthWrapped<T> where
+impl<T: Combinator, L: Combinator> Combinator for LengthWrapped<T, L> where
     <T as View>::V: SecureSpecCombinator<SpecResult = <<T as Combinator>::Owned as View>::V>,
     for<'a> T::Result<'a>: PolyfillClone,
+    <L as View>::V: SecureSpecCombinator<SpecResult = LengthValue>,
+    L: for<'a> Combinator<Result<'a> = LengthValue, Owned = LengthValue>,
 {
     type Result<'a> = T::Result<'a>;
     type Owned = T::Owned;
@@ -78,22 +82,26 @@ impl<T: Combinator> Combinator for LengthWrapped<T> where
     }
 
     open spec fn parse_requires(&self) -> bool {
-        self.0.parse_requires()
+        &&& self.0.parse_requires()
+        &&& self.1.parse_requires()
+        &&& L::V::is_prefix_secure()
     }
 
     fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
-        let (len, (_, v)) = new_length_wrapped_inner(&self.0).parse(s)?;
+        let (len, (_, v)) = new_length_wrapped_inner(&self.0, &self.1).parse(s)?;
         Ok((len, v))
     }
 
     open spec fn serialize_requires(&self) -> bool {
-        self.0.serialize_requires()
+        &&& self.0.serialize_requires()
+        &&& self.1.serialize_requires()
+        &&& L::V::is_prefix_secure()
     }
 
     fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
         // TODO: can we avoid serializing twice?
         let len = self.0.serialize(v.clone(), data, pos)?;
-        let final_len = new_length_wrapped_inner(&self.0).serialize((len as LengthValue, v), data, pos)?;
+        let final_len = new_length_wrapped_inner(&self.0, &self.1).serialize((len as LengthValue, v), data, pos)?;
 
         if pos < data.len() && final_len < data.len() - pos {
             assert(data@ =~= seq_splice(old(data)@, pos, self@.spec_serialize(v@).unwrap()));
@@ -128,13 +136,13 @@ impl<'b, T: Combinator> Continuation for LengthWrappedCont<'b, T> where
     }
 }
 
-type SpecLengthWrappedInner<T> = SpecDepend<Length, AndThen<Bytes, T>>;
-type LengthWrappedInner<'a, T> = Depend<Length, AndThen<Bytes, &'a T>, LengthWrappedCont<'a, T>>;
+type SpecLengthWrappedInner<T, L> = SpecDepend<L, AndThen<Bytes, T>>;
+type LengthWrappedInner<'a, T, L> = Depend<&'a L, AndThen<Bytes, &'a T>, LengthWrappedCont<'a, T>>;
 
 /// SpecDepend version of new_length_wrapped_inner
-pub open spec fn new_spec_length_wrapped_inner<T: SpecCombinator>(inner: T) -> SpecLengthWrappedInner<T> {
+pub open spec fn new_spec_length_wrapped_inner<T: SpecCombinator, L: SecureSpecCombinator<SpecResult = LengthValue>>(inner: T, len: L) -> SpecLengthWrappedInner<T, L> {
     SpecDepend {
-        fst: Length,
+        fst: len,
         snd: |l| {
             AndThen(Bytes(l as usize), inner)
         },
@@ -142,11 +150,13 @@ pub open spec fn new_spec_length_wrapped_inner<T: SpecCombinator>(inner: T) -> S
 }
 
 /// Spec version of new_length_wrapped_inner
-closed spec fn new_length_wrapped_inner_spec<'a, T: Combinator>(inner: &'a T) -> LengthWrappedInner<'a, T> where
+closed spec fn new_length_wrapped_inner_spec<'a, T: Combinator, L: Combinator>(inner: &'a T, len: &'a L) -> LengthWrappedInner<'a, T, L> where
     <T as View>::V: SecureSpecCombinator<SpecResult = <<T as Combinator>::Owned as View>::V>,
+    <L as View>::V: SecureSpecCombinator<SpecResult = LengthValue>,
+    L: for<'b> Combinator<Result<'b> = LengthValue, Owned = LengthValue>,
 {
     Depend {
-        fst: Length,
+        fst: len,
         snd: LengthWrappedCont(inner),
         spec_snd: Ghost(|l| {
             AndThen(Bytes(l as usize), inner@)
@@ -154,15 +164,17 @@ closed spec fn new_length_wrapped_inner_spec<'a, T: Combinator>(inner: &'a T) ->
     }
 }
 
-fn new_length_wrapped_inner<'a, T: Combinator>(inner: &'a T) -> (res: LengthWrappedInner<'a, T>) where
+fn new_length_wrapped_inner<'a, T: Combinator, L: Combinator>(inner: &'a T, len: &'a L) -> (res: LengthWrappedInner<'a, T, L>) where
     <T as View>::V: SecureSpecCombinator<SpecResult = <<T as Combinator>::Owned as View>::V>,
+    <L as View>::V: SecureSpecCombinator<SpecResult = LengthValue>,
+    L: for<'b> Combinator<Result<'b> = LengthValue, Owned = LengthValue>,
 
     ensures
-        res == new_length_wrapped_inner_spec(inner),
-        res@ == new_spec_length_wrapped_inner(inner@),
+        res == new_length_wrapped_inner_spec(inner, len),
+        res@ == new_spec_length_wrapped_inner(inner@, len@),
 {
     Depend {
-        fst: Length,
+        fst: len,
         snd: LengthWrappedCont(inner),
         spec_snd: Ghost(|l| {
             AndThen(Bytes(l as usize), inner@)
@@ -3,12 +3,22 @@
 mod var_int;
 mod len;
 mod integer;
+mod enumerated;
+mod real;
 mod bounds;
 mod octet_string;
 mod utf8_string;
 mod bit_string;
 mod ia5_string;
+mod code_point;
+mod fixed_width_string;
+mod bmp_string;
+mod universal_string;
+mod teletex_string;
 mod printable_string;
+mod numeric_string;
+mod visible_string;
+mod general_string;
 mod base128;
 mod oid;
 mod tag;
@@ -16,22 +26,35 @@ mod implicit;
 mod explicit;
 mod big_int;
 mod seq_of;
+mod set_of;
 mod boolean;
 mod len_wrapped;
 mod disjoint;
 mod null;
 mod utc_time;
 mod gen_time;
+mod ber;
+mod any_tlv;
 
 pub use bounds::UInt;
 pub use var_int::*;
 pub use len::*;
 pub use integer::*;
+pub use enumerated::*;
+pub use real::*;
 pub use octet_string::*;
 pub use utf8_string::*;
 pub use bit_string::*;
 pub use ia5_string::*;
+pub use code_point::*;
+pub use fixed_width_string::*;
+pub use bmp_string::*;
+pub use universal_string::*;
+pub use teletex_string::*;
 pub use printable_string::*;
+pub use numeric_string::*;
+pub use visible_string::*;
+pub use general_string::*;
 pub use base128::*;
 pub use oid::*;
 pub use tag::*;
@@ -39,9 +62,12 @@ pub use implicit::*;
 pub use explicit::*;
 pub use big_int::*;
 pub use seq_of::*;
+pub use set_of::*;
 pub use boolean::*;
 pub use len_wrapped::*;
 pub use disjoint::*;
 pub use null::*;
 pub use utc_time::*;
 pub use gen_time::*;
+pub use ber::*;
+pub use any_tlv::*;
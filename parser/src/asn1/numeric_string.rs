@@ -0,0 +1,175 @@
+use std::fmt::{self, Debug, Formatter};
+use vstd::prelude::*;
+
+use polyfill::*;
+
+use crate::common::*;
+
+use super::utf8_string::*;
+use super::tag::*;
+
+verus! {
+
+/// Combinator for NumericString in ASN.1
+/// Essentially a wrapper around UTF8String
+/// that checks that each character is a digit or a space
+#[derive(Debug, View)]
+pub struct NumericString;
+
+asn1_tagged!(NumericString, tag_of!(NUMERIC_STRING));
+
+pub type SpecNumericStringValue = Seq<char>;
+pub type NumericStringValue<'a> = &'a str;
+pub type NumericStringValueOwned = String;
+
+impl SpecCombinator for NumericString {
+    type SpecResult = SpecNumericStringValue;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        Refined {
+            inner: UTF8String,
+            predicate: NumericStringPred,
+        }.spec_parse(s)
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        Refined {
+            inner: UTF8String,
+            predicate: NumericStringPred,
+        }.spec_parse_wf(s)
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        Refined {
+            inner: UTF8String,
+            predicate: NumericStringPred,
+        }.spec_serialize(v)
+    }
+}
+
+impl SecureSpecCombinator for NumericString {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        Refined {
+            inner: UTF8String,
+            predicate: NumericStringPred,
+        }.theorem_serialize_parse_roundtrip(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        Refined {
+            inner: UTF8String,
+            predicate: NumericStringPred,
+        }.theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        Refined {
+            inner: UTF8String,
+            predicate: NumericStringPred,
+        }.lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl Combinator for NumericString {
+    type Result<'a> = NumericStringValue<'a>;
+    type Owned = NumericStringValueOwned;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        Refined {
+            inner: UTF8String,
+            predicate: NumericStringPred,
+        }.parse(s)
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        Refined {
+            inner: UTF8String,
+            predicate: NumericStringPred,
+        }.serialize(v, data, pos)
+    }
+}
+
+/// Characters allowed in a NumericString: digits and space
+#[derive(View)]
+pub struct NumericStringPred;
+
+impl NumericStringPred {
+    pub open spec fn wf_char(c: char) -> bool {
+        ||| ('0' <= c && c <= '9')
+        ||| c == ' '
+    }
+
+    fn exec_wf_char(c: char) -> (res: bool)
+        ensures res == Self::wf_char(c)
+    {
+        ('0' <= c && c <= '9') ||
+        c == ' '
+    }
+}
+
+impl SpecPred for NumericStringPred {
+    type Input = Seq<char>;
+
+    open spec fn spec_apply(&self, s: &Self::Input) -> bool {
+        forall |i| 0 <= i < s.len() ==> #[trigger] Self::wf_char(s[i])
+    }
+}
+
+impl Pred for NumericStringPred {
+    type Input<'a> = &'a str;
+    type InputOwned = String;
+
+    fn apply(&self, s: &Self::Input<'_>) -> (res: bool)
+    {
+        let len = s.unicode_len();
+        for i in 0..len
+            invariant
+                len == s@.len(),
+                forall |j| 0 <= j < i ==> #[trigger] Self::wf_char(s@[j]),
+        {
+            if !Self::exec_wf_char(s.get_char(i)) {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(NumericString.parse(&[ 0x05, 0x31, 0x32, 0x20, 0x33, 0x34 ]).unwrap(), (6, "12 34"));
+        assert_eq!(NumericString.parse(&[ 0x00 ]).unwrap(), (1, ""));
+
+        // Letters, punctuation and control characters
+        assert!(NumericString.parse(&[ 0x02, 0x31, 0x61 ]).is_err());
+        assert!(NumericString.parse(&[ 0x01, 0x2d ]).is_err());
+        assert!(NumericString.parse(&[ 0x01, 0x0a ]).is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        let mut data = vec![0; 10];
+        assert_eq!(NumericString.serialize("0 9", &mut data, 0).unwrap(), 4);
+        assert_eq!(&data[..4], &[ 0x03, 0x30, 0x20, 0x39 ]);
+
+        assert!(NumericString.serialize("1a", &mut data, 0).is_err());
+    }
+}
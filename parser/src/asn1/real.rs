@@ -0,0 +1,108 @@
+use vstd::prelude::*;
+
+use crate::common::*;
+
+use super::octet_string::*;
+use super::tag::*;
+
+verus! {
+
+/// Combinator for REAL in ASN.1
+///
+/// NOTE: the content octets (X.690, 8.5) are kept as they are:
+/// we do not decode the binary/decimal forms or check the DER
+/// restrictions on them (X.690, 11.3), since REAL is only used
+/// in a few vendor extensions that we treat as opaque
+#[derive(Debug, View)]
+pub struct Real;
+
+asn1_tagged!(Real, tag_of!(REAL));
+
+pub type SpecRealValue = Seq<u8>;
+pub type RealValue<'a> = &'a [u8];
+pub type RealValueOwned = Vec<u8>;
+
+impl SpecCombinator for Real {
+    type SpecResult = SpecRealValue;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        OctetString.spec_parse(s)
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        OctetString.spec_parse_wf(s)
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        OctetString.spec_serialize(v)
+    }
+}
+
+impl SecureSpecCombinator for Real {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        OctetString.theorem_serialize_parse_roundtrip(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        OctetString.theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        OctetString.lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl Combinator for Real {
+    type Result<'a> = RealValue<'a>;
+    type Owned = RealValueOwned;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        OctetString.parse(s)
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        OctetString.serialize(v, data, pos)
+    }
+}
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asn1::*;
+
+    #[test]
+    fn parse() {
+        // 0 is encoded with no content octets
+        assert_eq!(ASN1(Real).parse(&[ 0x09, 0x00 ]).unwrap(), (2, &[][..]));
+
+        // Binary encoding of 1.5 = 3 * 2^-1, kept as-is
+        assert_eq!(ASN1(Real).parse(&[ 0x09, 0x03, 0x80, 0xff, 0x03 ]).unwrap(), (5, &[ 0x80, 0xff, 0x03 ][..]));
+
+        // Wrong tag (OCTET STRING)
+        assert!(ASN1(Real).parse(&[ 0x04, 0x00 ]).is_err());
+
+        // Truncated
+        assert!(ASN1(Real).parse(&[ 0x09, 0x02, 0x40 ]).is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        let mut data = vec![0; 10];
+        assert_eq!(ASN1(Real).serialize(&[ 0x40 ], &mut data, 0).unwrap(), 3);
+        assert_eq!(&data[..3], &[ 0x09, 0x01, 0x40 ]);
+    }
+}
@@ -0,0 +1,532 @@
+use vstd::prelude::*;
+use vstd::slice::slice_subrange;
+
+use polyfill::*;
+
+use crate::common::*;
+
+use super::tag::*;
+use super::explicit::*;
+
+verus! {
+
+/// SET OF in ASN.1
+///
+/// Same as SEQUENCE OF, except that DER (X.690, 11.6) requires the
+/// encodings of the elements to appear in ascending order, where the
+/// shorter encoding is padded with trailing zeros for comparison.
+/// Encodings violating the order are rejected.
+///
+/// Since the order of elements is fixed by their encodings, the
+/// order of the parsed elements carries no extra information,
+/// and consumers should treat the result as an unordered collection.
+///
+/// NOTE: this is stricter than parsing SET OF as SEQUENCE OF (as done
+/// before for RDNs), so e.g. certificates with unsorted multi-valued
+/// RDNs are now rejected. Use UnorderedSetOf to accept any order.
+#[derive(Debug, View)]
+pub struct SetOf<C>(pub C);
+
+pub type SetOfValue<T> = VecDeep<T>;
+
+impl<C> ASN1Tagged for SetOf<C> {
+    open spec fn spec_tag(&self) -> TagValue {
+        tag_of!(SET)
+    }
+
+    fn tag(&self) -> TagValue {
+        tag_of!(SET)
+    }
+}
+
+impl<C: View> ViewWithASN1Tagged for SetOf<C> {
+    proof fn lemma_view_preserves_tag(&self) {}
+}
+
+impl<C: SecureSpecCombinator + SpecCombinator> SpecCombinator for SetOf<C> {
+    type SpecResult = Seq<C::SpecResult>;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        ExplicitTag(self.spec_tag(), SortedRepeat(self.0)).spec_parse(s)
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {}
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        ExplicitTag(self.spec_tag(), SortedRepeat(self.0)).spec_serialize(v)
+    }
+}
+
+impl<C: SecureSpecCombinator + SpecCombinator> SecureSpecCombinator for SetOf<C> {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        ExplicitTag(self.spec_tag(), SortedRepeat(self.0)).theorem_serialize_parse_roundtrip(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        ExplicitTag(self.spec_tag(), SortedRepeat(self.0)).theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        ExplicitTag(self.spec_tag(), SortedRepeat(self.0)).lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl<C: Combinator> Combinator for SetOf<C> where
+    <C as View>::V: SecureSpecCombinator<SpecResult = <C::Owned as View>::V>,
+    for<'a> C::Result<'a>: PolyfillClone,
+{
+    type Result<'a> = SetOfValue<C::Result<'a>>;
+    type Owned = SetOfValue<C::Owned>;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    open spec fn parse_requires(&self) -> bool {
+        &&& <C as View>::V::is_prefix_secure()
+        &&& self.0.parse_requires()
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        ExplicitTag(self.tag(), SortedRepeat(&self.0)).parse(s)
+    }
+
+    /// Also requires parse_requires, since the order is
+    /// checked by parsing back the serialized elements
+    open spec fn serialize_requires(&self) -> bool {
+        &&& <C as View>::V::is_prefix_secure()
+        &&& self.0.serialize_requires()
+        &&& self.0.parse_requires()
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        ExplicitTag(self.tag(), SortedRepeat(&self.0)).serialize(v, data, pos)
+    }
+}
+
+/// Non-strict version of SetOf that accepts the elements in any order
+/// (e.g. BER encodings, or non-conforming DER encodings seen in practice)
+#[derive(Debug, View)]
+pub struct UnorderedSetOf<C>(pub C);
+
+impl<C> ASN1Tagged for UnorderedSetOf<C> {
+    open spec fn spec_tag(&self) -> TagValue {
+        tag_of!(SET)
+    }
+
+    fn tag(&self) -> TagValue {
+        tag_of!(SET)
+    }
+}
+
+impl<C: View> ViewWithASN1Tagged for UnorderedSetOf<C> {
+    proof fn lemma_view_preserves_tag(&self) {}
+}
+
+impl<C: SecureSpecCombinator + SpecCombinator> SpecCombinator for UnorderedSetOf<C> {
+    type SpecResult = Seq<C::SpecResult>;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        ExplicitTag(self.spec_tag(), Repeat(self.0)).spec_parse(s)
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {}
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        ExplicitTag(self.spec_tag(), Repeat(self.0)).spec_serialize(v)
+    }
+}
+
+impl<C: SecureSpecCombinator + SpecCombinator> SecureSpecCombinator for UnorderedSetOf<C> {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        ExplicitTag(self.spec_tag(), Repeat(self.0)).theorem_serialize_parse_roundtrip(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        ExplicitTag(self.spec_tag(), Repeat(self.0)).theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        ExplicitTag(self.spec_tag(), Repeat(self.0)).lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl<C: Combinator> Combinator for UnorderedSetOf<C> where
+    <C as View>::V: SecureSpecCombinator<SpecResult = <C::Owned as View>::V>,
+    for<'a> C::Result<'a>: PolyfillClone,
+{
+    type Result<'a> = SetOfValue<C::Result<'a>>;
+    type Owned = SetOfValue<C::Owned>;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    open spec fn parse_requires(&self) -> bool {
+        &&& <C as View>::V::is_prefix_secure()
+        &&& self.0.parse_requires()
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        ExplicitTag(self.tag(), Repeat(&self.0)).parse(s)
+    }
+
+    open spec fn serialize_requires(&self) -> bool {
+        &&& <C as View>::V::is_prefix_secure()
+        &&& self.0.serialize_requires()
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        ExplicitTag(self.tag(), Repeat(&self.0)).serialize(v, data, pos)
+    }
+}
+
+/// The order on encodings of SET OF elements in DER:
+/// compared as octet strings, with the shorter one
+/// padded with trailing zeros
+pub open spec fn spec_der_set_of_le(a: Seq<u8>, b: Seq<u8>) -> bool
+    decreases a.len()
+{
+    if a.len() == 0 {
+        true
+    } else if b.len() == 0 {
+        forall |i| 0 <= i < a.len() ==> a[i] == 0
+    } else if a[0] != b[0] {
+        a[0] < b[0]
+    } else {
+        spec_der_set_of_le(a.drop_first(), b.drop_first())
+    }
+}
+
+/// Exec version of spec_der_set_of_le
+pub fn der_set_of_le(a: &[u8], b: &[u8]) -> (res: bool)
+    ensures res == spec_der_set_of_le(a@, b@)
+{
+    let len = if a.len() < b.len() { a.len() } else { b.len() };
+
+    assert(a@.skip(0) == a@ && b@.skip(0) == b@);
+
+    for i in 0..len
+        invariant
+            len <= a.len() && len <= b.len(),
+            spec_der_set_of_le(a@, b@) == spec_der_set_of_le(a@.skip(i as int), b@.skip(i as int)),
+    {
+        assert(a@.skip(i as int)[0] == a[i as int] && b@.skip(i as int)[0] == b[i as int]);
+
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+
+        assert(a@.skip(i as int).drop_first() =~= a@.skip(i + 1));
+        assert(b@.skip(i as int).drop_first() =~= b@.skip(i + 1));
+    }
+
+    if a.len() <= b.len() {
+        return true;
+    }
+
+    // b is a proper prefix of a (modulo padding),
+    // so the rest of a should be all zeros
+    for j in len..a.len()
+        invariant
+            len <= j <= a.len(),
+            forall |k| len <= k < j ==> a@[k] == 0,
+    {
+        if a[j] != 0 {
+            assert(a@.skip(len as int)[j - len] == a@[j as int]);
+            return false;
+        }
+    }
+
+    assert(b@.skip(len as int).len() == 0);
+    assert forall |k| 0 <= k < a@.skip(len as int).len() implies a@.skip(len as int)[k] == 0 by {
+        assert(a@.skip(len as int)[k] == a@[len + k]);
+    }
+
+    true
+}
+
+/// Repeat, but additionally requires that the encodings of
+/// consecutive elements are in the DER SET OF order
+#[derive(Debug, View)]
+pub struct SortedRepeat<C>(pub C);
+
+impl<C: SecureSpecCombinator + SpecCombinator> SortedRepeat<C> {
+    /// The encoding of a is not greater than the encoding of b
+    pub open spec fn spec_elem_le(&self, a: C::SpecResult, b: C::SpecResult) -> bool {
+        match (self.0.spec_serialize(a), self.0.spec_serialize(b)) {
+            (Ok(a), Ok(b)) => spec_der_set_of_le(a, b),
+            _ => false,
+        }
+    }
+
+    pub open spec fn spec_sorted(&self, v: Seq<C::SpecResult>) -> bool {
+        forall |i| #![trigger v[i]] 0 <= i < v.len() - 1 ==> self.spec_elem_le(v[i], v[i + 1])
+    }
+}
+
+impl<C: SecureSpecCombinator + SpecCombinator> SpecCombinator for SortedRepeat<C> {
+    type SpecResult = Seq<C::SpecResult>;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        match Repeat(self.0).spec_parse(s) {
+            Ok((n, v)) =>
+                if self.spec_sorted(v) {
+                    Ok((n, v))
+                } else {
+                    Err(())
+                },
+            Err(..) => Err(()),
+        }
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        Repeat(self.0).spec_parse_wf(s);
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        if self.spec_sorted(v) {
+            Repeat(self.0).spec_serialize(v)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl<C: SecureSpecCombinator + SpecCombinator> SecureSpecCombinator for SortedRepeat<C> {
+    /// Same as Repeat
+    open spec fn is_prefix_secure() -> bool {
+        false
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        Repeat(self.0).theorem_serialize_parse_roundtrip(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        Repeat(self.0).theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {}
+}
+
+impl<C: Combinator> SortedRepeat<C> where
+    <C as View>::V: SecureSpecCombinator<SpecResult = <C::Owned as View>::V>,
+{
+    /// Similar to Repeat::parse_helper, but also checks that the
+    /// encoding of each element is not less than the previous one
+    fn parse_helper<'a>(&self, s: &'a [u8], prev: Option<&'a [u8]>, res: &mut VecDeep<C::Result<'a>>) -> (r: Result<(), ParseError>)
+        requires
+            self.0.parse_requires(),
+            <C as View>::V::is_prefix_secure(),
+            self@.spec_sorted(old(res)@),
+            prev is None <==> old(res)@.len() == 0,
+            prev matches Some(prev) ==> self.0@.spec_serialize(old(res)@.last()) == Ok::<Seq<u8>, ()>(prev@),
+
+        ensures
+            r is Ok ==> {
+                &&& Repeat(self.0@).spec_parse(s@) is Ok
+                &&& Repeat(self.0@).spec_parse(s@) matches Ok((n, v)) ==> {
+                    &&& res@ =~= old(res)@ + v
+                    &&& self@.spec_sorted(res@)
+                }
+            },
+            r is Err ==> {
+                ||| Repeat(self.0@).spec_parse(s@) is Err
+                ||| Repeat(self.0@).spec_parse(s@) matches Ok((n, v)) && !self@.spec_sorted(old(res)@ + v)
+            },
+    {
+        if s.len() == 0 {
+            assert(old(res)@ + seq![] =~= old(res)@);
+            return Ok(());
+        }
+
+        let (n, v) = self.0.parse(s)?;
+
+        if n == 0 {
+            return Err(ParseError::RepeatEmptyElement);
+        }
+
+        let elem = slice_take(s, n);
+
+        proof {
+            self.0@.theorem_parse_serialize_roundtrip(s@);
+            assert(s@.subrange(0, n as int) == elem@);
+        }
+
+        if let Some(prev) = prev {
+            if !der_set_of_le(prev, elem) {
+                proof {
+                    let old_res = old(res)@;
+                    if let Ok((_, vs)) = Repeat(self.0@).spec_parse(s@) {
+                        let all = old_res + vs;
+                        assert(all[old_res.len() - 1] == old_res.last());
+                        assert(all[old_res.len() as int] == v@);
+                    }
+                }
+                return Err(ParseError::Other("SET OF elements not in DER order".to_string()));
+            }
+        }
+
+        let ghost prev_res = res@;
+
+        res.push(v);
+
+        assert(self@.spec_sorted(res@)) by {
+            assert forall |i| 0 <= i < res@.len() - 1 implies self@.spec_elem_le(#[trigger] res@[i], res@[i + 1]) by {
+                if i < prev_res.len() - 1 {
+                    assert(prev_res[i] == res@[i] && prev_res[i + 1] == res@[i + 1]);
+                } else {
+                    assert(res@[i] == prev_res.last() && res@[i + 1] == v@);
+                }
+            }
+        }
+
+        let r = self.parse_helper(slice_subrange(s, n, s.len()), Some(elem), res);
+
+        proof {
+            if let Ok((_, rest)) = Repeat(self.0@).spec_parse(s@.skip(n as int)) {
+                assert(old(res)@ + (seq![v@] + rest) =~= prev_res.push(v@) + rest);
+            }
+        }
+
+        r
+    }
+}
+
+impl<C: Combinator> Combinator for SortedRepeat<C> where
+    <C as View>::V: SecureSpecCombinator<SpecResult = <C::Owned as View>::V>,
+{
+    type Result<'a> = VecDeep<C::Result<'a>>;
+    type Owned = VecDeep<C::Owned>;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    open spec fn parse_requires(&self) -> bool {
+        &&& <C as View>::V::is_prefix_secure()
+        &&& self.0.parse_requires()
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        let mut res = VecDeep::new();
+        assert(res@ + seq![] =~= res@);
+        self.parse_helper(s, None, &mut res)?;
+        Ok((s.len(), res))
+    }
+
+    /// Also requires parse_requires for parse_helper below
+    open spec fn serialize_requires(&self) -> bool {
+        &&& <C as View>::V::is_prefix_secure()
+        &&& self.0.serialize_requires()
+        &&& self.0.parse_requires()
+    }
+
+    /// Serialize as in Repeat, then check the order
+    /// by parsing the serialized elements back
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        let ghost v_view = v@;
+        let n = Repeat(&self.0).serialize(v, data, pos)?;
+
+        if pos > data.len() || n > data.len() - pos {
+            return Err(SerializeError::InsufficientBuffer);
+        }
+
+        proof {
+            Repeat(self.0@).theorem_serialize_parse_roundtrip(v_view);
+            assert(data@.subrange(pos as int, pos + n) =~= Repeat(self.0@).spec_serialize(v_view).unwrap());
+        }
+
+        let mut parsed = VecDeep::new();
+        assert(parsed@ + seq![] =~= parsed@);
+
+        match self.parse_helper(slice_subrange(data.as_slice(), pos, pos + n), None, &mut parsed) {
+            Ok(()) => {
+                assert(parsed@ =~= v_view);
+                Ok(n)
+            }
+            Err(..) => Err(SerializeError::Other("SET OF elements not in DER order".to_string())),
+        }
+    }
+}
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::asn1::*;
+
+    verus! {
+        /// Check that all trait bounds and preconditions are satisfied
+        #[test]
+        fn is_combinator() {
+            let _ = SetOf(ASN1(Integer)).parse(&[]);
+            let _ = UnorderedSetOf(ASN1(Integer)).parse(&[]);
+        }
+    }
+
+    #[test]
+    fn der_order() {
+        assert!(der_set_of_le(&[], &[]));
+        assert!(der_set_of_le(&[ 0x01 ], &[ 0x02 ]));
+        assert!(!der_set_of_le(&[ 0x02 ], &[ 0x01 ]));
+        assert!(der_set_of_le(&[ 0x01 ], &[ 0x01, 0x00 ]));
+        assert!(der_set_of_le(&[ 0x01, 0x00 ], &[ 0x01 ]));
+        assert!(!der_set_of_le(&[ 0x01, 0x01 ], &[ 0x01 ]));
+    }
+
+    #[test]
+    fn sorted() {
+        let parsed = SetOf(ASN1(Integer)).parse(&[
+            0x0A, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02, 0x02, 0x02, 0x01, 0x00,
+        ]).unwrap();
+
+        assert_eq!(parsed.0, 11);
+        assert_eq!(parsed.1.len(), 3);
+    }
+
+    #[test]
+    fn unsorted() {
+        assert!(SetOf(ASN1(Integer)).parse(&[
+            0x06, 0x02, 0x01, 0x02, 0x02, 0x01, 0x01,
+        ]).is_err());
+
+        // Accepted in the non-strict mode, in the encoding order
+        let parsed = UnorderedSetOf(ASN1(Integer)).parse(&[
+            0x06, 0x02, 0x01, 0x02, 0x02, 0x01, 0x01,
+        ]).unwrap();
+
+        assert_eq!(parsed.0, 7);
+        assert_eq!(parsed.1.len(), 2);
+    }
+
+    #[test]
+    fn serialize_unsorted() {
+        let mut data = vec![0; 16];
+        assert!(SetOf(ASN1(Integer)).serialize(VecDeep::from_vec(vec![ 2, 1 ]), &mut data, 0).is_err());
+        assert_eq!(SetOf(ASN1(Integer)).serialize(VecDeep::from_vec(vec![ 1, 2 ]), &mut data, 0).unwrap(), 7);
+        assert_eq!(&data[..7], &[ 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02 ]);
+    }
+}
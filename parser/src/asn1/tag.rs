@@ -2,10 +2,17 @@ use vstd::prelude::*;
 use vstd::slice::slice_subrange;
 
 use crate::common::*;
+use super::base128::*;
+use super::bounds::*;
 
 verus! {
 
 /// Combinator for ASN.1 tags
+///
+/// Tag numbers up to 30 are encoded in the low 5 bits of
+/// the identifier octet. Larger tag numbers use the long form
+/// (X.690, 8.1.2.4), where the low 5 bits are all 1 and the
+/// tag number follows in base-128
 #[derive(Debug, View)]
 pub struct ASN1Tag;
 
@@ -13,10 +20,7 @@ pub struct ASN1Tag;
 pub struct TagValue {
     pub class: TagClass,
     pub form: TagForm,
-
-    /// Currently we only support tag numbers up to 31
-    /// Larger tag numbers require the long form of tags
-    pub num: u8,
+    pub num: UInt,
 }
 
 impl View for TagValue {
@@ -79,114 +83,227 @@ impl TagValue {
     }
 }
 
-impl SpecCombinator for ASN1Tag {
-    type SpecResult = TagValue;
-
-    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
-        if s.len() == 0 {
-            Err(())
+impl ASN1Tag {
+    pub open spec fn spec_parse_class(b: u8) -> TagClass {
+        let class_num = b >> 6 & 0b11;
+        if class_num == 0 {
+            TagClass::Universal
+        } else if class_num == 1 {
+            TagClass::Application
+        } else if class_num == 2 {
+            TagClass::ContextSpecific
         } else {
-            let class_num = s[0] >> 6 & 0b11;
-            let class = if class_num == 0 {
-                TagClass::Universal
-            } else if class_num == 1 {
-                TagClass::Application
-            } else if class_num == 2 {
-                TagClass::ContextSpecific
-            } else {
-                TagClass::Private
-            };
-
-            let form_num = s[0] >> 5 & 1;
-            let form = if form_num == 0 {
-                TagForm::Primitive
-            } else {
-                TagForm::Constructed
-            };
-
-            Ok((1, TagValue {
-                class,
-                form,
-                num: s[0] & 0b11111,
-            }))
+            TagClass::Private
         }
     }
 
-    proof fn spec_parse_wf(&self, s: Seq<u8>) {}
+    pub open spec fn spec_parse_form(b: u8) -> TagForm {
+        if b >> 5 & 1 == 0 {
+            TagForm::Primitive
+        } else {
+            TagForm::Constructed
+        }
+    }
 
-    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
-        let class: u8 = match v.class {
+    pub open spec fn spec_class_num(class: TagClass) -> u8 {
+        match class {
             TagClass::Universal => 0,
             TagClass::Application => 1,
             TagClass::ContextSpecific => 2,
             _ => 3,
-        };
+        }
+    }
 
-        let form: u8 = match v.form {
+    pub open spec fn spec_form_num(form: TagForm) -> u8 {
+        match form {
             TagForm::Primitive => 0,
             _ => 1,
-        };
+        }
+    }
 
-        if v.num > 0b11111 {
-            Err(())
+    fn parse_class(b: u8) -> (res: TagClass)
+        ensures res == Self::spec_parse_class(b)
+    {
+        let class_num = b >> 6 & 0b11;
+        if class_num == 0 {
+            TagClass::Universal
+        } else if class_num == 1 {
+            TagClass::Application
+        } else if class_num == 2 {
+            TagClass::ContextSpecific
         } else {
-            Ok(seq![(class << 6) | (form << 5) | (v.num & 0b11111)])
+            TagClass::Private
         }
     }
-}
 
-impl SecureSpecCombinator for ASN1Tag {
-    open spec fn is_prefix_secure() -> bool {
-        true
+    fn parse_form(b: u8) -> (res: TagForm)
+        ensures res == Self::spec_parse_form(b)
+    {
+        if b >> 5 & 1 == 0 {
+            TagForm::Primitive
+        } else {
+            TagForm::Constructed
+        }
     }
 
-    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
-        let class_num: u8 = match v.class {
+    fn class_num(class: &TagClass) -> (res: u8)
+        ensures res == Self::spec_class_num(*class)
+    {
+        match class {
             TagClass::Universal => 0,
             TagClass::Application => 1,
             TagClass::ContextSpecific => 2,
             _ => 3,
-        };
+        }
+    }
 
-        let form_num: u8 = match v.form {
+    fn form_num(form: &TagForm) -> (res: u8)
+        ensures res == Self::spec_form_num(*form)
+    {
+        match form {
             TagForm::Primitive => 0,
             _ => 1,
-        };
+        }
+    }
+}
 
-        let num = v.num;
+impl SpecCombinator for ASN1Tag {
+    type SpecResult = TagValue;
 
-        // Restate parse(serialize(v)) = v, but purely in BV
-        assert(
-            0 <= class_num < 4 &&
-            0 <= form_num < 2 &&
-            0 <= num <= 0b11111 ==> {
-            let ser = (class_num << 6) | (form_num << 5) | (num & 0b11111);
-            &&& ser >> 6 & 0b11 == class_num
-            &&& ser >> 5 & 1 == form_num
-            &&& ser & 0b11111 == num
-        }) by (bit_vector);
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        if s.len() == 0 {
+            Err(())
+        } else {
+            let class = Self::spec_parse_class(s[0]);
+            let form = Self::spec_parse_form(s[0]);
+
+            if s[0] & 0b11111 != 0b11111 {
+                Ok((1, TagValue {
+                    class,
+                    form,
+                    num: (s[0] & 0b11111) as UInt,
+                }))
+            } else {
+                match Base128UInt.spec_parse(s.drop_first()) {
+                    // The long form is only allowed for tag numbers >= 31
+                    Ok((n, num)) =>
+                        if num >= 0b11111 && n < usize::MAX {
+                            Ok(((n + 1) as usize, TagValue { class, form, num }))
+                        } else {
+                            Err(())
+                        }
+                    Err(()) => Err(()),
+                }
+            }
+        }
     }
 
-    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
-        let tag = buf[0];
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        if s.len() != 0 {
+            Base128UInt.spec_parse_wf(s.drop_first());
+        }
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        let class = Self::spec_class_num(v.class);
+        let form = Self::spec_form_num(v.form);
 
-        // Restate serialize(parse(v)) = v, but purely in BV
-        assert(
-            (tag >> 6 & 0b11) << 6 | (tag >> 5 & 1) << 5 | ((tag & 0b11111) & 0b11111) == tag
-        ) by (bit_vector);
+        if v.num < 0b11111 {
+            Ok(seq![(class << 6) | (form << 5) | (v.num as u8 & 0b11111)])
+        } else {
+            match Base128UInt.spec_serialize(v.num) {
+                Ok(rest) => Ok(seq![(class << 6) | (form << 5) | 0b11111] + rest),
+                Err(()) => Err(()),
+            }
+        }
+    }
+}
 
-        // Some bound facts
-        assert(tag >> 6 & 0b11 < 4) by (bit_vector);
-        assert(tag >> 5 & 1 < 2) by (bit_vector);
-        assert(tag & 0b11111 <= 0b11111) by (bit_vector);
+impl SecureSpecCombinator for ASN1Tag {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
 
-        if let Ok((n, v)) = self.spec_parse(buf) {
-            let ser = self.spec_serialize(v).unwrap();
-            assert(ser =~= buf.subrange(0, 1));
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        let class_num = Self::spec_class_num(v.class);
+        let form_num = Self::spec_form_num(v.form);
+
+        if v.num < 0b11111 {
+            let num = v.num as u8;
+
+            // Restate parse(serialize(v)) = v, but purely in BV
+            assert(
+                0 <= class_num < 4 &&
+                0 <= form_num < 2 &&
+                0 <= num < 0b11111 ==> {
+                let ser = (class_num << 6) | (form_num << 5) | (num & 0b11111);
+                &&& ser >> 6 & 0b11 == class_num
+                &&& ser >> 5 & 1 == form_num
+                &&& ser & 0b11111 == num
+            }) by (bit_vector);
+        } else {
+            let first = (class_num << 6) | (form_num << 5) | 0b11111;
+            let rest = Base128UInt.spec_serialize(v.num).unwrap();
+            let ser = seq![first] + rest;
+
+            assert(
+                0 <= class_num < 4 &&
+                0 <= form_num < 2 ==> {
+                let first = (class_num << 6) | (form_num << 5) | 0b11111;
+                &&& first >> 6 & 0b11 == class_num
+                &&& first >> 5 & 1 == form_num
+                &&& first & 0b11111 == 0b11111
+            }) by (bit_vector);
+
+            Base128UInt.theorem_serialize_parse_roundtrip(v.num);
+            Base128UInt::lemma_spec_serialize_len_bound(v.num);
+            assert(ser.drop_first() == rest);
         }
     }
 
-    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {}
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        if buf.len() != 0 {
+            let tag = buf[0];
+
+            // Some bound facts
+            assert(tag >> 6 & 0b11 < 4) by (bit_vector);
+            assert(tag >> 5 & 1 < 2) by (bit_vector);
+            assert(tag & 0b11111 <= 0b11111) by (bit_vector);
+
+            if tag & 0b11111 != 0b11111 {
+                // Restate serialize(parse(v)) = v, but purely in BV
+                assert(
+                    (tag >> 6 & 0b11) << 6 | (tag >> 5 & 1) << 5 | ((tag & 0b11111) & 0b11111) == tag
+                ) by (bit_vector);
+                assert((tag & 0b11111) as UInt as u8 == tag & 0b11111);
+
+                if let Ok((n, v)) = self.spec_parse(buf) {
+                    let ser = self.spec_serialize(v).unwrap();
+                    assert(ser =~= buf.subrange(0, 1));
+                }
+            } else {
+                assert(
+                    tag & 0b11111 == 0b11111 ==>
+                    (tag >> 6 & 0b11) << 6 | (tag >> 5 & 1) << 5 | 0b11111 == tag
+                ) by (bit_vector);
+
+                Base128UInt.theorem_parse_serialize_roundtrip(buf.drop_first());
+
+                if let Ok((n, v)) = self.spec_parse(buf) {
+                    let ser = self.spec_serialize(v).unwrap();
+                    assert(ser =~= buf.subrange(0, n as int));
+                }
+            }
+        }
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        if s1.len() != 0 {
+            assert((s1 + s2)[0] == s1[0]);
+            assert((s1 + s2).drop_first() == s1.drop_first() + s2);
+            Base128UInt.lemma_prefix_secure(s1.drop_first(), s2);
+        }
+    }
 }
 
 impl Combinator for ASN1Tag {
@@ -194,68 +311,76 @@ impl Combinator for ASN1Tag {
     type Owned = TagValue;
 
     open spec fn spec_length(&self) -> Option<usize> {
-        Some(1)
+        None
     }
 
     fn length(&self) -> Option<usize> {
-        Some(1)
+        None
     }
 
     fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
         if s.len() == 0 {
             return Err(ParseError::UnexpectedEndOfInput);
-        } else {
-            let class_num = s[0] >> 6 & 0b11;
-            let class = if class_num == 0 {
-                TagClass::Universal
-            } else if class_num == 1 {
-                TagClass::Application
-            } else if class_num == 2 {
-                TagClass::ContextSpecific
-            } else {
-                TagClass::Private
-            };
+        }
 
-            let form_num = s[0] >> 5 & 1;
-            let form = if form_num == 0 {
-                TagForm::Primitive
-            } else {
-                TagForm::Constructed
-            };
+        let class = Self::parse_class(s[0]);
+        let form = Self::parse_form(s[0]);
 
-            Ok((1, TagValue {
+        if s[0] & 0b11111 != 0b11111 {
+            return Ok((1, TagValue {
                 class,
                 form,
-                num: s[0] & 0b11111,
-            }))
+                num: (s[0] & 0b11111) as UInt,
+            }));
+        }
+
+        // Long form
+        let rest = slice_subrange(s, 1, s.len());
+        assert(rest@ == s@.drop_first());
+
+        let (n, num) = Base128UInt.parse(rest)?;
+
+        if num < 0b11111 {
+            return Err(ParseError::Other("Non-minimal tag encoding".to_string()));
         }
+
+        if n >= usize::MAX {
+            return Err(ParseError::SizeOverflow);
+        }
+
+        Ok((n + 1, TagValue { class, form, num }))
     }
 
     fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
-        let class: u8 = match v.class {
-            TagClass::Universal => 0,
-            TagClass::Application => 1,
-            TagClass::ContextSpecific => 2,
-            _ => 3,
-        };
+        let class = Self::class_num(&v.class);
+        let form = Self::form_num(&v.form);
 
-        let form: u8 = match v.form {
-            TagForm::Primitive => 0,
-            _ => 1,
-        };
-
-        if v.num > 0b11111 {
-            return Err(SerializeError::Other("Invalid tag number".to_string()));
+        if pos >= data.len() {
+            return Err(SerializeError::InsufficientBuffer);
         }
 
-        if pos < data.len() {
-            let tag = (class << 6) | (form << 5) | (v.num & 0b11111);
+        if v.num < 0b11111 {
+            let tag = (class << 6) | (form << 5) | (v.num as u8 & 0b11111);
             data.set(pos, tag);
             assert(data@ == seq_splice(old(data)@, pos, seq![tag]));
-            Ok(1)
-        } else {
-            Err(SerializeError::InsufficientBuffer)
+            return Ok(1);
+        }
+
+        // Long form
+        let first = (class << 6) | (form << 5) | 0b11111;
+        data.set(pos, first);
+
+        let ghost data_first = data@;
+        assert(data_first == seq_splice(old(data)@, pos, seq![first]));
+
+        let n = Base128UInt.serialize(v.num, data, pos + 1)?;
+
+        if n >= usize::MAX {
+            return Err(SerializeError::SizeOverflow);
         }
+
+        assert(data@ =~= seq_splice(old(data)@, pos, self@.spec_serialize(v@).unwrap()));
+        Ok(n + 1)
     }
 }
 
@@ -361,17 +486,18 @@ impl<T: ASN1Tagged + Combinator> Combinator for ASN1<T> where
     type Result<'a> = T::Result<'a>;
     type Owned = T::Owned;
 
+    /// Only known if the tag fits in a single byte
     open spec fn spec_length(&self) -> Option<usize> {
         match self.0.spec_length() {
-            Some(len) => len.checked_add(1),
-            None => None,
+            Some(len) if self.0.spec_tag().num < 0b11111 => len.checked_add(1),
+            _ => None,
         }
     }
 
     fn length(&self) -> Option<usize> {
         match self.0.length() {
-            Some(len) => len.checked_add(1),
-            None => None,
+            Some(len) if self.0.tag().num < 0b11111 => len.checked_add(1),
+            _ => None,
         }
     }
 
@@ -444,6 +570,9 @@ macro_rules! asn1_tagged {
 pub use asn1_tagged;
 
 /// Tags of common ASN.1 types
+///
+/// Tag numbers of 31 or above (e.g. `tag_of!(IMPLICIT 100)`)
+/// are encoded in the long form
 #[allow(unused_macros)]
 macro_rules! tag_of {
     (BOOLEAN) => {
@@ -494,6 +623,14 @@ macro_rules! tag_of {
         }
     };
 
+    (REAL) => {
+        TagValue {
+            class: TagClass::Universal,
+            form: TagForm::Primitive,
+            num: 0x09,
+        }
+    };
+
     (SEQUENCE) => {
         TagValue {
             class: TagClass::Universal,
@@ -518,6 +655,14 @@ macro_rules! tag_of {
         }
     };
 
+    (NUMERIC_STRING) => {
+        TagValue {
+            class: TagClass::Universal,
+            form: TagForm::Primitive,
+            num: 0x12,
+        }
+    };
+
     (PRINTABLE_STRING) => {
         TagValue {
             class: TagClass::Universal,
@@ -542,6 +687,30 @@ macro_rules! tag_of {
         }
     };
 
+    (ENUMERATED) => {
+        TagValue {
+            class: TagClass::Universal,
+            form: TagForm::Primitive,
+            num: 0x0a,
+        }
+    };
+
+    (VISIBLE_STRING) => {
+        TagValue {
+            class: TagClass::Universal,
+            form: TagForm::Primitive,
+            num: 0x1a,
+        }
+    };
+
+    (GENERAL_STRING) => {
+        TagValue {
+            class: TagClass::Universal,
+            form: TagForm::Primitive,
+            num: 0x1b,
+        }
+    };
+
     (UNIVERSAL_STRING) => {
         TagValue {
             class: TagClass::Universal,
@@ -589,11 +758,43 @@ macro_rules! tag_of {
             num: $num,
         }
     };
+
+    (APPLICATION IMPLICIT $num:literal) => {
+        TagValue {
+            class: TagClass::Application,
+            form: TagForm::Primitive,
+            num: $num,
+        }
+    };
+
+    (APPLICATION EXPLICIT $num:literal) => {
+        TagValue {
+            class: TagClass::Application,
+            form: TagForm::Constructed,
+            num: $num,
+        }
+    };
+
+    (PRIVATE IMPLICIT $num:literal) => {
+        TagValue {
+            class: TagClass::Private,
+            form: TagForm::Primitive,
+            num: $num,
+        }
+    };
+
+    (PRIVATE EXPLICIT $num:literal) => {
+        TagValue {
+            class: TagClass::Private,
+            form: TagForm::Constructed,
+            num: $num,
+        }
+    };
 }
 pub(crate) use tag_of;
 
 /// Placeholder to parse a TLV tuple as OctetString
-/// with the provided tag
+/// with the provided tag (same syntax as tag_of!)
 #[allow(unused_macros)]
 macro_rules! placeholder {
     ($($tag:tt)*) => {
@@ -633,3 +834,54 @@ pub(crate) use placeholder_type;
 // pub(crate) use explicit_tag;
 
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::asn1::*;
+
+    fn serialize_tag(v: TagValue) -> Result<Vec<u8>, SerializeError> {
+        let mut data = vec![0; 16];
+        let len = ASN1Tag.serialize(v, &mut data, 0)?;
+        data.truncate(len);
+        Ok(data)
+    }
+
+    #[test]
+    fn short_form() {
+        let (n, tag) = ASN1Tag.parse(&[0x30]).unwrap();
+        assert_eq!(n, 1);
+        assert!(tag.eq(tag_of!(SEQUENCE)));
+
+        assert_eq!(serialize_tag(tag_of!(IMPLICIT 30)).unwrap(), [0x9e]);
+    }
+
+    #[test]
+    fn long_form() {
+        let (n, tag) = ASN1Tag.parse(&[0x9f, 0x1f]).unwrap();
+        assert_eq!(n, 2);
+        assert!(tag.eq(tag_of!(IMPLICIT 31)));
+        assert_eq!(serialize_tag(tag_of!(IMPLICIT 31)).unwrap(), [0x9f, 0x1f]);
+
+        let (n, tag) = ASN1Tag.parse(&[0xff, 0x87, 0x68]).unwrap();
+        assert_eq!(n, 3);
+        assert!(tag.eq(tag_of!(PRIVATE EXPLICIT 1000)));
+        assert_eq!(serialize_tag(tag_of!(PRIVATE EXPLICIT 1000)).unwrap(), [0xff, 0x87, 0x68]);
+
+        let (n, v) = ASN1(ImplicitTag(tag_of!(IMPLICIT 100), Integer)).parse(&[0x9f, 0x64, 0x01, 0x05]).unwrap();
+        assert_eq!(n, 4);
+        assert_eq!(v, 5);
+    }
+
+    #[test]
+    fn non_minimal() {
+        // Tag numbers below 31 must use the short form
+        assert!(ASN1Tag.parse(&[0x9f, 0x1e]).is_err());
+
+        // Leading zero in the base-128 tag number
+        assert!(ASN1Tag.parse(&[0x9f, 0x80, 0x1f]).is_err());
+
+        // Truncated tag number
+        assert!(ASN1Tag.parse(&[0x9f, 0x81]).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use vstd::prelude::*;
+
+use crate::common::*;
+
+use super::fixed_width_string::*;
+use super::tag::*;
+
+verus! {
+
+/// Combinator for TeletexString (T61String) in ASN.1
+///
+/// Full T.61 uses non-spacing diacritic prefixes and code points
+/// above 0x7F that differ from Latin-1, so we only accept the subset
+/// of the T.61 primary set that coincides with ASCII (see T61StringPred),
+/// and decode each byte as the code point of the same value
+#[derive(Debug, View)]
+pub struct TeletexString;
+
+asn1_tagged!(TeletexString, tag_of!(TELETEX_STRING));
+
+pub type SpecTeletexStringValue = Seq<char>;
+pub type TeletexStringValue = String;
+pub type TeletexStringValueOwned = String;
+
+impl SpecCombinator for TeletexString {
+    type SpecResult = SpecTeletexStringValue;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        Refined {
+            inner: FixedWidthString(1),
+            predicate: T61StringPred,
+        }.spec_parse(s)
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        Refined {
+            inner: FixedWidthString(1),
+            predicate: T61StringPred,
+        }.spec_parse_wf(s)
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        Refined {
+            inner: FixedWidthString(1),
+            predicate: T61StringPred,
+        }.spec_serialize(v)
+    }
+}
+
+impl SecureSpecCombinator for TeletexString {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        Refined {
+            inner: FixedWidthString(1),
+            predicate: T61StringPred,
+        }.theorem_serialize_parse_roundtrip(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        Refined {
+            inner: FixedWidthString(1),
+            predicate: T61StringPred,
+        }.theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        Refined {
+            inner: FixedWidthString(1),
+            predicate: T61StringPred,
+        }.lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl Combinator for TeletexString {
+    type Result<'a> = TeletexStringValue;
+    type Owned = TeletexStringValueOwned;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        Refined {
+            inner: FixedWidthString(1),
+            predicate: T61StringPred,
+        }.parse(s)
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        Refined {
+            inner: FixedWidthString(1),
+            predicate: T61StringPred,
+        }.serialize(v, data, pos)
+    }
+}
+
+/// Characters of the T.61 primary set (ISO-IR 102) that are encoded
+/// as in ASCII: printable ASCII except for # $ \ ^ ` { } ~, which are
+/// either unassigned or encoded elsewhere in T.61
+#[derive(View)]
+pub struct T61StringPred;
+
+impl T61StringPred {
+    pub open spec fn wf_char(c: char) -> bool {
+        &&& ' ' <= c <= '|'
+        &&& c != '#' && c != '$' && c != '\\' && c != '^' && c != '`' && c != '{'
+    }
+
+    fn exec_wf_char(c: char) -> (res: bool)
+        ensures res == Self::wf_char(c)
+    {
+        ' ' <= c && c <= '|' &&
+        c != '#' && c != '$' && c != '\\' && c != '^' && c != '`' && c != '{'
+    }
+}
+
+impl SpecPred for T61StringPred {
+    type Input = Seq<char>;
+
+    open spec fn spec_apply(&self, s: &Self::Input) -> bool {
+        forall |i| 0 <= i < s.len() ==> #[trigger] Self::wf_char(s[i])
+    }
+}
+
+impl Pred for T61StringPred {
+    type Input<'a> = String;
+    type InputOwned = String;
+
+    fn apply(&self, s: &Self::Input<'_>) -> (res: bool)
+    {
+        let s = s.as_str();
+        let len = s.unicode_len();
+        for i in 0..len
+            invariant
+                len == s@.len(),
+                forall |j| 0 <= j < i ==> #[trigger] Self::wf_char(s@[j]),
+        {
+            if !Self::exec_wf_char(s.get_char(i)) {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(TeletexString.parse(&[
+            0x07, 0x52, 0x75, 0x62, 0x79, 0x20, 0x43, 0x41,
+        ]).unwrap(), (8, "Ruby CA".to_string()));
+
+        // 0xFC is not ü in T.61
+        assert!(TeletexString.parse(&[ 0x02, 0x4D, 0xFC ]).is_err());
+
+        // Not encoded as in ASCII
+        assert!(TeletexString.parse(&[ 0x01, 0x24 ]).is_err());
+        assert!(TeletexString.parse(&[ 0x01, 0x7E ]).is_err());
+        assert!(TeletexString.parse(&[ 0x01, 0x0A ]).is_err());
+
+        assert_eq!(TeletexString.parse(&[ 0x01, 0x7C ]).unwrap(), (2, "|".to_string()));
+    }
+
+    #[test]
+    fn serialize() {
+        let mut data = vec![0; 10];
+        assert_eq!(TeletexString.serialize("ab".to_string(), &mut data, 0).unwrap(), 3);
+        assert_eq!(&data[..3], &[ 0x02, 0x61, 0x62 ]);
+
+        // Not representable in one byte
+        assert!(TeletexString.serialize("黑".to_string(), &mut data, 0).is_err());
+
+        // Not in the ASCII-compatible subset of T.61
+        assert!(TeletexString.serialize("ü".to_string(), &mut data, 0).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use vstd::prelude::*;
+
+use crate::common::*;
+
+use super::fixed_width_string::*;
+use super::tag::*;
+
+verus! {
+
+/// Combinator for UniversalString in ASN.1,
+/// which is encoded in UCS-4 (4-byte big-endian code points)
+#[derive(Debug, View)]
+pub struct UniversalString;
+
+asn1_tagged!(UniversalString, tag_of!(UNIVERSAL_STRING));
+
+pub type SpecUniversalStringValue = Seq<char>;
+pub type UniversalStringValue = String;
+pub type UniversalStringValueOwned = String;
+
+impl SpecCombinator for UniversalString {
+    type SpecResult = SpecUniversalStringValue;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        FixedWidthString(4).spec_parse(s)
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        FixedWidthString(4).spec_parse_wf(s)
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        FixedWidthString(4).spec_serialize(v)
+    }
+}
+
+impl SecureSpecCombinator for UniversalString {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        FixedWidthString(4).theorem_serialize_parse_roundtrip(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        FixedWidthString(4).theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        FixedWidthString(4).lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl Combinator for UniversalString {
+    type Result<'a> = UniversalStringValue;
+    type Owned = UniversalStringValueOwned;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        FixedWidthString(4).parse(s)
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        FixedWidthString(4).serialize(v, data, pos)
+    }
+}
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(UniversalString.parse(&[
+            0x08, 0x00, 0x00, 0x00, 0x41, 0x00, 0x01, 0xF4, 0x68,
+        ]).unwrap(), (9, "A👨".to_string()));
+
+        // Length not a multiple of 4
+        assert!(UniversalString.parse(&[ 0x02, 0x00, 0x41 ]).is_err());
+
+        // Out of the Unicode range
+        assert!(UniversalString.parse(&[ 0x04, 0x00, 0x11, 0x00, 0x00 ]).is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        let mut data = vec![0; 10];
+        assert_eq!(UniversalString.serialize("A".to_string(), &mut data, 0).unwrap(), 5);
+        assert_eq!(&data[..5], &[ 0x04, 0x00, 0x00, 0x00, 0x41 ]);
+    }
+}
@@ -1,6 +1,7 @@
 use vstd::prelude::*;
 
 use crate::common::*;
+use super::len::*;
 use super::len_wrapped::*;
 use super::tag::*;
 
@@ -44,13 +45,13 @@ impl SpecCombinator for UTCTime {
     type SpecResult = UTCTimeValueInner;
 
     open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
-        LengthWrapped(UTCTimeInner).spec_parse(s)
+        LengthWrapped(UTCTimeInner, Length).spec_parse(s)
     }
 
     proof fn spec_parse_wf(&self, s: Seq<u8>) {}
 
     open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
-        LengthWrapped(UTCTimeInner).spec_serialize(v)
+        LengthWrapped(UTCTimeInner, Length).spec_serialize(v)
     }
 }
 
@@ -60,15 +61,15 @@ impl SecureSpecCombinator for UTCTime {
     }
 
     proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
-        LengthWrapped(UTCTimeInner).theorem_serialize_parse_roundtrip(v);
+        LengthWrapped(UTCTimeInner, Length).theorem_serialize_parse_roundtrip(v);
     }
 
     proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
-        LengthWrapped(UTCTimeInner).theorem_parse_serialize_roundtrip(buf);
+        LengthWrapped(UTCTimeInner, Length).theorem_parse_serialize_roundtrip(buf);
     }
 
     proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
-        LengthWrapped(UTCTimeInner).lemma_prefix_secure(s1, s2);
+        LengthWrapped(UTCTimeInner, Length).lemma_prefix_secure(s1, s2);
     }
 }
 
@@ -85,11 +86,11 @@ impl Combinator for UTCTime {
     }
 
     fn parse<'a>(&self, v: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
-        LengthWrapped(UTCTimeInner).parse(v)
+        LengthWrapped(UTCTimeInner, Length).parse(v)
     }
 
     fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
-        LengthWrapped(UTCTimeInner).serialize(v, data, pos)
+        LengthWrapped(UTCTimeInner, Length).serialize(v, data, pos)
     }
 }
 
@@ -0,0 +1,173 @@
+use std::fmt::{self, Debug, Formatter};
+use vstd::prelude::*;
+
+use polyfill::*;
+
+use crate::common::*;
+
+use super::utf8_string::*;
+use super::tag::*;
+
+verus! {
+
+/// Combinator for VisibleString (ISO646String) in ASN.1
+/// Essentially a wrapper around UTF8String
+/// that checks that each character is printable ASCII (0x20 to 0x7e)
+#[derive(Debug, View)]
+pub struct VisibleString;
+
+asn1_tagged!(VisibleString, tag_of!(VISIBLE_STRING));
+
+pub type SpecVisibleStringValue = Seq<char>;
+pub type VisibleStringValue<'a> = &'a str;
+pub type VisibleStringValueOwned = String;
+
+impl SpecCombinator for VisibleString {
+    type SpecResult = SpecVisibleStringValue;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        Refined {
+            inner: UTF8String,
+            predicate: VisibleStringPred,
+        }.spec_parse(s)
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {
+        Refined {
+            inner: UTF8String,
+            predicate: VisibleStringPred,
+        }.spec_parse_wf(s)
+    }
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        Refined {
+            inner: UTF8String,
+            predicate: VisibleStringPred,
+        }.spec_serialize(v)
+    }
+}
+
+impl SecureSpecCombinator for VisibleString {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        Refined {
+            inner: UTF8String,
+            predicate: VisibleStringPred,
+        }.theorem_serialize_parse_roundtrip(v);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        Refined {
+            inner: UTF8String,
+            predicate: VisibleStringPred,
+        }.theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        Refined {
+            inner: UTF8String,
+            predicate: VisibleStringPred,
+        }.lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl Combinator for VisibleString {
+    type Result<'a> = VisibleStringValue<'a>;
+    type Owned = VisibleStringValueOwned;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        Refined {
+            inner: UTF8String,
+            predicate: VisibleStringPred,
+        }.parse(s)
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        Refined {
+            inner: UTF8String,
+            predicate: VisibleStringPred,
+        }.serialize(v, data, pos)
+    }
+}
+
+/// Characters allowed in a VisibleString: printable ASCII (0x20 to 0x7e)
+#[derive(View)]
+pub struct VisibleStringPred;
+
+impl VisibleStringPred {
+    pub open spec fn wf_char(c: char) -> bool {
+        ' ' <= c && c <= '~'
+    }
+
+    fn exec_wf_char(c: char) -> (res: bool)
+        ensures res == Self::wf_char(c)
+    {
+        ' ' <= c && c <= '~'
+    }
+}
+
+impl SpecPred for VisibleStringPred {
+    type Input = Seq<char>;
+
+    open spec fn spec_apply(&self, s: &Self::Input) -> bool {
+        forall |i| 0 <= i < s.len() ==> #[trigger] Self::wf_char(s[i])
+    }
+}
+
+impl Pred for VisibleStringPred {
+    type Input<'a> = &'a str;
+    type InputOwned = String;
+
+    fn apply(&self, s: &Self::Input<'_>) -> (res: bool)
+    {
+        let len = s.unicode_len();
+        for i in 0..len
+            invariant
+                len == s@.len(),
+                forall |j| 0 <= j < i ==> #[trigger] Self::wf_char(s@[j]),
+        {
+            if !Self::exec_wf_char(s.get_char(i)) {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(VisibleString.parse(&[ 0x04, 0x20, 0x41, 0x7e, 0x30 ]).unwrap(), (5, " A~0"));
+        assert_eq!(VisibleString.parse(&[ 0x00 ]).unwrap(), (1, ""));
+
+        // Control characters, DEL and non-ASCII
+        assert!(VisibleString.parse(&[ 0x02, 0x41, 0x0a ]).is_err());
+        assert!(VisibleString.parse(&[ 0x01, 0x7f ]).is_err());
+        assert!(VisibleString.parse(&[ 0x02, 0xc3, 0xbc ]).is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        let mut data = vec![0; 10];
+        assert_eq!(VisibleString.serialize("a~", &mut data, 0).unwrap(), 3);
+        assert_eq!(&data[..3], &[ 0x02, 0x61, 0x7e ]);
+
+        assert!(VisibleString.serialize("a\tb", &mut data, 0).is_err());
+    }
+}
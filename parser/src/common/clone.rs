@@ -49,6 +49,18 @@ impl PolyfillClone for u16 {
     }
 }
 
+impl PolyfillClone for char {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl PolyfillClone for String {
+    fn clone(&self) -> Self {
+        self.as_str().to_string()
+    }
+}
+
 // Can't do this due to https://github.com/verus-lang/verus/issues/1108
 // impl PolyfillClone for () {
 //     fn clone(&self) -> Self {
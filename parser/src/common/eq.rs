@@ -115,6 +115,16 @@ impl<U: PolyfillEq, V: PolyfillEq> PolyfillEq for Either<U, V> {
     }
 }
 
+impl<T: PolyfillEq> PolyfillEq for OptionDeep<T> {
+    fn polyfill_eq(&self, other: &Self) -> (res: bool) {
+        match (self, other) {
+            (OptionDeep::Some(a), OptionDeep::Some(b)) => a.polyfill_eq(b),
+            (OptionDeep::None, OptionDeep::None) => true,
+            _ => false,
+        }
+    }
+}
+
 impl PolyfillEq for NullValue {
     fn polyfill_eq(&self, other: &Self) -> (res: bool) {
         true
@@ -1,5 +1,6 @@
 mod common;
 pub mod asn1;
+pub mod tls;
 pub mod x509;
 
 pub use common::*;
@@ -0,0 +1,7 @@
+#![allow(unused_imports)]
+
+/// Combinators for the TLS presentation language (RFC 8446, Section 3)
+
+mod vec16;
+
+pub use vec16::*;
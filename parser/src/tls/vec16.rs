@@ -0,0 +1,107 @@
+use vstd::prelude::*;
+
+use crate::common::*;
+use crate::asn1::*;
+
+verus! {
+
+/// Combinator for the 2-byte big-endian length prefix of a TLS
+/// vector <0..2^16-1> (RFC 8446, Section 3.4)
+#[derive(Debug, View)]
+pub struct TLSLength16;
+
+/// Wrap the inner combinator in a TLS vector <0..2^16-1>
+pub type TLSVec16<T> = LengthWrapped<T, TLSLength16>;
+
+impl SpecCombinator for TLSLength16 {
+    type SpecResult = LengthValue;
+
+    open spec fn spec_parse(&self, s: Seq<u8>) -> Result<(usize, Self::SpecResult), ()> {
+        match VarUInt(2).spec_parse(s) {
+            Ok((n, v)) => Ok((n, v as LengthValue)),
+            Err(..) => Err(()),
+        }
+    }
+
+    proof fn spec_parse_wf(&self, s: Seq<u8>) {}
+
+    open spec fn spec_serialize(&self, v: Self::SpecResult) -> Result<Seq<u8>, ()> {
+        VarUInt(2).spec_serialize(v as VarUIntResult)
+    }
+}
+
+impl SecureSpecCombinator for TLSLength16 {
+    open spec fn is_prefix_secure() -> bool {
+        true
+    }
+
+    proof fn theorem_serialize_parse_roundtrip(&self, v: Self::SpecResult) {
+        VarUInt(2).theorem_serialize_parse_roundtrip(v as VarUIntResult);
+    }
+
+    proof fn theorem_parse_serialize_roundtrip(&self, buf: Seq<u8>) {
+        if VarUInt(2).spec_parse(buf).is_ok() {
+            // The parsed value fits in 2 bytes, so the cast to LengthValue is lossless
+            VarUInt(2).lemma_parse_ok_bound(buf);
+        }
+        VarUInt(2).theorem_parse_serialize_roundtrip(buf);
+    }
+
+    proof fn lemma_prefix_secure(&self, s1: Seq<u8>, s2: Seq<u8>) {
+        VarUInt(2).lemma_prefix_secure(s1, s2);
+    }
+}
+
+impl Combinator for TLSLength16 {
+    type Result<'a> = LengthValue;
+    type Owned = LengthValue;
+
+    open spec fn spec_length(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    fn length(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    fn parse<'a>(&self, s: &'a [u8]) -> (res: Result<(usize, Self::Result<'a>), ParseError>) {
+        let (n, v) = VarUInt(2).parse(s)?;
+
+        proof {
+            VarUInt(2).lemma_parse_ok_bound(s@);
+        }
+
+        Ok((n, v as LengthValue))
+    }
+
+    fn serialize(&self, v: Self::Result<'_>, data: &mut Vec<u8>, pos: usize) -> (res: Result<usize, SerializeError>) {
+        VarUInt(2).serialize(v as VarUIntResult, data, pos)
+    }
+}
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(TLSLength16.parse(&[ 0x00, 0x00 ]).unwrap(), (2, 0));
+        assert_eq!(TLSLength16.parse(&[ 0x01, 0x02 ]).unwrap(), (2, 0x0102));
+        assert_eq!(TLSLength16.parse(&[ 0xff, 0xff, 0x00 ]).unwrap(), (2, 0xffff));
+        assert!(TLSLength16.parse(&[ 0x01 ]).is_err());
+
+        assert_eq!(LengthWrapped(Tail, TLSLength16).parse(&[ 0x00, 0x02, 0xab, 0xcd, 0xef ]).unwrap(), (4, [ 0xab, 0xcd ].as_slice()));
+        assert!(LengthWrapped(Tail, TLSLength16).parse(&[ 0x00, 0x03, 0xab, 0xcd ]).is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        let mut data = vec![0; 4];
+        assert_eq!(LengthWrapped(Tail, TLSLength16).serialize([ 0xab, 0xcd ].as_slice(), &mut data, 0).unwrap(), 4);
+        assert_eq!(data, [ 0x00, 0x02, 0xab, 0xcd ]);
+
+        assert!(TLSLength16.serialize(0x10000, &mut data, 0).is_err());
+    }
+}
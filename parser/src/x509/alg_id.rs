@@ -35,7 +35,7 @@ wrap_combinator! {
                 fst: ASN1(ObjectIdentifier),
                 snd: AlgorithmParamCont,
                 spec_snd: Ghost(|i| AlgorithmParamCont::spec_apply(i)),
-            }),
+            }, Length),
             mapper: AlgorithmIdentifierMapper,
         };
 }
@@ -80,6 +80,7 @@ impl<'a> PolyfillEq for AlgorithmIdentifierValue<'a> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::x509::*;
 
     verus! {
         /// Check that all trait bounds and preconditions are satisfied
@@ -95,4 +96,101 @@ mod test {
             0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0C, 0x05, 0x00,
         ]).is_ok());
     }
+    #[test]
+    fn rsassa_pss() {
+        // SHA-256, MGF1 with SHA-256, salt length 32
+        let (_, alg) = ASN1(AlgorithmIdentifier).parse(&[
+            0x30, 0x41, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0A, 0x30, 0x34, 0xA0, 0x0F, 0x30, 0x0D, 0x06,
+            0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0xA1, 0x1C, 0x30, 0x1A, 0x06, 0x09, 0x2A, 0x86,
+            0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x08, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01,
+            0x05, 0x00, 0xA2, 0x03, 0x02, 0x01, 0x20,
+        ]).unwrap();
+
+        match alg.param {
+            AlgorithmParamValue::RSASSAPSS(Either::Left(param)) => {
+                match &param.hash_alg {
+                    OptionDeep::Some(hash_alg) => assert!(hash_alg.id.polyfill_eq(&oid!(SHA256))),
+                    _ => panic!("{:?}", param),
+                }
+
+                match &param.mask_gen_alg {
+                    OptionDeep::Some(mgf) => {
+                        assert!(mgf.id.polyfill_eq(&oid!(MGF1)));
+                        assert!(mgf.param.id.polyfill_eq(&oid!(SHA256)));
+                    }
+                    _ => panic!("{:?}", param),
+                }
+
+                assert_eq!(param.salt_len, 32);
+                assert_eq!(param.trailer_field, 1);
+            }
+            _ => panic!("{:?}", alg),
+        }
+
+        // All fields set to default
+        let (_, alg) = ASN1(AlgorithmIdentifier).parse(&[
+            0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0A, 0x30, 0x00,
+        ]).unwrap();
+
+        match alg.param {
+            AlgorithmParamValue::RSASSAPSS(Either::Left(param)) => {
+                assert!(matches!(param.hash_alg, OptionDeep::None));
+                assert!(matches!(param.mask_gen_alg, OptionDeep::None));
+                assert_eq!(param.salt_len, 20);
+                assert_eq!(param.trailer_field, 1);
+            }
+            _ => panic!("{:?}", alg),
+        }
+
+        // hashAlgorithm explicitly set to the default sha1Identifier
+        assert!(ASN1(AlgorithmIdentifier).parse(&[
+            0x30, 0x1A, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0A, 0x30, 0x0D, 0xA0, 0x0B, 0x30, 0x09, 0x06,
+            0x05, 0x2B, 0x0E, 0x03, 0x02, 0x1A, 0x05, 0x00,
+        ]).is_err());
+
+        // SHA-1 without the NULL parameter is a different value, so it can be encoded
+        assert!(ASN1(AlgorithmIdentifier).parse(&[
+            0x30, 0x18, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0A, 0x30, 0x0B, 0xA0, 0x09, 0x30, 0x07, 0x06,
+            0x05, 0x2B, 0x0E, 0x03, 0x02, 0x1A,
+        ]).is_ok());
+
+        // maskGenAlgorithm explicitly set to the default mgf1SHA1Identifier
+        assert!(ASN1(AlgorithmIdentifier).parse(&[
+            0x30, 0x27, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0A, 0x30, 0x1A, 0xA1, 0x18, 0x30, 0x16, 0x06,
+            0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x08, 0x30, 0x09, 0x06, 0x05, 0x2B, 0x0E, 0x03, 0x02, 0x1A, 0x05,
+            0x00,
+        ]).is_err());
+    }
+    #[test]
+    fn eddsa() {
+        let (_, alg) = ASN1(AlgorithmIdentifier).parse(&[ 0x30, 0x05, 0x06, 0x03, 0x2B, 0x65, 0x70 ]).unwrap();
+        assert!(matches!(alg.param, AlgorithmParamValue::Ed25519(..)));
+
+        let (_, alg) = ASN1(AlgorithmIdentifier).parse(&[ 0x30, 0x05, 0x06, 0x03, 0x2B, 0x65, 0x71 ]).unwrap();
+        assert!(matches!(alg.param, AlgorithmParamValue::Ed448(..)));
+
+        // Parameters must be absent
+        assert!(ASN1(AlgorithmIdentifier).parse(&[ 0x30, 0x07, 0x06, 0x03, 0x2B, 0x65, 0x70, 0x05, 0x00 ]).is_err());
+    }
+
+    #[test]
+    fn legacy_rsa() {
+        // sha1WithRSAEncryption with NULL parameter
+        let (_, alg) = ASN1(AlgorithmIdentifier).parse(&[
+            0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x05, 0x05, 0x00,
+        ]).unwrap();
+        assert!(matches!(alg.param, AlgorithmParamValue::RSASignatureWithSHA1(Either::Left(..))));
+
+        // md5WithRSAEncryption with absent parameter
+        let (_, alg) = ASN1(AlgorithmIdentifier).parse(&[
+            0x30, 0x0B, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x04,
+        ]).unwrap();
+        assert!(matches!(alg.param, AlgorithmParamValue::RSASignatureWithMD5(Either::Right(..))));
+
+        // md2WithRSAEncryption
+        let (_, alg) = ASN1(AlgorithmIdentifier).parse(&[
+            0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x02, 0x05, 0x00,
+        ]).unwrap();
+        assert!(matches!(alg.param, AlgorithmParamValue::RSASignatureWithMD2(..)));
+    }
 }
@@ -1,6 +1,7 @@
 use vstd::prelude::*;
 
 use crate::asn1::*;
+use crate::asn1::Integer;
 use crate::common::*;
 
 use super::macros::*;
@@ -31,6 +32,105 @@ asn1! {
         modulus: ASN1<BigInt> = ASN1(BigInt),
         exponent: ASN1<BigInt> = ASN1(BigInt),
     }
+
+    // RFC 4055, 3.1
+    // RSASSA-PSS-params  ::=  SEQUENCE  {
+    //     hashAlgorithm      [0] HashAlgorithm DEFAULT sha1Identifier,
+    //     maskGenAlgorithm   [1] MaskGenAlgorithm DEFAULT mgf1SHA1Identifier,
+    //     saltLength         [2] INTEGER DEFAULT 20,
+    //     trailerField       [3] INTEGER DEFAULT 1  }
+    //
+    // NOTE: #[default(...)] only takes values constructible in spec mode,
+    // which rules out the OIDs in sha1Identifier and mgf1SHA1Identifier.
+    // So hashAlgorithm and maskGenAlgorithm are parsed as optional fields,
+    // with None standing for the default values, and the refinements below
+    // reject an explicit encoding of the default value (X.690, 11.5),
+    // which is exactly what #[default] would enforce
+    seq RSASSAPSSParam {
+        #[optional] hash_alg: ASN1<ExplicitTag<Refined<ASN1<HashAlgorithm>, NonDefaultHashAlgorithm>>>
+            = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), Refined { inner: ASN1(HashAlgorithm), predicate: NonDefaultHashAlgorithm })),
+        #[optional] mask_gen_alg: ASN1<ExplicitTag<Refined<ASN1<MaskGenAlgorithm>, NonDefaultMaskGenAlgorithm>>>
+            = ASN1(ExplicitTag(tag_of!(EXPLICIT 1), Refined { inner: ASN1(MaskGenAlgorithm), predicate: NonDefaultMaskGenAlgorithm })),
+        #[default(20i64)] salt_len: ASN1<ExplicitTag<ASN1<Integer>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 2), ASN1(Integer))),
+        #[default(1i64)] trailer_field: ASN1<ExplicitTag<ASN1<Integer>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 3), ASN1(Integer))),
+    }
+
+    // HashAlgorithm ::= AlgorithmIdentifier
+    //
+    // NOTE: the parameters are NULL or absent for all
+    // hash functions allowed in RSASSA-PSS-params
+    seq HashAlgorithm {
+        id: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+        #[optional] param: ASN1<Null> = ASN1(Null),
+    }
+
+    // MaskGenAlgorithm ::= AlgorithmIdentifier
+    //
+    // NOTE: the only mask generation function defined is
+    // MGF1, whose parameter is a HashAlgorithm
+    seq MaskGenAlgorithm {
+        id: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+        param: ASN1<HashAlgorithm> = ASN1(HashAlgorithm),
+    }
+}
+
+/// Rejects sha1Identifier ::= { id-sha1, NULL }, the
+/// DEFAULT value of RSASSA-PSS-params.hashAlgorithm (RFC 4055, 2.1)
+#[derive(View)]
+pub struct NonDefaultHashAlgorithm;
+
+impl NonDefaultHashAlgorithm {
+    pub open spec fn is_sha1_identifier(v: SpecHashAlgorithmValue) -> bool {
+        v.id == spec_oid!(SHA1) && v.param is Some
+    }
+
+    fn exec_is_sha1_identifier(v: &HashAlgorithmValue) -> (res: bool)
+        ensures res == Self::is_sha1_identifier(v@)
+    {
+        v.id.polyfill_eq(&oid!(SHA1)) && match &v.param {
+            OptionDeep::Some(..) => true,
+            OptionDeep::None => false,
+        }
+    }
+}
+
+impl SpecPred for NonDefaultHashAlgorithm {
+    type Input = SpecHashAlgorithmValue;
+
+    open spec fn spec_apply(&self, v: &Self::Input) -> bool {
+        !Self::is_sha1_identifier(*v)
+    }
+}
+
+impl Pred for NonDefaultHashAlgorithm {
+    type Input<'a> = HashAlgorithmValue<'a>;
+    type InputOwned = HashAlgorithmValueOwned;
+
+    fn apply(&self, v: &Self::Input<'_>) -> (res: bool) {
+        !Self::exec_is_sha1_identifier(v)
+    }
+}
+
+/// Rejects mgf1SHA1Identifier ::= { id-mgf1, sha1Identifier }, the
+/// DEFAULT value of RSASSA-PSS-params.maskGenAlgorithm (RFC 4055, 2.2)
+#[derive(View)]
+pub struct NonDefaultMaskGenAlgorithm;
+
+impl SpecPred for NonDefaultMaskGenAlgorithm {
+    type Input = SpecMaskGenAlgorithmValue;
+
+    open spec fn spec_apply(&self, v: &Self::Input) -> bool {
+        !(v.id == spec_oid!(MGF1) && NonDefaultHashAlgorithm::is_sha1_identifier(v.param))
+    }
+}
+
+impl Pred for NonDefaultMaskGenAlgorithm {
+    type Input<'a> = MaskGenAlgorithmValue<'a>;
+    type InputOwned = MaskGenAlgorithmValueOwned;
+
+    fn apply(&self, v: &Self::Input<'_>) -> (res: bool) {
+        !(v.id.polyfill_eq(&oid!(MGF1)) && NonDefaultHashAlgorithm::exec_is_sha1_identifier(&v.param))
+    }
 }
 
 // TODO: DSA, ECDSA, etc.
@@ -40,15 +140,19 @@ oid_match_continuation! {
         // NOTE: for some of these, technically the param field should
         // be NULL (or for some should be empty), but some certificates
         // do not comply with this
-        // oid(RSA_SIGNATURE_MD2) => RSASignatureWithMD2(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
-        // oid(RSA_SIGNATURE_MD5) => RSASignatureWithMD5(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
-        // oid(RSA_SIGNATURE_SHA1) => RSASignatureWithSHA1(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
+        oid(RSA_SIGNATURE_MD2) => RSASignatureWithMD2(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
+        oid(RSA_SIGNATURE_MD5) => RSASignatureWithMD5(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
+        oid(RSA_SIGNATURE_SHA1) => RSASignatureWithSHA1(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
 
         oid(RSA_SIGNATURE_SHA256) => RSASignatureWithSHA256(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
         oid(RSA_SIGNATURE_SHA384) => RSASignatureWithSHA384(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
         oid(RSA_SIGNATURE_SHA512) => RSASignatureWithSHA512(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
         oid(RSA_SIGNATURE_SHA224) => RSASignatureWithSHA224(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
 
+        // Parameters are required in signatureAlgorithm,
+        // but may be absent in subject public key info
+        oid(RSASSA_PSS) => RSASSAPSS(OrdChoice(ASN1(RSASSAPSSParam), End)): OrdChoice<ASN1<RSASSAPSSParam>, End>,
+
         oid(DSA_SIGNATURE) => DSASignature(OrdChoice(ASN1(DSAParam), End)): OrdChoice<ASN1<DSAParam>, End>,
 
         oid(ECDSA_SIGNATURE_SHA224) => ECDSASignatureWithSHA224(End): End,
@@ -56,6 +160,12 @@ oid_match_continuation! {
         oid(ECDSA_SIGNATURE_SHA384) => ECDSASignatureWithSHA384(End): End,
         oid(ECDSA_SIGNATURE_SHA512) => ECDSASignatureWithSHA512(End): End,
 
+        // RFC 8410: the same OIDs are used for both signature
+        // and subject public key algorithms, and the parameters
+        // MUST be absent
+        oid(ED25519) => Ed25519(End): End,
+        oid(ED448) => Ed448(End): End,
+
         // Subject public key algorithms
         oid(RSA_ENCRYPTION) => RSAEncryption(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
         oid(EC_PUBLIC_KEY) => ECPublicKey(ASN1(ObjectIdentifier)): ASN1<ObjectIdentifier>, // Currently only support named curves
@@ -70,21 +180,45 @@ impl<'a> PolyfillEq for DSAParamValue<'a> {
     }
 }
 
+impl<'a> PolyfillEq for HashAlgorithmValue<'a> {
+    fn polyfill_eq(&self, other: &Self) -> bool {
+        self.id.polyfill_eq(&other.id) && self.param.polyfill_eq(&other.param)
+    }
+}
+
+impl<'a> PolyfillEq for MaskGenAlgorithmValue<'a> {
+    fn polyfill_eq(&self, other: &Self) -> bool {
+        self.id.polyfill_eq(&other.id) && self.param.polyfill_eq(&other.param)
+    }
+}
+
+impl<'a> PolyfillEq for RSASSAPSSParamValue<'a> {
+    fn polyfill_eq(&self, other: &Self) -> bool {
+        self.hash_alg.polyfill_eq(&other.hash_alg) &&
+        self.mask_gen_alg.polyfill_eq(&other.mask_gen_alg) &&
+        self.salt_len.polyfill_eq(&other.salt_len) &&
+        self.trailer_field.polyfill_eq(&other.trailer_field)
+    }
+}
+
 impl<'a> PolyfillEq for AlgorithmParamValue<'a> {
     fn polyfill_eq(&self, other: &Self) -> bool {
         match (self, other) {
-            // (AlgorithmParamValue::RSASignatureWithMD2(a), AlgorithmParamValue::RSASignatureWithMD2(b)) => a.polyfill_eq(b),
-            // (AlgorithmParamValue::RSASignatureWithMD5(a), AlgorithmParamValue::RSASignatureWithMD5(b)) => a.polyfill_eq(b),
-            // (AlgorithmParamValue::RSASignatureWithSHA1(a), AlgorithmParamValue::RSASignatureWithSHA1(b)) => a.polyfill_eq(b),
+            (AlgorithmParamValue::RSASignatureWithMD2(a), AlgorithmParamValue::RSASignatureWithMD2(b)) => a.polyfill_eq(b),
+            (AlgorithmParamValue::RSASignatureWithMD5(a), AlgorithmParamValue::RSASignatureWithMD5(b)) => a.polyfill_eq(b),
+            (AlgorithmParamValue::RSASignatureWithSHA1(a), AlgorithmParamValue::RSASignatureWithSHA1(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::RSASignatureWithSHA256(a), AlgorithmParamValue::RSASignatureWithSHA256(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::RSASignatureWithSHA384(a), AlgorithmParamValue::RSASignatureWithSHA384(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::RSASignatureWithSHA512(a), AlgorithmParamValue::RSASignatureWithSHA512(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::RSASignatureWithSHA224(a), AlgorithmParamValue::RSASignatureWithSHA224(b)) => a.polyfill_eq(b),
+            (AlgorithmParamValue::RSASSAPSS(a), AlgorithmParamValue::RSASSAPSS(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::DSASignature(a), AlgorithmParamValue::DSASignature(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::ECDSASignatureWithSHA224(a), AlgorithmParamValue::ECDSASignatureWithSHA224(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::ECDSASignatureWithSHA256(a), AlgorithmParamValue::ECDSASignatureWithSHA256(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::ECDSASignatureWithSHA384(a), AlgorithmParamValue::ECDSASignatureWithSHA384(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::ECDSASignatureWithSHA512(a), AlgorithmParamValue::ECDSASignatureWithSHA512(b)) => a.polyfill_eq(b),
+            (AlgorithmParamValue::Ed25519(a), AlgorithmParamValue::Ed25519(b)) => a.polyfill_eq(b),
+            (AlgorithmParamValue::Ed448(a), AlgorithmParamValue::Ed448(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::RSAEncryption(a), AlgorithmParamValue::RSAEncryption(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::ECPublicKey(a), AlgorithmParamValue::ECPublicKey(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::Other(a), AlgorithmParamValue::Other(b)) => a.polyfill_eq(b),
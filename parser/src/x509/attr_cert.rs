@@ -0,0 +1,208 @@
+use vstd::prelude::*;
+
+use crate::asn1::*;
+use crate::asn1::Integer;
+
+use crate::common::*;
+use super::*;
+
+verus! {
+
+// RFC 5755, 4.1
+// AttributeCertificate ::= SEQUENCE {
+//     acinfo               AttributeCertificateInfo,
+//     signatureAlgorithm   AlgorithmIdentifier,
+//     signatureValue       BIT STRING }
+//
+// AttributeCertificateInfo ::= SEQUENCE {
+//     version              AttCertVersion -- version is v2,
+//     holder               Holder,
+//     issuer               AttCertIssuer,
+//     signature            AlgorithmIdentifier,
+//     serialNumber         CertificateSerialNumber,
+//     attrCertValidityPeriod   AttCertValidityPeriod,
+//     attributes           SEQUENCE OF Attribute,
+//     issuerUniqueID       UniqueIdentifier OPTIONAL,
+//     extensions           Extensions OPTIONAL }
+//
+// AttCertIssuer ::= CHOICE {
+//     v1Form   GeneralNames,  -- MUST NOT be used in this profile
+//     v2Form   [0] V2Form     -- v2 only }
+asn1! {
+    seq AttributeCertificateInfo {
+        version: ASN1<Integer> = ASN1(Integer),
+        holder: ASN1<Holder> = ASN1(Holder),
+
+        // NOTE: only the v2Form is accepted, as required by RFC 5755
+        issuer: ASN1<ImplicitTag<V2Form>> = ASN1(ImplicitTag(tag_of!(EXPLICIT 0), V2Form)),
+
+        signature: ASN1<AlgorithmIdentifier> = ASN1(AlgorithmIdentifier),
+        serial: ASN1<BigInt> = ASN1(BigInt),
+        validity: ASN1<AttCertValidityPeriod> = ASN1(AttCertValidityPeriod),
+        attrs: ASN1<AttCertAttributes> = ASN1(AttCertAttributes),
+
+        #[optional] issuer_uid: ASN1<BitString> = ASN1(BitString),
+        #[optional] extensions: ASN1<Extensions> = ASN1(Extensions),
+    }
+
+    seq of AttCertAttributes(ASN1(Attribute)): ASN1<Attribute>;
+
+    // Holder ::= SEQUENCE {
+    //     baseCertificateID   [0] IssuerSerial OPTIONAL,
+    //     entityName          [1] GeneralNames OPTIONAL,
+    //     objectDigestInfo    [2] ObjectDigestInfo OPTIONAL }
+    seq Holder {
+        #[optional] base_cert_id: ASN1<ImplicitTag<IssuerSerial>> = ASN1(ImplicitTag(tag_of!(EXPLICIT 0), IssuerSerial)),
+        #[optional] entity_name: ASN1<ImplicitTag<GeneralNames>> = ASN1(ImplicitTag(tag_of!(EXPLICIT 1), GeneralNames)),
+        #[optional] object_digest_info: ASN1<ImplicitTag<ObjectDigestInfo>> = ASN1(ImplicitTag(tag_of!(EXPLICIT 2), ObjectDigestInfo)),
+    }
+
+    // V2Form ::= SEQUENCE {
+    //     issuerName          GeneralNames OPTIONAL,
+    //     baseCertificateID   [0] IssuerSerial OPTIONAL,
+    //     objectDigestInfo    [1] ObjectDigestInfo OPTIONAL }
+    seq V2Form {
+        #[optional] issuer_name: ASN1<GeneralNames> = ASN1(GeneralNames),
+        #[optional] base_cert_id: ASN1<ImplicitTag<IssuerSerial>> = ASN1(ImplicitTag(tag_of!(EXPLICIT 0), IssuerSerial)),
+        #[optional] object_digest_info: ASN1<ImplicitTag<ObjectDigestInfo>> = ASN1(ImplicitTag(tag_of!(EXPLICIT 1), ObjectDigestInfo)),
+    }
+
+    // IssuerSerial ::= SEQUENCE {
+    //     issuer      GeneralNames,
+    //     serial      CertificateSerialNumber,
+    //     issuerUID   UniqueIdentifier OPTIONAL }
+    seq IssuerSerial {
+        issuer: ASN1<GeneralNames> = ASN1(GeneralNames),
+        serial: ASN1<BigInt> = ASN1(BigInt),
+        #[optional] issuer_uid: ASN1<BitString> = ASN1(BitString),
+    }
+
+    // ObjectDigestInfo ::= SEQUENCE {
+    //     digestedObjectType  ENUMERATED {
+    //         publicKey           (0),
+    //         publicKeyCert       (1),
+    //         otherObjectTypes    (2) },
+    //     otherObjectTypeID   OBJECT IDENTIFIER OPTIONAL,
+    //     digestAlgorithm     AlgorithmIdentifier,
+    //     objectDigest        BIT STRING }
+    seq ObjectDigestInfo {
+        digested_object_type: ASN1<Enumerated> = ASN1(Enumerated),
+        #[optional] other_object_type_id: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+        digest_alg: ASN1<AlgorithmIdentifier> = ASN1(AlgorithmIdentifier),
+        object_digest: ASN1<BitString> = ASN1(BitString),
+    }
+
+    // AttCertValidityPeriod ::= SEQUENCE {
+    //     notBeforeTime   GeneralizedTime,
+    //     notAfterTime    GeneralizedTime }
+    seq AttCertValidityPeriod {
+        not_before: ASN1<GeneralizedTime> = ASN1(GeneralizedTime),
+        not_after: ASN1<GeneralizedTime> = ASN1(GeneralizedTime),
+    }
+
+    seq AttributeCertificateInner {
+        acinfo: Cached<ASN1<AttributeCertificateInfo>> = Cached(ASN1(AttributeCertificateInfo)),
+        sig_alg: ASN1<AlgorithmIdentifier> = ASN1(AlgorithmIdentifier),
+        sig: ASN1<BitString> = ASN1(BitString),
+    }
+}
+
+wrap_combinator! {
+    pub struct AttributeCertificate: Cached<ASN1<AttributeCertificateInner>> = Cached(ASN1(AttributeCertificateInner));
+}
+
+pub type SpecAttributeCertificateValue = SpecAttributeCertificateInnerValue;
+pub type AttributeCertificateValue<'a> = CachedValue<'a, ASN1<AttributeCertificateInner>>;
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    verus! {
+        /// Check that all trait bounds and preconditions are satisfied
+        #[test]
+        fn is_combinator() {
+            let _ = AttributeCertificate.parse(&[]);
+            let _ = ASN1(AttributeCertificateInfo).parse(&[]);
+            let _ = ASN1(Holder).parse(&[]);
+            let _ = ASN1(V2Form).parse(&[]);
+            let _ = ASN1(ObjectDigestInfo).parse(&[]);
+        }
+
+        /// Check if the serialization cache is correct
+        #[test]
+        fn cached() {
+            if let Ok((_, res)) = AttributeCertificate.parse(&[]) {
+                let ser = res.serialize();
+                assert(ASN1(AttributeCertificateInner)@.spec_serialize(res@).is_ok());
+                assert(ser@ == ASN1(AttributeCertificateInner)@.spec_serialize(res@).unwrap());
+
+                let acinfo: &CachedValue<ASN1<AttributeCertificateInfo>> = &res.get().acinfo;
+                let acinfo_ser = acinfo.serialize();
+                assert(ASN1(AttributeCertificateInfo)@.spec_serialize(acinfo@).is_ok());
+                assert(acinfo_ser@ == ASN1(AttributeCertificateInfo)@.spec_serialize(acinfo@).unwrap());
+            }
+        }
+    }
+
+    /// A v2 attribute certificate with a role, a group and a clearance attribute
+    #[test]
+    fn role_group_clearance() {
+        let bytes = [
+            0x30, 0x81, 0xBC, 0x30, 0x81, 0xA5, 0x02, 0x01, 0x01, 0x30, 0x11, 0xA0, 0x0F, 0x30, 0x09, 0x82, 0x07, 0x63, 0x61, 0x2E,
+            0x74, 0x65, 0x73, 0x74, 0x02, 0x02, 0x12, 0x34, 0xA0, 0x0B, 0x30, 0x09, 0x82, 0x07, 0x61, 0x61, 0x2E, 0x74, 0x65, 0x73,
+            0x74, 0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B, 0x05, 0x00, 0x02, 0x01, 0x01, 0x30,
+            0x22, 0x18, 0x0F, 0x32, 0x30, 0x32, 0x34, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5A, 0x18, 0x0F,
+            0x32, 0x30, 0x32, 0x35, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5A, 0x30, 0x4A, 0x30, 0x1B, 0x06,
+            0x03, 0x55, 0x04, 0x48, 0x31, 0x14, 0x30, 0x12, 0xA1, 0x10, 0x86, 0x0E, 0x75, 0x72, 0x6E, 0x3A, 0x72, 0x6F, 0x6C, 0x65,
+            0x3A, 0x61, 0x64, 0x6D, 0x69, 0x6E, 0x30, 0x17, 0x06, 0x08, 0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x0A, 0x04, 0x31, 0x0B,
+            0x30, 0x09, 0x30, 0x07, 0x0C, 0x05, 0x73, 0x74, 0x61, 0x66, 0x66, 0x30, 0x12, 0x06, 0x03, 0x55, 0x04, 0x37, 0x31, 0x0B,
+            0x30, 0x09, 0x06, 0x03, 0x2A, 0x03, 0x04, 0x03, 0x02, 0x02, 0x04, 0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7,
+            0x0D, 0x01, 0x01, 0x0B, 0x05, 0x00, 0x03, 0x03, 0x00, 0xAB, 0xCD,
+        ];
+
+        let (n, ac) = AttributeCertificate.parse(&bytes).unwrap();
+        assert_eq!(n, bytes.len());
+        assert_eq!(&bytes[..], ac.serialize());
+
+        let info = ac.get().acinfo.get();
+        assert_eq!(info.version, 1);
+        assert!(matches!(info.holder.base_cert_id, OptionDeep::Some(..)));
+        assert!(matches!(info.holder.entity_name, OptionDeep::None));
+        assert!(matches!(info.issuer.issuer_name, OptionDeep::Some(..)));
+        assert_eq!(info.validity.not_before.year, 2024);
+        assert_eq!(info.validity.not_after.year, 2025);
+        assert!(matches!(info.extensions, OptionDeep::None));
+
+        assert_eq!(info.attrs.len(), 3);
+
+        match &info.attrs.get(0).param {
+            AttributeParamValue::Role(roles) => {
+                assert_eq!(roles.len(), 1);
+                assert!(matches!(roles.get(0).role_authority, OptionDeep::None));
+                assert_eq!(roles.get(0).role_name, GeneralNameValue::URI("urn:role:admin"));
+            }
+            _ => panic!("{:?}", info.attrs),
+        }
+
+        match &info.attrs.get(1).param {
+            AttributeParamValue::Group(groups) => {
+                assert_eq!(groups.len(), 1);
+                assert_eq!(groups.get(0).values.len(), 1);
+                assert!(matches!(groups.get(0).values.get(0), IetfAttrValueValue::String("staff")));
+            }
+            _ => panic!("{:?}", info.attrs),
+        }
+
+        match &info.attrs.get(2).param {
+            AttributeParamValue::Clearance(clearances) => {
+                assert_eq!(clearances.len(), 1);
+                assert!(matches!(clearances.get(0).class_list, OptionDeep::Some(..)));
+                assert!(matches!(clearances.get(0).security_categories, OptionDeep::None));
+            }
+            _ => panic!("{:?}", info.attrs),
+        }
+    }
+}
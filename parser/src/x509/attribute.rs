@@ -0,0 +1,324 @@
+use vstd::prelude::*;
+
+use crate::asn1::*;
+use crate::common::*;
+
+use super::dir_string::*;
+use super::extension::*;
+use super::general_name::*;
+use super::macros::*;
+
+verus! {
+
+broadcast use super::oid::axiom_disjoint_oids;
+
+// Attribute ::= SEQUENCE {
+//     type    AttributeType,
+//     values  SET SIZE(1..MAX) OF AttributeValue }
+//
+// AttributeType ::= OBJECT IDENTIFIER
+// AttributeValue ::= ANY DEFINED BY AttributeType
+pub type AttributeInner = Mapped<
+    LengthWrapped<
+        Depend<
+            ASN1<ObjectIdentifier>,
+            <AttributeParamCont as Continuation>::Output,
+            AttributeParamCont,
+        >,
+    >,
+    AttributeMapper>;
+
+wrap_combinator! {
+    pub struct Attribute: AttributeInner =>
+        spec SpecAttributeValue,
+        exec<'a> AttributeValue<'a>,
+        owned AttributeValueOwned,
+    = Mapped {
+            inner: LengthWrapped(Depend {
+                fst: ASN1(ObjectIdentifier),
+                snd: AttributeParamCont,
+                spec_snd: Ghost(|i| AttributeParamCont::spec_apply(i)),
+            }, Length),
+            mapper: AttributeMapper,
+        };
+}
+
+asn1_tagged!(Attribute, tag_of!(SEQUENCE));
+
+mapper! {
+    pub struct AttributeMapper;
+
+    for <Typ, Param>
+    from AttributeFrom where type AttributeFrom<Typ, Param> = (Typ, Param);
+    to AttributePoly where pub struct AttributePoly<Typ, Param> {
+        pub typ: Typ,
+        pub param: Param,
+    }
+
+    spec SpecAttributeValue with <SpecObjectIdentifierValue, SpecAttributeParamValue>;
+    exec AttributeValue<'a> with <ObjectIdentifierValue, AttributeParamValue<'a>>;
+    owned AttributeValueOwned with <ObjectIdentifierValueOwned, AttributeParamValueOwned>;
+
+    forward(x) {
+        AttributePoly {
+            typ: x.0,
+            param: x.1,
+        }
+    }
+
+    backward(y) {
+        (y.typ, y.param)
+    }
+}
+
+oid_match_continuation! {
+    continuation AttributeParam {
+        // RFC 2985, 5.4.1
+        // challengePassword ATTRIBUTE ::= {
+        //     WITH SYNTAX DirectoryString {pkcs-9-ub-challengePassword}
+        //     EQUALITY MATCHING RULE caseExactMatch
+        //     SINGLE VALUE TRUE
+        //     ID pkcs-9-at-challengePassword }
+        oid(CHALLENGE_PASSWORD) => ChallengePassword(ASN1(ChallengePasswordValues)): ASN1<ChallengePasswordValues>,
+
+        // RFC 2985, 5.4.2
+        // extensionRequest ATTRIBUTE ::= {
+        //     WITH SYNTAX ExtensionRequest
+        //     SINGLE VALUE TRUE
+        //     ID pkcs-9-at-extensionRequest }
+        //
+        // ExtensionRequest ::= Extensions
+        oid(EXTENSION_REQUEST) => ExtensionRequest(ASN1(ExtensionRequestValues)): ASN1<ExtensionRequestValues>,
+
+        // Attributes in attribute certificates (RFC 5755, 4.4)
+        oid(AT_ROLE) => Role(ASN1(RoleValues)): ASN1<RoleValues>,
+        oid(ACA_GROUP) => Group(ASN1(GroupValues)): ASN1<GroupValues>,
+        oid(AT_CLEARANCE) => Clearance(ASN1(ClearanceValues)): ASN1<ClearanceValues>,
+
+        _ => Other(Tail): Tail,
+    }
+}
+
+asn1! {
+    set of ChallengePasswordValues(DirectoryString): DirectoryString;
+    set of ExtensionRequestValues(ASN1(Extensions)): ASN1<Extensions>;
+    set of RoleValues(ASN1(RoleSyntax)): ASN1<RoleSyntax>;
+    set of GroupValues(ASN1(IetfAttrSyntax)): ASN1<IetfAttrSyntax>;
+    set of ClearanceValues(ASN1(Clearance)): ASN1<Clearance>;
+
+    // RoleSyntax ::= SEQUENCE {
+    //     roleAuthority  [0] GeneralNames OPTIONAL,
+    //     roleName       [1] GeneralName }
+    seq RoleSyntax {
+        #[optional] role_authority: ASN1<ImplicitTag<GeneralNames>> = ASN1(ImplicitTag(tag_of!(EXPLICIT 0), GeneralNames)),
+        // NOTE: GeneralName is a CHOICE, so the tag is explicit
+        role_name: ASN1<ExplicitTag<GeneralName>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 1), GeneralName)),
+    }
+
+    // IetfAttrSyntax ::= SEQUENCE {
+    //     policyAuthority [0] GeneralNames OPTIONAL,
+    //     values          SEQUENCE OF CHOICE {
+    //                         octets  OCTET STRING,
+    //                         oid     OBJECT IDENTIFIER,
+    //                         string  UTF8String } }
+    seq IetfAttrSyntax {
+        #[optional] policy_authority: ASN1<ImplicitTag<GeneralNames>> = ASN1(ImplicitTag(tag_of!(EXPLICIT 0), GeneralNames)),
+        values: ASN1<IetfAttrValues> = ASN1(IetfAttrValues),
+    }
+
+    seq of IetfAttrValues(IetfAttrValue): IetfAttrValue;
+
+    choice IetfAttrValue {
+        Octets(ASN1(OctetString)): ASN1<OctetString>,
+        OID(ASN1(ObjectIdentifier)): ASN1<ObjectIdentifier>,
+        String(ASN1(UTF8String)): ASN1<UTF8String>,
+    }
+
+    // Clearance ::= SEQUENCE {
+    //     policyId            OBJECT IDENTIFIER,
+    //     classList           ClassList DEFAULT {unclassified},
+    //     securityCategories  SET OF SecurityCategory OPTIONAL }
+    //
+    // ClassList ::= BIT STRING {
+    //     unmarked (0), unclassified (1), restricted (2),
+    //     confidential (3), secret (4), topSecret (5) }
+    seq Clearance {
+        policy_id: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+        // NOTE: absent means the default {unclassified}
+        #[optional] class_list: ASN1<BitString> = ASN1(BitString),
+        #[optional] security_categories: ASN1<SecurityCategories> = ASN1(SecurityCategories),
+    }
+
+    set of SecurityCategories(ASN1(SecurityCategory)): ASN1<SecurityCategory>;
+
+    // SecurityCategory ::= SEQUENCE {
+    //     type   [0] OBJECT IDENTIFIER,
+    //     value  [1] EXPLICIT ANY DEFINED BY type }
+    seq SecurityCategory {
+        typ: ASN1<ImplicitTag<ObjectIdentifier>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 0), ObjectIdentifier)),
+        value: ASN1<ExplicitTag<Tail>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 1), Tail)),
+    }
+
+    // Attributes ::= SET OF Attribute
+    set of Attributes(ASN1(Attribute)): ASN1<Attribute>;
+}
+
+// RFC 5280, 4.2.1.8
+// SubjectDirectoryAttributes ::= SEQUENCE SIZE (1..MAX) OF Attribute
+//
+// NOTE: this has the same syntax as Attribute above, but with the
+// personal data attributes in RFC 3739 instead of the PKCS#9 ones
+// (the PKCS#9 extensionRequest would make the extension types recursive)
+pub type DirectoryAttributeInner = Mapped<
+    LengthWrapped<
+        Depend<
+            ASN1<ObjectIdentifier>,
+            <DirectoryAttributeParamCont as Continuation>::Output,
+            DirectoryAttributeParamCont,
+        >,
+    >,
+    DirectoryAttributeMapper>;
+
+wrap_combinator! {
+    pub struct DirectoryAttribute: DirectoryAttributeInner =>
+        spec SpecDirectoryAttributeValue,
+        exec<'a> DirectoryAttributeValue<'a>,
+        owned DirectoryAttributeValueOwned,
+    = Mapped {
+            inner: LengthWrapped(Depend {
+                fst: ASN1(ObjectIdentifier),
+                snd: DirectoryAttributeParamCont,
+                spec_snd: Ghost(|i| DirectoryAttributeParamCont::spec_apply(i)),
+            }, Length),
+            mapper: DirectoryAttributeMapper,
+        };
+}
+
+asn1_tagged!(DirectoryAttribute, tag_of!(SEQUENCE));
+
+mapper! {
+    pub struct DirectoryAttributeMapper;
+
+    for <Typ, Param>
+    from DirectoryAttributeFrom where type DirectoryAttributeFrom<Typ, Param> = (Typ, Param);
+    to DirectoryAttributePoly where pub struct DirectoryAttributePoly<Typ, Param> {
+        pub typ: Typ,
+        pub param: Param,
+    }
+
+    spec SpecDirectoryAttributeValue with <SpecObjectIdentifierValue, SpecDirectoryAttributeParamValue>;
+    exec DirectoryAttributeValue<'a> with <ObjectIdentifierValue, DirectoryAttributeParamValue<'a>>;
+    owned DirectoryAttributeValueOwned with <ObjectIdentifierValueOwned, DirectoryAttributeParamValueOwned>;
+
+    forward(x) {
+        DirectoryAttributePoly {
+            typ: x.0,
+            param: x.1,
+        }
+    }
+
+    backward(y) {
+        (y.typ, y.param)
+    }
+}
+
+oid_match_continuation! {
+    continuation DirectoryAttributeParam {
+        // DateOfBirth ::= GeneralizedTime
+        oid(PDA_DATE_OF_BIRTH) => DateOfBirth(ASN1(GeneralizedTimeValues)): ASN1<GeneralizedTimeValues>,
+
+        // PlaceOfBirth ::= DirectoryString
+        oid(PDA_PLACE_OF_BIRTH) => PlaceOfBirth(ASN1(DirectoryStringValues)): ASN1<DirectoryStringValues>,
+
+        // Gender ::= PrintableString (SIZE(1))
+        oid(PDA_GENDER) => Gender(ASN1(PrintableStringValues)): ASN1<PrintableStringValues>,
+
+        // CountryOfCitizenship ::= PrintableString (SIZE (2))
+        // CountryOfResidence ::= PrintableString (SIZE (2))
+        oid(PDA_COUNTRY_OF_CITIZENSHIP) => CountryOfCitizenship(ASN1(PrintableStringValues)): ASN1<PrintableStringValues>,
+        oid(PDA_COUNTRY_OF_RESIDENCE) => CountryOfResidence(ASN1(PrintableStringValues)): ASN1<PrintableStringValues>,
+
+        _ => Other(Tail): Tail,
+    }
+}
+
+asn1! {
+    set of GeneralizedTimeValues(ASN1(GeneralizedTime)): ASN1<GeneralizedTime>;
+    set of DirectoryStringValues(DirectoryString): DirectoryString;
+    set of PrintableStringValues(ASN1(PrintableString)): ASN1<PrintableString>;
+
+    seq of SubjectDirectoryAttributes(ASN1(DirectoryAttribute)): ASN1<DirectoryAttribute>;
+}
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    verus! {
+        /// Check that all trait bounds and preconditions are satisfied
+        #[test]
+        fn is_combinator() {
+            let _ = ASN1(Attribute).parse(&[]);
+            let _ = ASN1(Attributes).parse(&[]);
+            let _ = ASN1(SubjectDirectoryAttributes).parse(&[]);
+        }
+    }
+
+    #[test]
+    fn challenge_password() {
+        let (_, attr) = ASN1(Attribute).parse(&[
+            0x30, 0x15, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x07, 0x31, 0x08, 0x0C,
+            0x06, 0x73, 0x65, 0x63, 0x72, 0x65, 0x74,
+        ]).unwrap();
+
+        match attr.param {
+            AttributeParamValue::ChallengePassword(values) => {
+                assert_eq!(values.len(), 1);
+                assert!(matches!(values.get(0), DirectoryStringValue::UTF8String("secret")));
+            }
+            _ => panic!("{:?}", attr),
+        }
+    }
+
+    #[test]
+    fn unknown() {
+        let (_, attr) = ASN1(Attribute).parse(&[
+            0x30, 0x0C, 0x06, 0x03, 0x2A, 0x03, 0x04, 0x31, 0x05, 0x0C, 0x03, 0x61, 0x62, 0x63,
+        ]).unwrap();
+
+        assert!(matches!(attr.param, AttributeParamValue::Other(..)));
+    }
+
+    #[test]
+    fn subject_dir_attrs() {
+        // dateOfBirth 1970-01-01, countryOfCitizenship DE, and an unknown attribute
+        let (_, attrs) = ASN1(SubjectDirectoryAttributes).parse(&[
+            0x30, 0x3E, 0x30, 0x1D, 0x06, 0x08, 0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x09, 0x01, 0x31, 0x11, 0x18, 0x0F, 0x31, 0x39,
+            0x37, 0x30, 0x30, 0x31, 0x30, 0x31, 0x31, 0x32, 0x30, 0x30, 0x30, 0x30, 0x5A, 0x30, 0x10, 0x06, 0x08, 0x2B, 0x06, 0x01,
+            0x05, 0x05, 0x07, 0x09, 0x04, 0x31, 0x04, 0x13, 0x02, 0x44, 0x45, 0x30, 0x0B, 0x06, 0x03, 0x2A, 0x03, 0x04, 0x31, 0x04,
+            0x02, 0x02, 0x01, 0x00,
+        ]).unwrap();
+
+        assert_eq!(attrs.len(), 3);
+
+        match &attrs.get(0).param {
+            DirectoryAttributeParamValue::DateOfBirth(times) => {
+                assert_eq!(times.len(), 1);
+                assert_eq!(times.get(0).year, 1970);
+            }
+            _ => panic!("{:?}", attrs),
+        }
+
+        match &attrs.get(1).param {
+            DirectoryAttributeParamValue::CountryOfCitizenship(countries) => {
+                assert_eq!(countries.len(), 1);
+                assert_eq!(*countries.get(0), "DE");
+            }
+            _ => panic!("{:?}", attrs),
+        }
+
+        assert!(matches!(attrs.get(2).param, DirectoryAttributeParamValue::Other(..)));
+    }
+}
@@ -0,0 +1,169 @@
+use vstd::prelude::*;
+
+use crate::asn1::*;
+use crate::asn1::Integer;
+
+use crate::common::*;
+use super::*;
+
+verus! {
+
+// RFC 5280, 5.1
+// CertificateList  ::=  SEQUENCE  {
+//     tbsCertList          TBSCertList,
+//     signatureAlgorithm   AlgorithmIdentifier,
+//     signatureValue       BIT STRING  }
+//
+// TBSCertList  ::=  SEQUENCE  {
+//     version                 Version OPTIONAL,
+//                                  -- if present, MUST be v2
+//     signature               AlgorithmIdentifier,
+//     issuer                  Name,
+//     thisUpdate              Time,
+//     nextUpdate              Time OPTIONAL,
+//     revokedCertificates     SEQUENCE OF SEQUENCE  {
+//          userCertificate         CertificateSerialNumber,
+//          revocationDate          Time,
+//          crlEntryExtensions      Extensions OPTIONAL
+//                                   -- if present, version MUST be v2
+//                               }  OPTIONAL,
+//     crlExtensions           [0]  EXPLICIT Extensions OPTIONAL
+//                                   -- if present, version MUST be v2
+//                               }
+asn1! {
+    seq TBSCertList {
+        // NOTE: unlike TBSCertificate, the version here is untagged
+        #[optional] version: ASN1<Integer> = ASN1(Integer),
+
+        signature: ASN1<AlgorithmIdentifier> = ASN1(AlgorithmIdentifier),
+        issuer: ASN1<Name> = ASN1(Name),
+        this_update: Time = Time,
+
+        // NOTE: the unwrapped CHOICE is used so that the disjointness
+        // from the following optional fields can be proved
+        #[optional] next_update: TimeUnwrapped = Time::unwrapped(),
+        #[optional] revoked_certs: ASN1<RevokedCertificates> = ASN1(RevokedCertificates),
+        #[optional] crl_extensions: ASN1<ExplicitTag<ASN1<Extensions>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(Extensions))),
+    }
+
+    seq of RevokedCertificates(ASN1(RevokedCertificate)): ASN1<RevokedCertificate>;
+
+    seq RevokedCertificate {
+        serial: ASN1<BigInt> = ASN1(BigInt),
+        revocation_date: Time = Time,
+        #[optional] crl_entry_extensions: ASN1<Extensions> = ASN1(Extensions),
+    }
+
+    seq CertificateListInner {
+        cert_list: Cached<ASN1<TBSCertList>> = Cached(ASN1(TBSCertList)),
+        sig_alg: ASN1<AlgorithmIdentifier> = ASN1(AlgorithmIdentifier),
+        sig: ASN1<BitString> = ASN1(BitString),
+    }
+}
+
+wrap_combinator! {
+    pub struct CertificateList: Cached<ASN1<CertificateListInner>> = Cached(ASN1(CertificateListInner));
+}
+
+pub type SpecCertificateListValue = SpecCertificateListInnerValue;
+pub type CertificateListValue<'a> = CachedValue<'a, ASN1<CertificateListInner>>;
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    verus! {
+        /// Check that all trait bounds and preconditions are satisfied
+        #[test]
+        fn is_combinator() {
+            let _ = CertificateList.parse(&[]);
+            let _ = ASN1(TBSCertList).parse(&[]);
+            let _ = ASN1(RevokedCertificate).parse(&[]);
+        }
+
+        /// Check if the serialization cache is correct
+        #[test]
+        fn cached() {
+            if let Ok((_, res)) = CertificateList.parse(&[]) {
+                let ser = res.serialize();
+                assert(ASN1(CertificateListInner)@.spec_serialize(res@).is_ok());
+                assert(ser@ == ASN1(CertificateListInner)@.spec_serialize(res@).unwrap());
+
+                let tbs: &CachedValue<ASN1<TBSCertList>> = &res.get().cert_list;
+                let tbs_ser = tbs.serialize();
+                assert(ASN1(TBSCertList)@.spec_serialize(tbs@).is_ok());
+                assert(tbs_ser@ == ASN1(TBSCertList)@.spec_serialize(tbs@).unwrap());
+            }
+        }
+    }
+
+    /// A v1 CRL with only the mandatory fields
+    #[test]
+    fn minimal() {
+        let bytes = [
+            0x30, 0x45, 0x30, 0x2F, 0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B, 0x05, 0x00, 0x30,
+            0x0F, 0x31, 0x0D, 0x30, 0x0B, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0C, 0x04, 0x54, 0x65, 0x73, 0x74, 0x17, 0x0D, 0x32, 0x34,
+            0x30, 0x31, 0x30, 0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5A, 0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7,
+            0x0D, 0x01, 0x01, 0x0B, 0x05, 0x00, 0x03, 0x03, 0x00, 0xAB, 0xCD,
+        ];
+
+        let (n, crl) = CertificateList.parse(&bytes).unwrap();
+        assert_eq!(n, bytes.len());
+        assert_eq!(&bytes[..], crl.serialize());
+
+        let tbs = crl.get().cert_list.get();
+        assert_eq!(tbs.version, OptionDeep::None);
+        assert!(matches!(tbs.next_update, OptionDeep::None));
+        assert!(matches!(tbs.revoked_certs, OptionDeep::None));
+        assert!(matches!(tbs.crl_extensions, OptionDeep::None));
+    }
+
+    /// A v2 CRL with one revoked certificate (reason code keyCompromise)
+    /// and a CRL number extension
+    #[test]
+    fn revoked() {
+        let bytes = [
+            0x30, 0x81, 0x8C, 0x30, 0x76, 0x02, 0x01, 0x01, 0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01,
+            0x0B, 0x05, 0x00, 0x30, 0x0F, 0x31, 0x0D, 0x30, 0x0B, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0C, 0x04, 0x54, 0x65, 0x73, 0x74,
+            0x17, 0x0D, 0x32, 0x34, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5A, 0x17, 0x0D, 0x32, 0x34, 0x30,
+            0x32, 0x30, 0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5A, 0x30, 0x23, 0x30, 0x21, 0x02, 0x02, 0x12, 0x34, 0x17, 0x0D,
+            0x32, 0x34, 0x30, 0x31, 0x31, 0x35, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5A, 0x30, 0x0C, 0x30, 0x0A, 0x06, 0x03, 0x55,
+            0x1D, 0x15, 0x04, 0x03, 0x0A, 0x01, 0x01, 0xA0, 0x0E, 0x30, 0x0C, 0x30, 0x0A, 0x06, 0x03, 0x55, 0x1D, 0x14, 0x04, 0x03,
+            0x02, 0x01, 0x05, 0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B, 0x05, 0x00, 0x03, 0x03,
+            0x00, 0xAB, 0xCD,
+        ];
+
+        let (n, crl) = CertificateList.parse(&bytes).unwrap();
+        assert_eq!(n, bytes.len());
+        assert_eq!(&bytes[..], crl.serialize());
+
+        let tbs = crl.get().cert_list.get();
+        assert_eq!(tbs.version, OptionDeep::Some(1));
+        assert!(matches!(tbs.next_update, OptionDeep::Some(TimeValue::UTCTime(..))));
+
+        match &tbs.revoked_certs {
+            OptionDeep::Some(revoked) => {
+                assert_eq!(revoked.len(), 1);
+
+                match &revoked.get(0).crl_entry_extensions {
+                    OptionDeep::Some(exts) => {
+                        assert_eq!(exts.len(), 1);
+                        assert!(matches!(exts.get(0).param, ExtensionParamValue::ReasonCode(1)));
+                    }
+                    _ => panic!("{:?}", revoked),
+                }
+            }
+            _ => panic!("{:?}", tbs),
+        }
+
+        match &tbs.crl_extensions {
+            OptionDeep::Some(exts) => {
+                assert_eq!(exts.len(), 1);
+                assert!(matches!(exts.get(0).param, ExtensionParamValue::CRLNumber(..)));
+            }
+            _ => panic!("{:?}", tbs),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use vstd::prelude::*;
+
+use crate::asn1::*;
+use crate::asn1::Integer;
+
+use crate::common::*;
+use super::*;
+
+verus! {
+
+// RFC 2986, 4
+// CertificationRequestInfo ::= SEQUENCE {
+//     version       INTEGER { v1(0) } (v1,...),
+//     subject       Name,
+//     subjectPKInfo SubjectPublicKeyInfo{{ PKInfoAlgorithms }},
+//     attributes    [0] Attributes{{ CRIAttributes }}
+// }
+//
+// CertificationRequest ::= SEQUENCE {
+//     certificationRequestInfo CertificationRequestInfo,
+//     signatureAlgorithm AlgorithmIdentifier{{ SignatureAlgorithms }},
+//     signature          BIT STRING
+// }
+asn1! {
+    seq CertificationRequestInfo {
+        version: ASN1<Integer> = ASN1(Integer),
+        subject: ASN1<Name> = ASN1(Name),
+        subject_key: ASN1<PublicKeyInfo> = ASN1(PublicKeyInfo),
+
+        // Attributes is a SET OF, so the implicit tag is constructed
+        attributes: ASN1<ImplicitTag<Attributes>> = ASN1(ImplicitTag(tag_of!(EXPLICIT 0), Attributes)),
+    }
+
+    seq CertificationRequestInner {
+        info: Cached<ASN1<CertificationRequestInfo>> = Cached(ASN1(CertificationRequestInfo)),
+        sig_alg: ASN1<AlgorithmIdentifier> = ASN1(AlgorithmIdentifier),
+        sig: ASN1<BitString> = ASN1(BitString),
+    }
+}
+
+wrap_combinator! {
+    pub struct CertificationRequest: Cached<ASN1<CertificationRequestInner>> = Cached(ASN1(CertificationRequestInner));
+}
+
+pub type SpecCertificationRequestValue = SpecCertificationRequestInnerValue;
+pub type CertificationRequestValue<'a> = CachedValue<'a, ASN1<CertificationRequestInner>>;
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    verus! {
+        /// Check that all trait bounds and preconditions are satisfied
+        #[test]
+        fn is_combinator() {
+            let _ = CertificationRequest.parse(&[]);
+            let _ = ASN1(CertificationRequestInfo).parse(&[]);
+        }
+
+        /// Check if the serialization cache is correct
+        #[test]
+        fn cached() {
+            if let Ok((_, res)) = CertificationRequest.parse(&[]) {
+                let ser = res.serialize();
+                assert(ASN1(CertificationRequestInner)@.spec_serialize(res@).is_ok());
+                assert(ser@ == ASN1(CertificationRequestInner)@.spec_serialize(res@).unwrap());
+
+                let info: &CachedValue<ASN1<CertificationRequestInfo>> = &res.get().info;
+                let info_ser = info.serialize();
+                assert(ASN1(CertificationRequestInfo)@.spec_serialize(info@).is_ok());
+                assert(info_ser@ == ASN1(CertificationRequestInfo)@.spec_serialize(info@).unwrap());
+            }
+        }
+    }
+
+    /// A P-256 CSR generated by OpenSSL with a challengePassword
+    /// and a subjectAltName in the extensionRequest
+    #[test]
+    fn openssl() {
+        let bytes = [
+            0x30, 0x82, 0x01, 0x14, 0x30, 0x81, 0xBA, 0x02, 0x01, 0x00, 0x30, 0x17, 0x31, 0x15, 0x30, 0x13, 0x06, 0x03, 0x55, 0x04,
+            0x03, 0x0C, 0x0C, 0x74, 0x65, 0x73, 0x74, 0x2E, 0x65, 0x78, 0x61, 0x6D, 0x70, 0x6C, 0x65, 0x30, 0x59, 0x30, 0x13, 0x06,
+            0x07, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x02, 0x01, 0x06, 0x08, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07, 0x03, 0x42,
+            0x00, 0x04, 0x35, 0x49, 0x21, 0xEB, 0x4C, 0x1E, 0xB1, 0xFE, 0x2B, 0xD4, 0x1F, 0x63, 0xA5, 0x5D, 0xD8, 0x92, 0xD3, 0x21,
+            0xD5, 0x97, 0xDE, 0xB4, 0x30, 0x96, 0x39, 0x25, 0xE3, 0x52, 0x8B, 0x42, 0x20, 0x87, 0x8A, 0xBD, 0x94, 0x79, 0xB3, 0x2C,
+            0xBF, 0x29, 0xAB, 0xC5, 0xC5, 0xBD, 0x50, 0x11, 0x23, 0xF8, 0x82, 0x50, 0x2B, 0xD3, 0xDC, 0x0D, 0x6E, 0xC4, 0xE0, 0x91,
+            0x7F, 0x7E, 0xCD, 0xE3, 0x26, 0x21, 0xA0, 0x41, 0x30, 0x15, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09,
+            0x07, 0x31, 0x08, 0x0C, 0x06, 0x73, 0x65, 0x63, 0x72, 0x65, 0x74, 0x30, 0x28, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7,
+            0x0D, 0x01, 0x09, 0x0E, 0x31, 0x1B, 0x30, 0x19, 0x30, 0x17, 0x06, 0x03, 0x55, 0x1D, 0x11, 0x04, 0x10, 0x30, 0x0E, 0x82,
+            0x0C, 0x74, 0x65, 0x73, 0x74, 0x2E, 0x65, 0x78, 0x61, 0x6D, 0x70, 0x6C, 0x65, 0x30, 0x0A, 0x06, 0x08, 0x2A, 0x86, 0x48,
+            0xCE, 0x3D, 0x04, 0x03, 0x02, 0x03, 0x49, 0x00, 0x30, 0x46, 0x02, 0x21, 0x00, 0xEE, 0x6A, 0x2B, 0xB1, 0xA4, 0x46, 0xAC,
+            0xB4, 0xFE, 0x70, 0x30, 0xB5, 0xB1, 0xFE, 0xB3, 0xB9, 0x01, 0xDB, 0xD8, 0x36, 0xDE, 0xEA, 0x12, 0x54, 0x04, 0xAD, 0xF0,
+            0x14, 0x16, 0x8C, 0xBA, 0x87, 0x02, 0x21, 0x00, 0xA7, 0xE4, 0xD4, 0x07, 0x40, 0x69, 0x19, 0x88, 0xE3, 0xC0, 0xFE, 0x98,
+            0xCA, 0x89, 0x2A, 0xA7, 0xE0, 0xFA, 0x1F, 0xF4, 0xD7, 0x8F, 0x87, 0x4F, 0x23, 0xAD, 0xD0, 0x22, 0x49, 0x9E, 0xD8, 0x25,
+        ];
+
+        let (n, csr) = CertificationRequest.parse(&bytes).unwrap();
+        assert_eq!(n, bytes.len());
+        assert_eq!(&bytes[..], csr.serialize());
+
+        let info = csr.get().info.get();
+        assert_eq!(info.version, 0);
+        assert_eq!(info.attributes.len(), 2);
+
+        match &info.attributes.get(0).param {
+            AttributeParamValue::ChallengePassword(values) => {
+                assert_eq!(values.len(), 1);
+                assert!(matches!(values.get(0), DirectoryStringValue::UTF8String("secret")));
+            }
+            _ => panic!("{:?}", info),
+        }
+
+        match &info.attributes.get(1).param {
+            AttributeParamValue::ExtensionRequest(values) => {
+                assert_eq!(values.len(), 1);
+                assert_eq!(values.get(0).len(), 1);
+
+                match &values.get(0).get(0).param {
+                    ExtensionParamValue::SubjectAltName(names) => {
+                        assert_eq!(names.len(), 1);
+                        assert_eq!(names.get(0), &GeneralNameValue::DNS("test.example"));
+                    }
+                    _ => panic!("{:?}", values),
+                }
+            }
+            _ => panic!("{:?}", info),
+        }
+    }
+}
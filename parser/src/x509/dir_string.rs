@@ -14,15 +14,17 @@ verus! {
 //     utf8String              UTF8String (SIZE (1.. MAX)), // More common
 //     bmpString               BMPString (SIZE (1..MAX))
 //     ia5String               IA5String (SIZE (1..MAX))
+//     numericString           NumericString (SIZE (1..MAX)) // Not in RFC 5280, but used in legacy DNs
 // }
 asn1! {
     choice DirectoryString {
         PrintableString(ASN1(PrintableString)): ASN1<PrintableString>,
         UTF8String(ASN1(UTF8String)): ASN1<UTF8String>,
         IA5String(ASN1(IA5String)): ASN1<IA5String>,
-        TeletexString(placeholder!(TELETEX_STRING)): placeholder_type!(),
-        UniversalString(placeholder!(UNIVERSAL_STRING)): placeholder_type!(),
-        BMPString(placeholder!(BMP_STRING)): placeholder_type!(),
+        TeletexString(ASN1(TeletexString)): ASN1<TeletexString>,
+        UniversalString(ASN1(UniversalString)): ASN1<UniversalString>,
+        BMPString(ASN1(BMPString)): ASN1<BMPString>,
+        NumericString(ASN1(NumericString)): ASN1<NumericString>,
     }
 }
 
@@ -74,4 +76,39 @@ mod test {
             _ => panic!("{:?}", parsed),
         }
     }
+
+    #[test]
+    fn bmp_string() {
+        assert_eq!(DirectoryString.parse(&[
+            0x1E, 0x04, 0x00, 0x41, 0x00, 0x42,
+        ]).unwrap().1, DirectoryStringValue::BMPString("AB".to_string()));
+
+        // Surrogates are rejected
+        assert!(DirectoryString.parse(&[
+            0x1E, 0x04, 0xD8, 0x3D, 0xDC, 0x68,
+        ]).is_err());
+    }
+
+    #[test]
+    fn universal_string() {
+        assert_eq!(DirectoryString.parse(&[
+            0x1C, 0x08, 0x00, 0x00, 0x00, 0x41, 0x00, 0x00, 0x00, 0x42,
+        ]).unwrap().1, DirectoryStringValue::UniversalString("AB".to_string()));
+    }
+
+    #[test]
+    fn teletex_string() {
+        assert_eq!(DirectoryString.parse(&[
+            0x14, 0x02, 0x41, 0x42,
+        ]).unwrap().1, DirectoryStringValue::TeletexString("AB".to_string()));
+    }
+
+    #[test]
+    fn numeric_string() {
+        assert_eq!(DirectoryString.parse(&[
+            0x12, 0x05, 0x31, 0x32, 0x20, 0x33, 0x34,
+        ]).unwrap().1, DirectoryStringValue::NumericString("12 34"));
+
+        assert!(DirectoryString.parse(&[ 0x12, 0x02, 0x31, 0x41 ]).is_err());
+    }
 }
@@ -12,14 +12,27 @@ impl<'a> Display for DirectoryStringValue<'a> {
             DirectoryStringValue::PrintableString(s) => write!(f, "{}", s),
             DirectoryStringValue::UTF8String(s) => write!(f, "{}", s),
             DirectoryStringValue::IA5String(s) => write!(f, "{}", s),
-            DirectoryStringValue::TeletexString(..) => write!(f, "<TeletexString>"),
-            DirectoryStringValue::UniversalString(..) => write!(f, "<UniversalString>"),
-            DirectoryStringValue::BMPString(..) => write!(f, "<BMPString>"),
+            DirectoryStringValue::TeletexString(s) => write!(f, "{}", s),
+            DirectoryStringValue::UniversalString(s) => write!(f, "{}", s),
+            DirectoryStringValue::BMPString(s) => write!(f, "{}", s),
+            DirectoryStringValue::NumericString(s) => write!(f, "{}", s),
             DirectoryStringValue::Unreachable => write!(f, "<Unreachable>"),
         }
     }
 }
 
+impl<'a> Display for DisplayTextValue<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DisplayTextValue::IA5String(s) => write!(f, "{}", s),
+            DisplayTextValue::VisibleString(s) => write!(f, "{}", s),
+            DisplayTextValue::BMPString(s) => write!(f, "{}", s),
+            DisplayTextValue::UTF8String(s) => write!(f, "{}", s),
+            DisplayTextValue::Unreachable => write!(f, "<Unreachable>"),
+        }
+    }
+}
+
 impl<'a> Display for AttributeTypeAndValueValue<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if self.typ.polyfill_eq(&oid!(COMMON_NAME)) {
@@ -6,18 +6,53 @@ use crate::asn1::Integer;
 
 use crate::common::*;
 
+use super::attribute::*;
 use super::general_name::*;
+use super::policy_qualifier::*;
+use super::rdn::*;
+use super::sct::*;
+use super::qc_statement::*;
 use super::macros::*;
 use super::oid::*;
 
 verus! {
 
+// SkipCerts ::= INTEGER (0..MAX)
+wrap_combinator! {
+    pub struct SkipCerts: Refined<Integer, NonNegativeIntPred> =
+        Refined { inner: Integer, predicate: NonNegativeIntPred };
+}
+
+asn1_tagged!(SkipCerts, tag_of!(INTEGER));
+
+/// Rejects negative integers
+#[derive(View)]
+pub struct NonNegativeIntPred;
+
+impl SpecPred for NonNegativeIntPred {
+    type Input = IntegerValue;
+
+    open spec fn spec_apply(&self, i: &Self::Input) -> bool {
+        *i >= 0
+    }
+}
+
+impl Pred for NonNegativeIntPred {
+    type Input<'a> = IntegerValue;
+    type InputOwned = IntegerValue;
+
+    fn apply(&self, i: &Self::Input<'_>) -> (res: bool)
+    {
+        *i >= 0
+    }
+}
+
 asn1! {
     // RFC 2459, 4.2.1.1
     seq AuthorityKeyIdentifier {
         #[optional] key_id: ASN1<ImplicitTag<OctetString>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 0), OctetString)),
-        // TODO: Parsing of GeneralNames is not implemented yet
-        #[optional] auth_cert_issuer: placeholder_type!() = placeholder!(EXPLICIT 1),
+        // GeneralNames is a SEQUENCE OF, so the implicit tag is constructed
+        #[optional] auth_cert_issuer: ASN1<ImplicitTag<GeneralNames>> = ASN1(ImplicitTag(tag_of!(EXPLICIT 1), GeneralNames)),
         #[optional] auth_cert_serial: ASN1<ImplicitTag<BigInt>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 2), BigInt)),
     }
 
@@ -44,14 +79,40 @@ asn1! {
     // PolicyQualifierId ::= OBJECT IDENTIFIER ( id-qt-cps | id-qt-unotice )
     seq PolicyInfo {
         policy_id: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
-        #[optional] qualifiers: placeholder_type!() = placeholder!(SEQUENCE),
+        #[optional] qualifiers: ASN1<PolicyQualifiers> = ASN1(PolicyQualifiers),
     }
 
+    seq of PolicyQualifiers(ASN1(PolicyQualifierInfo)): ASN1<PolicyQualifierInfo>;
+
     // certificatePolicies ::= SEQUENCE SIZE (1..MAX) OF PolicyInformation
     seq of CertificatePolicies(ASN1(PolicyInfo)): ASN1<PolicyInfo>;
 
+    // PolicyMappings ::= SEQUENCE SIZE (1..MAX) OF SEQUENCE {
+    //     issuerDomainPolicy      CertPolicyId,
+    //     subjectDomainPolicy     CertPolicyId }
+    seq of PolicyMappings(ASN1(PolicyMapping)): ASN1<PolicyMapping>;
+
+    seq PolicyMapping {
+        issuer_domain_policy: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+        subject_domain_policy: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+    }
+
+    // PolicyConstraints ::= SEQUENCE {
+    //     requireExplicitPolicy           [0] SkipCerts OPTIONAL,
+    //     inhibitPolicyMapping            [1] SkipCerts OPTIONAL }
+    //
+    // SkipCerts ::= INTEGER (0..MAX)
+    seq PolicyConstraints {
+        #[optional] require_explicit_policy: ASN1<ImplicitTag<SkipCerts>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 0), SkipCerts)),
+        #[optional] inhibit_policy_mapping: ASN1<ImplicitTag<SkipCerts>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 1), SkipCerts)),
+    }
+
     seq of ExtendedKeyUsage(ASN1(ObjectIdentifier)): ASN1<ObjectIdentifier>;
 
+    // RFC 7633, 6
+    // Features ::= SEQUENCE OF INTEGER
+    seq of TLSFeatures(ASN1(Integer)): ASN1<Integer>;
+
     // NameConstraints ::= SEQUENCE {
     //     permittedSubtrees       [0]     GeneralSubtrees OPTIONAL,
     //     excludedSubtrees        [1]     GeneralSubtrees OPTIONAL }
@@ -77,6 +138,69 @@ asn1! {
         #[default(0i64)] min: ASN1<ImplicitTag<Integer>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 0), Integer)),
         #[optional] max: ASN1<ImplicitTag<Integer>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 1), Integer)),
     }
+
+    // CRLDistributionPoints ::= SEQUENCE SIZE (1..MAX) OF DistributionPoint
+    // (FreshestCRL uses the same syntax)
+    seq of CRLDistributionPoints(ASN1(DistributionPoint)): ASN1<DistributionPoint>;
+
+    // DistributionPoint ::= SEQUENCE {
+    //     distributionPoint       [0]     DistributionPointName OPTIONAL,
+    //     reasons                 [1]     ReasonFlags OPTIONAL,
+    //     cRLIssuer               [2]     GeneralNames OPTIONAL }
+    //
+    // ReasonFlags ::= BIT STRING
+    seq DistributionPoint {
+        // NOTE: DistributionPointName is a CHOICE, so the tag is explicit
+        #[optional] name: ASN1<ExplicitTag<DistributionPointName>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), DistributionPointName)),
+        #[optional] reasons: ASN1<ImplicitTag<BitString>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 1), BitString)),
+        #[optional] crl_issuer: ASN1<ImplicitTag<GeneralNames>> = ASN1(ImplicitTag(tag_of!(EXPLICIT 2), GeneralNames)),
+    }
+
+    // DistributionPointName ::= CHOICE {
+    //     fullName                [0]     GeneralNames,
+    //     nameRelativeToCRLIssuer [1]     RelativeDistinguishedName }
+    choice DistributionPointName {
+        FullName(ASN1(ImplicitTag(tag_of!(EXPLICIT 0), GeneralNames))): ASN1<ImplicitTag<GeneralNames>>,
+        NameRelativeToCRLIssuer(ASN1(ImplicitTag(tag_of!(EXPLICIT 1), RDN))): ASN1<ImplicitTag<RDN>>,
+    }
+
+    // AuthorityInfoAccessSyntax ::= SEQUENCE SIZE (1..MAX) OF AccessDescription
+    // SubjectInfoAccessSyntax ::= SEQUENCE SIZE (1..MAX) OF AccessDescription
+    seq of InfoAccess(ASN1(AccessDescription)): ASN1<AccessDescription>;
+
+    // AccessDescription ::= SEQUENCE {
+    //     accessMethod          OBJECT IDENTIFIER,
+    //     accessLocation        GeneralName }
+    seq AccessDescription {
+        method: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+        location: GeneralName = GeneralName,
+    }
+
+    // RFC 3280, 4.2.1.4
+    // PrivateKeyUsagePeriod ::= SEQUENCE {
+    //     notBefore       [0]     GeneralizedTime OPTIONAL,
+    //     notAfter        [1]     GeneralizedTime OPTIONAL }
+    seq PrivateKeyUsagePeriod {
+        #[optional] not_before: ASN1<ImplicitTag<GeneralizedTime>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 0), GeneralizedTime)),
+        #[optional] not_after: ASN1<ImplicitTag<GeneralizedTime>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 1), GeneralizedTime)),
+    }
+
+    // RFC 5280, 5.2.5
+    // IssuingDistributionPoint ::= SEQUENCE {
+    //     distributionPoint          [0] DistributionPointName OPTIONAL,
+    //     onlyContainsUserCerts      [1] BOOLEAN DEFAULT FALSE,
+    //     onlyContainsCACerts        [2] BOOLEAN DEFAULT FALSE,
+    //     onlySomeReasons            [3] ReasonFlags OPTIONAL,
+    //     indirectCRL                [4] BOOLEAN DEFAULT FALSE,
+    //     onlyContainsAttributeCerts [5] BOOLEAN DEFAULT FALSE }
+    seq IssuingDistributionPoint {
+        #[optional] name: ASN1<ExplicitTag<DistributionPointName>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), DistributionPointName)),
+        #[default(false)] only_user_certs: ASN1<ImplicitTag<Boolean>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 1), Boolean)),
+        #[default(false)] only_ca_certs: ASN1<ImplicitTag<Boolean>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 2), Boolean)),
+        #[optional] only_some_reasons: ASN1<ImplicitTag<BitString>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 3), BitString)),
+        #[default(false)] indirect_crl: ASN1<ImplicitTag<Boolean>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 4), Boolean)),
+        #[default(false)] only_attr_certs: ASN1<ImplicitTag<Boolean>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 5), Boolean)),
+    }
 }
 
 oid_match_continuation! {
@@ -102,11 +226,103 @@ oid_match_continuation! {
         oid(SUBJECT_ALT_NAME) =>
             SubjectAltName(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(GeneralNames)))): ASN1<ExplicitTag<ASN1<GeneralNames>>>,
 
+        // IssuerAltName ::= GeneralNames
+        oid(ISSUER_ALT_NAME) =>
+            IssuerAltName(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(GeneralNames)))): ASN1<ExplicitTag<ASN1<GeneralNames>>>,
+
+        oid(SUBJECT_DIR_ATTRS) =>
+            SubjectDirectoryAttributes(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(SubjectDirectoryAttributes)))): ASN1<ExplicitTag<ASN1<SubjectDirectoryAttributes>>>,
+
+        oid(PRIVATE_KEY_USAGE_PERIOD) =>
+            PrivateKeyUsagePeriod(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(PrivateKeyUsagePeriod)))): ASN1<ExplicitTag<ASN1<PrivateKeyUsagePeriod>>>,
+
         oid(NAME_CONSTRAINTS) =>
             NameConstraints(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(NameConstraints)))): ASN1<ExplicitTag<ASN1<NameConstraints>>>,
 
+        oid(CRL_DISTRIBUTION_POINTS) =>
+            CRLDistributionPoints(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(CRLDistributionPoints)))): ASN1<ExplicitTag<ASN1<CRLDistributionPoints>>>,
+
+        oid(FRESHEST_CRL) =>
+            FreshestCRL(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(CRLDistributionPoints)))): ASN1<ExplicitTag<ASN1<CRLDistributionPoints>>>,
+
+        oid(AUTHORITY_INFO_ACCESS) =>
+            AuthorityInfoAccess(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(InfoAccess)))): ASN1<ExplicitTag<ASN1<InfoAccess>>>,
+
+        oid(SUBJECT_INFO_ACCESS) =>
+            SubjectInfoAccess(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(InfoAccess)))): ASN1<ExplicitTag<ASN1<InfoAccess>>>,
+
+        oid(POLICY_MAPPINGS) =>
+            PolicyMappings(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(PolicyMappings)))): ASN1<ExplicitTag<ASN1<PolicyMappings>>>,
+
+        oid(POLICY_CONSTRAINTS) =>
+            PolicyConstraints(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(PolicyConstraints)))): ASN1<ExplicitTag<ASN1<PolicyConstraints>>>,
+
+        // InhibitAnyPolicy ::= SkipCerts
+        oid(INHIBIT_ANY_POLICY) =>
+            InhibitAnyPolicy(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(SkipCerts)))): ASN1<ExplicitTag<ASN1<SkipCerts>>>,
+
+        // The extension value is an OCTET STRING wrapping the TLS-encoded list
+        oid(CT_SCT_LIST) =>
+            SignedCertTimestampList(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(ExplicitTag(tag_of!(OCTET_STRING), SignedCertTimestampList))))): ASN1<ExplicitTag<ASN1<ExplicitTag<SignedCertTimestampList>>>>,
+
+        oid(TLS_FEATURE) =>
+            TLSFeature(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(TLSFeatures)))): ASN1<ExplicitTag<ASN1<TLSFeatures>>>,
+
+        // RFC 6962, 3.1: the poison extension value is an ASN.1 NULL
+        // (the extension MUST be critical, which is checked by
+        // CriticalCTPoison in Extension)
+        oid(CT_POISON) =>
+            CTPoison(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(Null)))): ASN1<ExplicitTag<ASN1<Null>>>,
+
+        oid(QC_STATEMENTS) =>
+            QCStatements(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(QCStatements)))): ASN1<ExplicitTag<ASN1<QCStatements>>>,
+
+        // CRL extensions (RFC 5280, 5.2)
+        // CRLNumber ::= INTEGER (0..MAX)
+        oid(CRL_NUMBER) =>
+            CRLNumber(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(BigInt)))): ASN1<ExplicitTag<ASN1<BigInt>>>,
+
+        // BaseCRLNumber ::= CRLNumber
+        oid(DELTA_CRL_INDICATOR) =>
+            DeltaCRLIndicator(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(BigInt)))): ASN1<ExplicitTag<ASN1<BigInt>>>,
+
+        oid(ISSUING_DIST_POINT) =>
+            IssuingDistributionPoint(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(IssuingDistributionPoint)))): ASN1<ExplicitTag<ASN1<IssuingDistributionPoint>>>,
+
+        // CRL entry extensions (RFC 5280, 5.3)
+        // CRLReason ::= ENUMERATED { ... }
+        oid(REASON_CODE) =>
+            ReasonCode(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(Enumerated)))): ASN1<ExplicitTag<ASN1<Enumerated>>>,
+
+        // InvalidityDate ::= GeneralizedTime
+        oid(INVALIDITY_DATE) =>
+            InvalidityDate(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(GeneralizedTime)))): ASN1<ExplicitTag<ASN1<GeneralizedTime>>>,
+
+        // CertificateIssuer ::= GeneralNames
+        oid(CERTIFICATE_ISSUER) =>
+            CertificateIssuer(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(GeneralNames)))): ASN1<ExplicitTag<ASN1<GeneralNames>>>,
+
         _ => Other(ASN1(OctetString)): ASN1<OctetString>,
     }
 }
 
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn skip_certs() {
+        assert_eq!(ASN1(SkipCerts).parse(&[ 0x02, 0x01, 0x00 ]).unwrap(), (3, 0));
+        assert_eq!(ASN1(SkipCerts).parse(&[ 0x02, 0x01, 0x03 ]).unwrap(), (3, 3));
+
+        // SkipCerts ::= INTEGER (0..MAX)
+        assert!(ASN1(SkipCerts).parse(&[ 0x02, 0x01, 0xff ]).is_err());
+        assert!(ASN1(SkipCerts).parse(&[ 0x02, 0x01, 0x80 ]).is_err());
+
+        // Implicitly tagged in PolicyConstraints
+        assert!(ASN1(PolicyConstraints).parse(&[ 0x30, 0x03, 0x80, 0x01, 0x02 ]).is_ok());
+        assert!(ASN1(PolicyConstraints).parse(&[ 0x30, 0x03, 0x80, 0x01, 0xfe ]).is_err());
+    }
+}
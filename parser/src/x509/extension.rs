@@ -16,15 +16,17 @@ broadcast use super::oid::axiom_disjoint_oids;
 ///     critical    BOOLEAN DEFAULT FALSE,
 ///     extnValue   OCTET STRING
 /// }
-pub type ExtensionInner = Mapped<
-    LengthWrapped<
-        Depend<
-            ASN1<ObjectIdentifier>,
-            <ExtensionCont as Continuation>::Output,
-            ExtensionCont,
+pub type ExtensionInner = Refined<
+    Mapped<
+        LengthWrapped<
+            Depend<
+                ASN1<ObjectIdentifier>,
+                <ExtensionCont as Continuation>::Output,
+                ExtensionCont,
+            >,
         >,
-    >,
-    ExtensionMapper>;
+        ExtensionMapper>,
+    CriticalCTPoison>;
 
 pub type ExtensionsInner = SequenceOf<ASN1<Extension>>;
 
@@ -33,13 +35,16 @@ wrap_combinator! {
         spec SpecExtensionValue,
         exec<'a> ExtensionValue<'a>,
         owned ExtensionValueOwned,
-    = Mapped {
-            inner: LengthWrapped(Depend {
-                fst: ASN1(ObjectIdentifier),
-                snd: ExtensionCont,
-                spec_snd: Ghost(|i| ExtensionCont::spec_apply(i)),
-            }),
-            mapper: ExtensionMapper,
+    = Refined {
+            inner: Mapped {
+                inner: LengthWrapped(Depend {
+                    fst: ASN1(ObjectIdentifier),
+                    snd: ExtensionCont,
+                    spec_snd: Ghost(|i| ExtensionCont::spec_apply(i)),
+                }, Length),
+                mapper: ExtensionMapper,
+            },
+            predicate: CriticalCTPoison,
         };
 }
 
@@ -77,6 +82,31 @@ mapper! {
     }
 }
 
+/// RFC 6962, 3.1: the CT poison extension MUST be critical
+#[derive(View)]
+pub struct CriticalCTPoison;
+
+impl SpecPred for CriticalCTPoison {
+    type Input = SpecExtensionValue;
+
+    open spec fn spec_apply(&self, ext: &Self::Input) -> bool {
+        ext.param is CTPoison ==> ext.critical
+    }
+}
+
+impl Pred for CriticalCTPoison {
+    type Input<'a> = ExtensionValue<'a>;
+    type InputOwned = ExtensionValueOwned;
+
+    fn apply(&self, ext: &Self::Input<'_>) -> (res: bool)
+    {
+        match &ext.param {
+            ExtensionParamValue::CTPoison(..) => ext.critical,
+            _ => true,
+        }
+    }
+}
+
 /// Parse an optional boolean field ("critical") (default to bool)
 /// before the actual extension parameter
 #[derive(Debug, View)]
@@ -110,6 +140,7 @@ impl Continuation for ExtensionCont {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::x509::*;
 
     verus! {
         /// Check that all trait bounds and preconditions are satisfied
@@ -130,4 +161,129 @@ mod test {
             0x30, 0x40, 0x30, 0x0E, 0x06, 0x03, 0x55, 0x1D, 0x0F, 0x01, 0x01, 0xFF, 0x04, 0x04, 0x03, 0x02, 0x01, 0x86, 0x30, 0x0F, 0x06, 0x03, 0x55, 0x1D, 0x13, 0x01, 0x01, 0xFF, 0x04, 0x05, 0x30, 0x03, 0x01, 0x01, 0xFF, 0x30, 0x1D, 0x06, 0x03, 0x55, 0x1D, 0x0E, 0x04, 0x16, 0x04, 0x14, 0xE4, 0xAF, 0x2B, 0x26, 0x71, 0x1A, 0x2B, 0x48, 0x27, 0x85, 0x2F, 0x52, 0x66, 0x2C, 0xEF, 0xF0, 0x89, 0x13, 0x71, 0x3E,
         ]).is_ok());
     }
+
+    #[test]
+    fn crl_distribution_points() {
+        let parsed = ASN1(Extension).parse(&[
+            0x30, 0x1B, 0x06, 0x03, 0x55, 0x1D, 0x1F, 0x04, 0x14, 0x30, 0x12, 0x30, 0x10, 0xA0, 0x0E, 0xA0, 0x0C,
+            0x86, 0x0A, 0x68, 0x74, 0x74, 0x70, 0x3A, 0x2F, 0x2F, 0x78, 0x2E, 0x79,
+        ]).unwrap().1;
+
+        match parsed.param {
+            ExtensionParamValue::CRLDistributionPoints(dps) => {
+                assert_eq!(dps.len(), 1);
+
+                match &dps.get(0).name {
+                    OptionDeep::Some(DistributionPointNameValue::FullName(names)) => {
+                        assert_eq!(names.len(), 1);
+                        assert_eq!(names.get(0), &GeneralNameValue::URI("http://x.y"));
+                    }
+                    _ => panic!("{:?}", dps),
+                }
+            }
+            _ => panic!("{:?}", parsed),
+        }
+    }
+
+    #[test]
+    fn authority_info_access() {
+        let parsed = ASN1(Extension).parse(&[
+            0x30, 0x26, 0x06, 0x08, 0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x01, 0x01, 0x04, 0x1A, 0x30, 0x18, 0x30, 0x16,
+            0x06, 0x08, 0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x01,
+            0x86, 0x0A, 0x68, 0x74, 0x74, 0x70, 0x3A, 0x2F, 0x2F, 0x78, 0x2E, 0x79,
+        ]).unwrap().1;
+
+        match parsed.param {
+            ExtensionParamValue::AuthorityInfoAccess(descs) => {
+                assert_eq!(descs.len(), 1);
+                assert!(descs.get(0).method.polyfill_eq(&oid!(AD_OCSP)));
+                assert_eq!(descs.get(0).location, GeneralNameValue::URI("http://x.y"));
+            }
+            _ => panic!("{:?}", parsed),
+        }
+    }
+
+    #[test]
+    fn policy_constraints() {
+        let parsed = ASN1(Extension).parse(&[
+            0x30, 0x0F, 0x06, 0x03, 0x55, 0x1D, 0x24, 0x04, 0x08, 0x30, 0x06, 0x80, 0x01, 0x00, 0x81, 0x01, 0x02,
+        ]).unwrap().1;
+
+        match parsed.param {
+            ExtensionParamValue::PolicyConstraints(param) => {
+                assert_eq!(param.require_explicit_policy, OptionDeep::Some(0));
+                assert_eq!(param.inhibit_policy_mapping, OptionDeep::Some(2));
+            }
+            _ => panic!("{:?}", parsed),
+        }
+    }
+
+    #[test]
+    fn issuer_alt_name() {
+        let parsed = ASN1(Extension).parse(&[
+            0x30, 0x15, 0x06, 0x03, 0x55, 0x1D, 0x12, 0x04, 0x0E, 0x30, 0x0C, 0x82, 0x0A, 0x63, 0x61, 0x2E, 0x65, 0x78, 0x61, 0x6D,
+            0x70, 0x6C, 0x65,
+        ]).unwrap().1;
+
+        match parsed.param {
+            ExtensionParamValue::IssuerAltName(names) => {
+                assert_eq!(names.len(), 1);
+                assert_eq!(names.get(0), &GeneralNameValue::DNS("ca.example"));
+            }
+            _ => panic!("{:?}", parsed),
+        }
+    }
+
+    #[test]
+    fn private_key_usage_period() {
+        // Only notAfter is present
+        let parsed = ASN1(Extension).parse(&[
+            0x30, 0x1A, 0x06, 0x03, 0x55, 0x1D, 0x10, 0x04, 0x13, 0x30, 0x11, 0x81, 0x0F, 0x32, 0x30, 0x33, 0x30, 0x30, 0x31, 0x30,
+            0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5A,
+        ]).unwrap().1;
+
+        match parsed.param {
+            ExtensionParamValue::PrivateKeyUsagePeriod(param) => {
+                assert!(matches!(param.not_before, OptionDeep::None));
+
+                match &param.not_after {
+                    OptionDeep::Some(t) => assert_eq!(t.year, 2030),
+                    _ => panic!("{:?}", param),
+                }
+            }
+            _ => panic!("{:?}", parsed),
+        }
+    }
+
+    #[test]
+    fn tls_feature() {
+        // status_request (must-staple)
+        let parsed = ASN1(Extension).parse(&[
+            0x30, 0x11, 0x06, 0x08, 0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x01, 0x18, 0x04, 0x05, 0x30, 0x03, 0x02, 0x01, 0x05,
+        ]).unwrap().1;
+
+        match parsed.param {
+            ExtensionParamValue::TLSFeature(features) => {
+                assert_eq!(features.len(), 1);
+                assert_eq!(*features.get(0), 5);
+            }
+            _ => panic!("{:?}", parsed),
+        }
+    }
+
+    #[test]
+    fn ct_poison() {
+        let parsed = ASN1(Extension).parse(&[
+            0x30, 0x13, 0x06, 0x0A, 0x2B, 0x06, 0x01, 0x04, 0x01, 0xD6, 0x79, 0x02, 0x04, 0x03, 0x01, 0x01, 0xFF, 0x04, 0x02, 0x05,
+            0x00,
+        ]).unwrap().1;
+
+        assert!(parsed.critical);
+        assert!(matches!(parsed.param, ExtensionParamValue::CTPoison(..)));
+
+        // Non-critical poison (critical omitted, i.e. DEFAULT FALSE)
+        assert!(ASN1(Extension).parse(&[
+            0x30, 0x10, 0x06, 0x0A, 0x2B, 0x06, 0x01, 0x04, 0x01, 0xD6, 0x79, 0x02, 0x04, 0x03, 0x04, 0x02, 0x05, 0x00,
+        ]).is_err());
+    }
 }
@@ -4,10 +4,13 @@ use crate::asn1::*;
 use crate::common::*;
 
 use super::name::*;
+use super::dir_string::*;
 use super::macros::*;
 
 verus! {
 
+broadcast use super::oid::axiom_disjoint_oids;
+
 // GeneralName ::= CHOICE {
 //     otherName                       [0]     OtherName,
 //     rfc822Name                      [1]     IA5String,
@@ -18,28 +21,149 @@ verus! {
 //     uniformResourceIdentifier       [6]     IA5String,
 //     iPAddress                       [7]     OCTET STRING,
 //     registeredID                    [8]     OBJECT IDENTIFIER}
-//
-// OtherName ::= SEQUENCE {
-//     type-id    OBJECT IDENTIFIER,
-//     value      [0] EXPLICIT ANY DEFINED BY type-id }
-//
-// EDIPartyName ::= SEQUENCE {
-//     nameAssigner            [0]     DirectoryString OPTIONAL,
-//     partyName               [1]     DirectoryString }
 asn1! {
     choice GeneralName {
-        Other(placeholder!(IMPLICIT 0)): placeholder_type!(),
+        // NOTE: implicit tags on SEQUENCE types keep the constructed bit
+        Other(ASN1(ImplicitTag(tag_of!(EXPLICIT 0), OtherName))): ASN1<ImplicitTag<OtherName>>,
         RFC822(ASN1(ImplicitTag(tag_of!(IMPLICIT 1), IA5String))): ASN1<ImplicitTag<IA5String>>,
         DNS(ASN1(ImplicitTag(tag_of!(IMPLICIT 2), IA5String))): ASN1<ImplicitTag<IA5String>>,
-        X400(placeholder!(IMPLICIT 3)): placeholder_type!(),
+        X400(ASN1(ImplicitTag(tag_of!(EXPLICIT 3), ORAddress))): ASN1<ImplicitTag<ORAddress>>,
         Directory(ASN1(ExplicitTag(tag_of!(EXPLICIT 4), ASN1(Name)))): ASN1<ExplicitTag<ASN1<Name>>>,
-        EDIParty(placeholder!(IMPLICIT 5)): placeholder_type!(),
+        EDIParty(ASN1(ImplicitTag(tag_of!(EXPLICIT 5), EDIPartyName))): ASN1<ImplicitTag<EDIPartyName>>,
         URI(ASN1(ImplicitTag(tag_of!(IMPLICIT 6), IA5String))): ASN1<ImplicitTag<IA5String>>,
         IP(ASN1(ImplicitTag(tag_of!(IMPLICIT 7), OctetString))): ASN1<ImplicitTag<OctetString>>,
         RegisteredID(ASN1(ImplicitTag(tag_of!(IMPLICIT 8), ObjectIdentifier))): ASN1<ImplicitTag<ObjectIdentifier>>,
     }
 
     seq of GeneralNames(GeneralName): GeneralName;
+
+    // EDIPartyName ::= SEQUENCE {
+    //     nameAssigner            [0]     DirectoryString OPTIONAL,
+    //     partyName               [1]     DirectoryString }
+    //
+    // NOTE: DirectoryString is a CHOICE, so the tags are always explicit
+    seq EDIPartyName {
+        #[optional] name_assigner: ASN1<ExplicitTag<DirectoryString>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), DirectoryString)),
+        party_name: ASN1<ExplicitTag<DirectoryString>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 1), DirectoryString)),
+    }
+
+    // ORAddress ::= SEQUENCE {
+    //     built-in-standard-attributes BuiltInStandardAttributes,
+    //     built-in-domain-defined-attributes
+    //                     BuiltInDomainDefinedAttributes OPTIONAL,
+    //     -- see also teletex-domain-defined-attributes
+    //     extension-attributes ExtensionAttributes OPTIONAL }
+    //
+    // BuiltInDomainDefinedAttributes ::= SEQUENCE SIZE
+    //                     (1..ub-domain-defined-attributes) OF
+    //                     BuiltInDomainDefinedAttribute
+    //
+    // ExtensionAttributes ::= SET SIZE (1..ub-extension-attributes) OF
+    //                 ExtensionAttribute
+    //
+    // The attributes themselves are rarely used outside of X.400,
+    // so we only split the address into its three components
+    seq ORAddress {
+        built_in_standard_attrs: placeholder_type!() = placeholder!(SEQUENCE),
+        #[optional] built_in_domain_defined_attrs: placeholder_type!() = placeholder!(SEQUENCE),
+        #[optional] extension_attrs: placeholder_type!() = placeholder!(SET),
+    }
+
+    // PermanentIdentifier ::= SEQUENCE {
+    //     identifierValue    UTF8String             OPTIONAL,
+    //     assigner           OBJECT IDENTIFIER      OPTIONAL }
+    // (RFC 4043)
+    seq PermanentIdentifier {
+        #[optional] identifier_value: ASN1<UTF8String> = ASN1(UTF8String),
+        #[optional] assigner: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+    }
+
+    // HardwareModuleName ::= SEQUENCE {
+    //     hwType OBJECT IDENTIFIER,
+    //     hwSerialNum OCTET STRING }
+    // (RFC 4108)
+    seq HardwareModuleName {
+        hw_type: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+        hw_serial_num: ASN1<OctetString> = ASN1(OctetString),
+    }
+}
+
+// OtherName ::= SEQUENCE {
+//     type-id    OBJECT IDENTIFIER,
+//     value      [0] EXPLICIT ANY DEFINED BY type-id }
+pub type OtherNameInner = Mapped<
+    LengthWrapped<
+        Depend<
+            ASN1<ObjectIdentifier>,
+            <OtherNameParamCont as Continuation>::Output,
+            OtherNameParamCont,
+        >,
+    >,
+    OtherNameMapper>;
+
+wrap_combinator! {
+    pub struct OtherName: OtherNameInner =>
+        spec SpecOtherNameValue,
+        exec<'a> OtherNameValue<'a>,
+        owned OtherNameValueOwned,
+    = Mapped {
+            inner: LengthWrapped(Depend {
+                fst: ASN1(ObjectIdentifier),
+                snd: OtherNameParamCont,
+                spec_snd: Ghost(|i| OtherNameParamCont::spec_apply(i)),
+            }, Length),
+            mapper: OtherNameMapper,
+        };
+}
+
+asn1_tagged!(OtherName, tag_of!(SEQUENCE));
+
+mapper! {
+    pub struct OtherNameMapper;
+
+    for <Id, Param>
+    from OtherNameFrom where type OtherNameFrom<Id, Param> = (Id, Param);
+    to OtherNamePoly where pub struct OtherNamePoly<Id, Param> {
+        pub id: Id,
+        pub param: Param,
+    }
+
+    spec SpecOtherNameValue with <SpecObjectIdentifierValue, SpecOtherNameParamValue>;
+    exec OtherNameValue<'a> with <ObjectIdentifierValue, OtherNameParamValue<'a>>;
+    owned OtherNameValueOwned with <ObjectIdentifierValueOwned, OtherNameParamValueOwned>;
+
+    forward(x) {
+        OtherNamePoly {
+            id: x.0,
+            param: x.1,
+        }
+    }
+
+    backward(y) {
+        (y.id, y.param)
+    }
+}
+
+oid_match_continuation! {
+    continuation OtherNameParam {
+        // Microsoft user principal name
+        oid(MS_SAN_UPN) =>
+            UPN(ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(UTF8String)))): ASN1<ExplicitTag<ASN1<UTF8String>>>,
+
+        // RFC 4043
+        oid(PERMANENT_IDENTIFIER) =>
+            PermanentIdentifier(ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(PermanentIdentifier)))): ASN1<ExplicitTag<ASN1<PermanentIdentifier>>>,
+
+        // RFC 4108
+        oid(HARDWARE_MODULE_NAME) =>
+            HardwareModuleName(ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(HardwareModuleName)))): ASN1<ExplicitTag<ASN1<HardwareModuleName>>>,
+
+        // RFC 8398
+        oid(SMTP_UTF8_MAILBOX) =>
+            SmtpUTF8Mailbox(ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(UTF8String)))): ASN1<ExplicitTag<ASN1<UTF8String>>>,
+
+        _ => Other(ASN1(ExplicitTag(tag_of!(EXPLICIT 0), Tail))): ASN1<ExplicitTag<Tail>>,
+    }
 }
 
 }
@@ -53,6 +177,8 @@ mod test {
         #[test]
         fn is_combinator() {
             let _ = GeneralName.parse(&[]);
+            let _ = ASN1(OtherName).parse(&[]);
+            let _ = ASN1(EDIPartyName).parse(&[]);
         }
     }
 
@@ -69,4 +195,47 @@ mod test {
             0xA4, 0x81, 0x91, 0x30, 0x81, 0x8E, 0x31, 0x47, 0x30, 0x45, 0x06, 0x03, 0x55, 0x04, 0x0A, 0x0C, 0x3E, 0x49, 0x5A, 0x45, 0x4E, 0x50, 0x45, 0x20, 0x53, 0x2E, 0x41, 0x2E, 0x20, 0x2D, 0x20, 0x43, 0x49, 0x46, 0x20, 0x41, 0x30, 0x31, 0x33, 0x33, 0x37, 0x32, 0x36, 0x30, 0x2D, 0x52, 0x4D, 0x65, 0x72, 0x63, 0x2E, 0x56, 0x69, 0x74, 0x6F, 0x72, 0x69, 0x61, 0x2D, 0x47, 0x61, 0x73, 0x74, 0x65, 0x69, 0x7A, 0x20, 0x54, 0x31, 0x30, 0x35, 0x35, 0x20, 0x46, 0x36, 0x32, 0x20, 0x53, 0x38, 0x31, 0x43, 0x30, 0x41, 0x06, 0x03, 0x55, 0x04, 0x09, 0x0C, 0x3A, 0x41, 0x76, 0x64, 0x61, 0x20, 0x64, 0x65, 0x6C, 0x20, 0x4D, 0x65, 0x64, 0x69, 0x74, 0x65, 0x72, 0x72, 0x61, 0x6E, 0x65, 0x6F, 0x20, 0x45, 0x74, 0x6F, 0x72, 0x62, 0x69, 0x64, 0x65, 0x61, 0x20, 0x31, 0x34, 0x20, 0x2D, 0x20, 0x30, 0x31, 0x30, 0x31, 0x30, 0x20, 0x56, 0x69, 0x74, 0x6F, 0x72, 0x69, 0x61, 0x2D, 0x47, 0x61, 0x73, 0x74, 0x65, 0x69, 0x7A,
         ]).is_ok());
     }
+
+    #[test]
+    fn other_name_upn() {
+        let parsed = GeneralName.parse(&[
+            0xA0, 0x15, 0x06, 0x0A, 0x2B, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x14, 0x02, 0x03, 0xA0, 0x07, 0x0C, 0x05, 0x61, 0x40, 0x62, 0x2E, 0x63,
+        ]).unwrap().1;
+
+        match parsed {
+            GeneralNameValue::Other(OtherNamePoly { param: OtherNameParamValue::UPN(s), .. }) => {
+                assert_eq!(s, "a@b.c");
+            }
+            _ => panic!("{:?}", parsed),
+        }
+    }
+
+    #[test]
+    fn other_name_unknown() {
+        let parsed = GeneralName.parse(&[
+            0xA0, 0x0B, 0x06, 0x03, 0x2A, 0x03, 0x04, 0xA0, 0x04, 0x04, 0x02, 0x01, 0x02,
+        ]).unwrap().1;
+
+        match parsed {
+            GeneralNameValue::Other(OtherNamePoly { param: OtherNameParamValue::Other(v), .. }) => {
+                assert_eq!(v, &[0x04, 0x02, 0x01, 0x02]);
+            }
+            _ => panic!("{:?}", parsed),
+        }
+    }
+
+    #[test]
+    fn edi_party_name() {
+        let parsed = GeneralName.parse(&[
+            0xA5, 0x07, 0xA1, 0x05, 0x0C, 0x03, 0x61, 0x62, 0x63,
+        ]).unwrap().1;
+
+        match parsed {
+            GeneralNameValue::EDIParty(name) => {
+                assert!(matches!(name.name_assigner, OptionDeep::None));
+                assert_eq!(name.party_name, DirectoryStringValue::UTF8String("abc"));
+            }
+            _ => panic!("{:?}", parsed),
+        }
+    }
 }
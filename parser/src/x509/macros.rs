@@ -71,7 +71,7 @@ macro_rules! asn1_sequence {
                             exec<'a> Value<'a>,
                             owned ValueOwned,
                         = Mapped {
-                                inner: LengthWrapped(gen_inner_combinator!($(($($modifier $(($modifier_arg))?)?, $field_combinator));*)),
+                                inner: LengthWrapped(gen_inner_combinator!($(($($modifier $(($modifier_arg))?)?, $field_combinator));*), Length),
                                 mapper: Mapper,
                             };
                     }
@@ -634,6 +634,7 @@ macro_rules! asn1_choice {
                 pub use [< internal_ $name >]::SpecValue as [< Spec $name Value >];
                 pub use [< internal_ $name >]::Value as [< $name Value >];
                 pub use [< internal_ $name >]::ValueOwned as [< $name ValueOwned >];
+                pub use [< internal_ $name >]::Unwrapped as [< $name Unwrapped >];
 
                 mod [< internal_ $name >] {
                     #![allow(non_camel_case_types)]
@@ -645,10 +646,7 @@ macro_rules! asn1_choice {
                     use crate::common::*;
 
                     wrap_combinator! {
-                        pub struct $name: Mapped<ord_choice_type!(
-                                $($combinator_type,)+
-                                Unreachable,
-                            ), Mapper> =>
+                        pub struct $name: Unwrapped =>
                             spec SpecValue,
                             exec<'a> Value<'a>,
                             owned ValueOwned,
@@ -661,6 +659,39 @@ macro_rules! asn1_choice {
                             };
                     }
 
+                    /// The same combinator as $name, but without the wrapper,
+                    /// so that its spec_parse is transparent (e.g. when proving
+                    /// disjointness for an optional field of this type)
+                    pub type Unwrapped = Mapped<ord_choice_type!(
+                        $($combinator_type,)+
+                        Unreachable,
+                    ), Mapper>;
+
+                    impl $name {
+                        pub open spec fn spec_unwrapped() -> Unwrapped {
+                            Mapped {
+                                inner: ord_choice!(
+                                    $($combinator,)+
+                                    Unreachable,
+                                ),
+                                mapper: Mapper,
+                            }
+                        }
+
+                        #[verifier::when_used_as_spec(spec_unwrapped)]
+                        pub fn unwrapped() -> (res: Unwrapped)
+                            ensures res == Self::spec_unwrapped()
+                        {
+                            Mapped {
+                                inner: ord_choice!(
+                                    $($combinator,)+
+                                    Unreachable,
+                                ),
+                                mapper: Mapper,
+                            }
+                        }
+                    }
+
                     mapper! {
                         // Implement a mapper from nested Eithers to a specific variant
                         // TODO: same as the mapper in match_continuation, merge?
@@ -745,12 +776,27 @@ macro_rules! asn1_sequence_of {
 }
 pub use asn1_sequence_of;
 
-/// Same as above, but for SET OF
+/// Generate a combinator for an ASN.1 SET OF,
+/// which additionally checks the DER order of elements (see SetOf)
+///
+/// With #[unordered], the elements are accepted in any order (see UnorderedSetOf)
 #[allow(unused_macros)]
 #[macro_export]
 macro_rules! asn1_set_of {
     (
         set of $name:ident($combinator:expr): $combinator_type:ty;
+    ) => {
+        crate::x509::macros::asn1_set_of! { SetOf; $name($combinator): $combinator_type; }
+    };
+
+    (
+        #[unordered] set of $name:ident($combinator:expr): $combinator_type:ty;
+    ) => {
+        crate::x509::macros::asn1_set_of! { UnorderedSetOf; $name($combinator): $combinator_type; }
+    };
+
+    (
+        $set_of:ident; $name:ident($combinator:expr): $combinator_type:ty;
     ) => {
         ::paste::paste! {
             ::builtin_macros::verus! {
@@ -769,18 +815,18 @@ macro_rules! asn1_set_of {
                     use crate::common::*;
 
                     wrap_combinator! {
-                        pub struct $name: SequenceOf<$combinator_type> =>
+                        pub struct $name: $set_of<$combinator_type> =>
                             spec SpecValue,
                             exec<'a> Value<'a>,
                             owned ValueOwned,
-                        = SequenceOf($combinator);
+                        = $set_of($combinator);
                     }
 
                     asn1_tagged!($name, tag_of!(SET));
 
                     pub type SpecValue = Seq<<<$combinator_type as View>::V as SpecCombinator>::SpecResult>;
-                    pub type Value<'a> = VecDeep<<$combinator_type as Combinator>::Result<'a>>;
-                    pub type ValueOwned = VecDeep<<$combinator_type as Combinator>::Owned>;
+                    pub type Value<'a> = SetOfValue<<$combinator_type as Combinator>::Result<'a>>;
+                    pub type ValueOwned = SetOfValue<<$combinator_type as Combinator>::Owned>;
                 }
             }
         }
@@ -813,6 +859,11 @@ macro_rules! asn1 {
         crate::x509::macros::asn1_set_of! { set of $name($combinator): $combinator_type; }
         crate::x509::macros::asn1! { $($rest)* }
     };
+
+    (#[unordered] set of $name:ident($combinator:expr): $combinator_type:ty; $($rest:tt)*) => {
+        crate::x509::macros::asn1_set_of! { #[unordered] set of $name($combinator): $combinator_type; }
+        crate::x509::macros::asn1! { $($rest)* }
+    };
 }
 pub use asn1;
 
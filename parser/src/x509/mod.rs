@@ -13,8 +13,16 @@ mod extension;
 mod ext_value;
 mod tbs_cert;
 mod cert;
+mod crl;
+mod csr;
+mod attr_cert;
+mod attribute;
+mod ocsp;
 mod display;
 mod general_name;
+mod policy_qualifier;
+mod sct;
+mod qc_statement;
 mod oid;
 
 pub mod macros;
@@ -32,7 +40,15 @@ pub use extension::*;
 pub use ext_value::*;
 pub use tbs_cert::*;
 pub use cert::*;
+pub use crl::*;
+pub use csr::*;
+pub use attr_cert::*;
+pub use attribute::*;
+pub use ocsp::*;
 pub use macros::*;
 pub use display::*;
 pub use general_name::*;
+pub use policy_qualifier::*;
+pub use sct::*;
+pub use qc_statement::*;
 pub use oid::*;
@@ -0,0 +1,279 @@
+use vstd::prelude::*;
+
+use crate::asn1::*;
+use crate::asn1::Integer;
+
+use crate::common::*;
+use super::*;
+
+verus! {
+
+broadcast use super::oid::axiom_disjoint_oids;
+
+// RFC 6960, 4.2.1
+// OCSPResponse ::= SEQUENCE {
+//     responseStatus         OCSPResponseStatus,
+//     responseBytes          [0] EXPLICIT ResponseBytes OPTIONAL }
+//
+// OCSPResponseStatus ::= ENUMERATED {
+//     successful            (0),  -- Response has valid confirmations
+//     malformedRequest      (1),  -- Illegal confirmation request
+//     internalError         (2),  -- Internal error in issuer
+//     tryLater              (3),  -- Try again later
+//                                 -- (4) is not used
+//     sigRequired           (5),  -- Must sign the request
+//     unauthorized          (6)   -- Request unauthorized
+// }
+asn1! {
+    seq OCSPResponse {
+        status: ASN1<Enumerated> = ASN1(Enumerated),
+        #[optional] bytes: ASN1<ExplicitTag<ASN1<ResponseBytes>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(ResponseBytes))),
+    }
+}
+
+// ResponseBytes ::= SEQUENCE {
+//     responseType   OBJECT IDENTIFIER,
+//     response       OCTET STRING }
+//
+// where the content of `response` is determined by `responseType`
+pub type ResponseBytesInner = Mapped<
+    LengthWrapped<
+        Depend<
+            ASN1<ObjectIdentifier>,
+            <ResponseTypeCont as Continuation>::Output,
+            ResponseTypeCont,
+        >,
+    >,
+    ResponseBytesMapper>;
+
+wrap_combinator! {
+    pub struct ResponseBytes: ResponseBytesInner =>
+        spec SpecResponseBytesValue,
+        exec<'a> ResponseBytesValue<'a>,
+        owned ResponseBytesValueOwned,
+    = Mapped {
+            inner: LengthWrapped(Depend {
+                fst: ASN1(ObjectIdentifier),
+                snd: ResponseTypeCont,
+                spec_snd: Ghost(|i| ResponseTypeCont::spec_apply(i)),
+            }, Length),
+            mapper: ResponseBytesMapper,
+        };
+}
+
+asn1_tagged!(ResponseBytes, tag_of!(SEQUENCE));
+
+mapper! {
+    pub struct ResponseBytesMapper;
+
+    for <Typ, Response>
+    from ResponseBytesFrom where type ResponseBytesFrom<Typ, Response> = (Typ, Response);
+    to ResponseBytesPoly where pub struct ResponseBytesPoly<Typ, Response> {
+        pub typ: Typ,
+        pub response: Response,
+    }
+
+    spec SpecResponseBytesValue with <SpecObjectIdentifierValue, SpecResponseTypeValue>;
+    exec ResponseBytesValue<'a> with <ObjectIdentifierValue, ResponseTypeValue<'a>>;
+    owned ResponseBytesValueOwned with <ObjectIdentifierValueOwned, ResponseTypeValueOwned>;
+
+    forward(x) {
+        ResponseBytesPoly {
+            typ: x.0,
+            response: x.1,
+        }
+    }
+
+    backward(y) {
+        (y.typ, y.response)
+    }
+}
+
+oid_match_continuation! {
+    continuation ResponseType {
+        // id-pkix-ocsp-basic
+        oid(OCSP_BASIC) =>
+            Basic(ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(BasicOCSPResponse)))): ASN1<ExplicitTag<ASN1<BasicOCSPResponse>>>,
+
+        _ => Other(ASN1(OctetString)): ASN1<OctetString>,
+    }
+}
+
+// RFC 6960, 4.2.1
+// BasicOCSPResponse ::= SEQUENCE {
+//     tbsResponseData      ResponseData,
+//     signatureAlgorithm   AlgorithmIdentifier,
+//     signature            BIT STRING,
+//     certs            [0] EXPLICIT SEQUENCE OF Certificate OPTIONAL }
+//
+// ResponseData ::= SEQUENCE {
+//     version              [0] EXPLICIT Version DEFAULT v1,
+//     responderID              ResponderID,
+//     producedAt               GeneralizedTime,
+//     responses                SEQUENCE OF SingleResponse,
+//     responseExtensions   [1] EXPLICIT Extensions OPTIONAL }
+//
+// ResponderID ::= CHOICE {
+//     byName               [1] Name,
+//     byKey                [2] KeyHash }
+//
+// KeyHash ::= OCTET STRING -- SHA-1 hash of responder's public key
+//
+// SingleResponse ::= SEQUENCE {
+//     certID                       CertID,
+//     certStatus                   CertStatus,
+//     thisUpdate                   GeneralizedTime,
+//     nextUpdate         [0]       EXPLICIT GeneralizedTime OPTIONAL,
+//     singleExtensions   [1]       EXPLICIT Extensions OPTIONAL }
+//
+// CertStatus ::= CHOICE {
+//     good        [0]     IMPLICIT NULL,
+//     revoked     [1]     IMPLICIT RevokedInfo,
+//     unknown     [2]     IMPLICIT UnknownInfo }
+//
+// RevokedInfo ::= SEQUENCE {
+//     revocationTime              GeneralizedTime,
+//     revocationReason    [0]     EXPLICIT CRLReason OPTIONAL }
+//
+// UnknownInfo ::= NULL
+//
+// CertID ::= SEQUENCE {
+//     hashAlgorithm           AlgorithmIdentifier,
+//     issuerNameHash          OCTET STRING, -- Hash of issuer's DN
+//     issuerKeyHash           OCTET STRING, -- Hash of issuer's public key
+//     serialNumber            CertificateSerialNumber }
+asn1! {
+    seq BasicOCSPResponse {
+        tbs_response_data: Cached<ASN1<ResponseData>> = Cached(ASN1(ResponseData)),
+        sig_alg: ASN1<AlgorithmIdentifier> = ASN1(AlgorithmIdentifier),
+        sig: ASN1<BitString> = ASN1(BitString),
+        #[optional] certs: ASN1<ExplicitTag<ASN1<OCSPCertificates>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(OCSPCertificates))),
+    }
+
+    seq of OCSPCertificates(Certificate): Certificate;
+
+    seq ResponseData {
+        #[default(0i64)] version: ASN1<ExplicitTag<ASN1<Integer>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(Integer))),
+        // NOTE: the unwrapped CHOICE is used so that the disjointness
+        // from the DEFAULT version field can be proved
+        responder_id: ResponderIDUnwrapped = ResponderID::unwrapped(),
+        produced_at: ASN1<GeneralizedTime> = ASN1(GeneralizedTime),
+        responses: ASN1<SingleResponses> = ASN1(SingleResponses),
+        #[optional] response_extensions: ASN1<ExplicitTag<ASN1<Extensions>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 1), ASN1(Extensions))),
+    }
+
+    // NOTE: the OCSP ASN.1 module uses explicit tagging by default
+    choice ResponderID {
+        ByName(ASN1(ExplicitTag(tag_of!(EXPLICIT 1), ASN1(Name)))): ASN1<ExplicitTag<ASN1<Name>>>,
+        ByKey(ASN1(ExplicitTag(tag_of!(EXPLICIT 2), ASN1(OctetString)))): ASN1<ExplicitTag<ASN1<OctetString>>>,
+    }
+
+    seq of SingleResponses(ASN1(SingleResponse)): ASN1<SingleResponse>;
+
+    seq SingleResponse {
+        cert_id: ASN1<CertID> = ASN1(CertID),
+        cert_status: CertStatus = CertStatus,
+        this_update: ASN1<GeneralizedTime> = ASN1(GeneralizedTime),
+        #[optional] next_update: ASN1<ExplicitTag<ASN1<GeneralizedTime>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(GeneralizedTime))),
+        #[optional] single_extensions: ASN1<ExplicitTag<ASN1<Extensions>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 1), ASN1(Extensions))),
+    }
+
+    choice CertStatus {
+        Good(ASN1(ImplicitTag(tag_of!(IMPLICIT 0), Null))): ASN1<ImplicitTag<Null>>,
+        // NOTE: implicit tags on SEQUENCE types keep the constructed bit
+        Revoked(ASN1(ImplicitTag(tag_of!(EXPLICIT 1), RevokedInfo))): ASN1<ImplicitTag<RevokedInfo>>,
+        Unknown(ASN1(ImplicitTag(tag_of!(IMPLICIT 2), Null))): ASN1<ImplicitTag<Null>>,
+    }
+
+    seq RevokedInfo {
+        revocation_time: ASN1<GeneralizedTime> = ASN1(GeneralizedTime),
+        // CRLReason ::= ENUMERATED
+        #[optional] revocation_reason: ASN1<ExplicitTag<ASN1<Enumerated>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(Enumerated))),
+    }
+
+    seq CertID {
+        hash_alg: ASN1<AlgorithmIdentifier> = ASN1(AlgorithmIdentifier),
+        issuer_name_hash: ASN1<OctetString> = ASN1(OctetString),
+        issuer_key_hash: ASN1<OctetString> = ASN1(OctetString),
+        serial: ASN1<BigInt> = ASN1(BigInt),
+    }
+}
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    verus! {
+        /// Check that all trait bounds and preconditions are satisfied
+        #[test]
+        fn is_combinator() {
+            let _ = ASN1(OCSPResponse).parse(&[]);
+            let _ = ASN1(ResponseBytes).parse(&[]);
+            let _ = ASN1(BasicOCSPResponse).parse(&[]);
+            let _ = ASN1(SingleResponse).parse(&[]);
+            let _ = ASN1(CertID).parse(&[]);
+        }
+    }
+
+    /// A response generated by OpenSSL for a revoked
+    /// certificate (serial 0x1234, reason keyCompromise)
+    #[test]
+    fn openssl() {
+        let bytes = [
+            0x30, 0x82, 0x01, 0x14, 0x0A, 0x01, 0x00, 0xA0, 0x82, 0x01, 0x0D, 0x30, 0x82, 0x01, 0x09, 0x06, 0x09, 0x2B, 0x06, 0x01,
+            0x05, 0x05, 0x07, 0x30, 0x01, 0x01, 0x04, 0x81, 0xFB, 0x30, 0x81, 0xF8, 0x30, 0x81, 0x9F, 0xA1, 0x0F, 0x30, 0x0D, 0x31,
+            0x0B, 0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0C, 0x02, 0x43, 0x41, 0x18, 0x0F, 0x32, 0x30, 0x32, 0x36, 0x31, 0x30,
+            0x31, 0x38, 0x30, 0x39, 0x35, 0x32, 0x32, 0x37, 0x5A, 0x30, 0x7B, 0x30, 0x79, 0x30, 0x3B, 0x30, 0x09, 0x06, 0x05, 0x2B,
+            0x0E, 0x03, 0x02, 0x1A, 0x05, 0x00, 0x04, 0x14, 0x71, 0x1D, 0x39, 0x76, 0x71, 0xC9, 0xFA, 0xCF, 0x22, 0x42, 0x09, 0x59,
+            0x25, 0x97, 0x9A, 0x06, 0x98, 0xE4, 0x6B, 0x11, 0x04, 0x14, 0xBB, 0x82, 0x62, 0x2D, 0xCC, 0x81, 0x06, 0xD6, 0x75, 0x75,
+            0xB7, 0x42, 0x51, 0xC8, 0x12, 0xAB, 0xB5, 0x42, 0x15, 0x7C, 0x02, 0x02, 0x12, 0x34, 0xA1, 0x16, 0x18, 0x0F, 0x32, 0x30,
+            0x32, 0x34, 0x30, 0x31, 0x31, 0x35, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5A, 0xA0, 0x03, 0x0A, 0x01, 0x01, 0x18, 0x0F,
+            0x32, 0x30, 0x32, 0x36, 0x31, 0x30, 0x31, 0x38, 0x30, 0x39, 0x35, 0x32, 0x32, 0x37, 0x5A, 0xA0, 0x11, 0x18, 0x0F, 0x32,
+            0x30, 0x32, 0x36, 0x31, 0x30, 0x32, 0x35, 0x30, 0x39, 0x35, 0x32, 0x32, 0x37, 0x5A, 0x30, 0x0A, 0x06, 0x08, 0x2A, 0x86,
+            0x48, 0xCE, 0x3D, 0x04, 0x03, 0x02, 0x03, 0x48, 0x00, 0x30, 0x45, 0x02, 0x21, 0x00, 0xB9, 0xF1, 0x46, 0x6C, 0x06, 0x51,
+            0x32, 0xCD, 0x0B, 0xD8, 0x71, 0x23, 0xA7, 0xC8, 0xFA, 0x19, 0x43, 0x56, 0xF7, 0xC4, 0x71, 0x76, 0x84, 0xDB, 0xC5, 0xBE,
+            0xC6, 0x70, 0x24, 0x88, 0x74, 0x74, 0x02, 0x20, 0x4B, 0x6E, 0x60, 0xD5, 0x18, 0xA3, 0x83, 0xFC, 0xF8, 0x53, 0xBE, 0x5A,
+            0x9E, 0x4A, 0xB2, 0x61, 0xAB, 0x3C, 0xDD, 0xAE, 0x68, 0xAC, 0x44, 0x28, 0x17, 0x07, 0x8F, 0xCE, 0xD1, 0x8F, 0xC9, 0xCD,
+        ];
+
+        let (n, resp) = ASN1(OCSPResponse).parse(&bytes).unwrap();
+        assert_eq!(n, bytes.len());
+        assert_eq!(resp.status, 0);
+
+        let basic = match &resp.bytes {
+            OptionDeep::Some(bytes) => match &bytes.response {
+                ResponseTypeValue::Basic(basic) => basic,
+                _ => panic!("{:?}", bytes),
+            }
+            _ => panic!("{:?}", resp),
+        };
+
+        assert!(matches!(basic.certs, OptionDeep::None));
+
+        let data = basic.tbs_response_data.get();
+        assert_eq!(data.version, 0);
+        assert!(matches!(data.responder_id, ResponderIDValue::ByName(..)));
+        assert_eq!(data.responses.len(), 1);
+
+        let single = data.responses.get(0);
+        assert_eq!(single.cert_id.serial.bytes(), &[0x12, 0x34]);
+        assert!(matches!(single.next_update, OptionDeep::Some(..)));
+
+        match &single.cert_status {
+            CertStatusValue::Revoked(info) =>
+                assert_eq!(info.revocation_reason, OptionDeep::Some(1)),
+            _ => panic!("{:?}", single),
+        }
+    }
+
+    #[test]
+    fn cert_status() {
+        assert!(matches!(CertStatus.parse(&[ 0x80, 0x00 ]).unwrap().1, CertStatusValue::Good(..)));
+        assert!(matches!(CertStatus.parse(&[ 0x82, 0x00 ]).unwrap().1, CertStatusValue::Unknown(..)));
+
+        // Constructed [0] is not a valid encoding of good
+        assert!(CertStatus.parse(&[ 0xA0, 0x00 ]).is_err());
+    }
+}
@@ -6,23 +6,64 @@ pub use crate::asn1::UInt;
 pub use crate::asn1::ObjectIdentifierValue;
 pub use crate::common::VecDeep;
 
-verus! {
+/// Generate `oid_name!` (mapping OID names to their values)
+/// and the reverse lookup `oid_to_name` from a single list
+macro_rules! gen_oid_names {
+    ($(($id:ident) => { [$($arc:literal),+] };)*) => {
+        /// Map OID names to their values
+        #[allow(unused_macros)]
+        #[macro_export]
+        macro_rules! oid_name {
+            $(($id) => { [$($arc),+] };)*
+        }
+        pub use oid_name;
 
-/// Map OID names to their values
-/// NOTE: to add a new OID, add an entry here
-/// and also in `gen_oid_axioms` below (if disjointness is required)
-#[allow(unused_macros)]
-#[macro_export]
-macro_rules! oid_name {
+        /// Name of an OID in oid_name!, if any (mainly for debugging output)
+        pub fn oid_to_name(arcs: &[UInt]) -> Option<&'static str> {
+            $(
+                if arcs == &[$($arc),+][..] {
+                    return Some(stringify!($id));
+                }
+            )*
+            None
+        }
+    };
+}
+
+// NOTE: to add a new OID, add an entry here
+// and also in `gen_oid_axioms` below (if disjointness is required)
+gen_oid_names! {
     // Extension names
+    (SUBJECT_DIR_ATTRS)     => { [2, 5, 29, 9] };
     (SUBJECT_KEY_IDENT)     => { [2, 5, 29, 14] };
     (KEY_USAGE)             => { [2, 5, 29, 15] };
+    (PRIVATE_KEY_USAGE_PERIOD) => { [2, 5, 29, 16] };
     (SUBJECT_ALT_NAME)      => { [2, 5, 29, 17] };
+    (ISSUER_ALT_NAME)       => { [2, 5, 29, 18] };
     (BASIC_CONSTRAINTS)     => { [2, 5, 29, 19] };
     (NAME_CONSTRAINTS)      => { [2, 5, 29, 30] };
+    (CRL_DISTRIBUTION_POINTS) => { [2, 5, 29, 31] };
     (CERT_POLICIES)         => { [2, 5, 29, 32] };
+    (POLICY_MAPPINGS)       => { [2, 5, 29, 33] };
     (AUTH_KEY_IDENT)        => { [2, 5, 29, 35] };
+    (POLICY_CONSTRAINTS)    => { [2, 5, 29, 36] };
     (EXTENDED_KEY_USAGE)    => { [2, 5, 29, 37] };
+    (FRESHEST_CRL)          => { [2, 5, 29, 46] };
+    (INHIBIT_ANY_POLICY)    => { [2, 5, 29, 54] };
+    (AUTHORITY_INFO_ACCESS) => { [1, 3, 6, 1, 5, 5, 7, 1, 1] };
+    (SUBJECT_INFO_ACCESS)   => { [1, 3, 6, 1, 5, 5, 7, 1, 11] };
+    (CT_SCT_LIST)           => { [1, 3, 6, 1, 4, 1, 11129, 2, 4, 2] };
+    (QC_STATEMENTS)         => { [1, 3, 6, 1, 5, 5, 7, 1, 3] };
+    (TLS_FEATURE)           => { [1, 3, 6, 1, 5, 5, 7, 1, 24] };
+    (CT_POISON)             => { [1, 3, 6, 1, 4, 1, 11129, 2, 4, 3] };
+
+    // CRL and CRL entry extensions
+    (CRL_NUMBER)            => { [2, 5, 29, 20] };
+    (REASON_CODE)           => { [2, 5, 29, 21] };
+    (INVALIDITY_DATE)       => { [2, 5, 29, 24] };
+    (DELTA_CRL_INDICATOR)   => { [2, 5, 29, 27] };
+    (ISSUING_DIST_POINT)    => { [2, 5, 29, 28] };
+    (CERTIFICATE_ISSUER)    => { [2, 5, 29, 29] };
 
     // Signature algorithms
     (RSA_SIGNATURE_MD2)     => { [1, 2, 840, 113549, 1, 1, 2] };
@@ -32,6 +73,7 @@ macro_rules! oid_name {
     (RSA_SIGNATURE_SHA384)  => { [1, 2, 840, 113549, 1, 1, 12] };
     (RSA_SIGNATURE_SHA512)  => { [1, 2, 840, 113549, 1, 1, 13] };
     (RSA_SIGNATURE_SHA224)  => { [1, 2, 840, 113549, 1, 1, 14] };
+    (RSASSA_PSS)            => { [1, 2, 840, 113549, 1, 1, 10] };
     (DSA_SIGNATURE)         => { [1, 2, 840, 10040, 4, 1] };
 
     (ECDSA_SIGNATURE_SHA224) => { [1, 2, 840, 10045, 4, 3, 1] };
@@ -39,12 +81,27 @@ macro_rules! oid_name {
     (ECDSA_SIGNATURE_SHA384) => { [1, 2, 840, 10045, 4, 3, 3] };
     (ECDSA_SIGNATURE_SHA512) => { [1, 2, 840, 10045, 4, 3, 4] };
 
+    (ED25519)               => { [1, 3, 101, 112] };
+    (ED448)                 => { [1, 3, 101, 113] };
+
     (RSA_ENCRYPTION)        => { [1, 2, 840, 113549, 1, 1, 1] };
     (EC_PUBLIC_KEY)         => { [1, 2, 840, 10045, 2, 1] };
 
+    // Hash algorithms and mask generation functions
+    (SHA1)                  => { [1, 3, 14, 3, 2, 26] };
+    (SHA224)                => { [2, 16, 840, 1, 101, 3, 4, 2, 4] };
+    (SHA256)                => { [2, 16, 840, 1, 101, 3, 4, 2, 1] };
+    (SHA384)                => { [2, 16, 840, 1, 101, 3, 4, 2, 2] };
+    (SHA512)                => { [2, 16, 840, 1, 101, 3, 4, 2, 3] };
+    (MGF1)                  => { [1, 2, 840, 113549, 1, 1, 8] };
+
     // EC curves
     (EC_P_256)              => { [1, 2, 840, 10045, 3, 1, 7] };
     (EC_P_384)              => { [1, 3, 132, 0, 34] };
+    (EC_P_521)              => { [1, 3, 132, 0, 35] };
+    (EC_BRAINPOOL_P256R1)   => { [1, 3, 36, 3, 3, 2, 8, 1, 1, 7] };
+    (EC_BRAINPOOL_P384R1)   => { [1, 3, 36, 3, 3, 2, 8, 1, 1, 11] };
+    (EC_BRAINPOOL_P512R1)   => { [1, 3, 36, 3, 3, 2, 8, 1, 1, 13] };
 
     // Directory names
     (COMMON_NAME)           => { [2, 5, 4, 3] };
@@ -63,6 +120,33 @@ macro_rules! oid_name {
 
     (DOMAIN_COMPONENT)      => { [0, 9, 2342, 19200300, 100, 1, 25] };
 
+    // PKCS#9 attributes
+    (CHALLENGE_PASSWORD)    => { [1, 2, 840, 113549, 1, 9, 7] };
+    (EXTENSION_REQUEST)     => { [1, 2, 840, 113549, 1, 9, 14] };
+
+    // Qualified certificate statements (ETSI EN 319 412-5)
+    (QCS_COMPLIANCE)        => { [0, 4, 0, 1862, 1, 1] };
+    (QCS_RETENTION_PERIOD)  => { [0, 4, 0, 1862, 1, 3] };
+    (QCS_SSCD)              => { [0, 4, 0, 1862, 1, 4] };
+    (QCS_PDS)               => { [0, 4, 0, 1862, 1, 5] };
+    (QCS_TYPE)              => { [0, 4, 0, 1862, 1, 6] };
+    (QCT_ESIGN)             => { [0, 4, 0, 1862, 1, 6, 1] };
+    (QCT_ESEAL)             => { [0, 4, 0, 1862, 1, 6, 2] };
+    (QCT_WEB)               => { [0, 4, 0, 1862, 1, 6, 3] };
+    (QCS_PSD2)              => { [0, 4, 0, 19495, 2] };
+
+    // Attribute certificate attributes (RFC 5755, 4.4)
+    (AT_ROLE)               => { [2, 5, 4, 72] };
+    (AT_CLEARANCE)          => { [2, 5, 4, 55] };
+    (ACA_GROUP)             => { [1, 3, 6, 1, 5, 5, 7, 10, 4] };
+
+    // Personal data attributes (RFC 3739, 3.2.2)
+    (PDA_DATE_OF_BIRTH)     => { [1, 3, 6, 1, 5, 5, 7, 9, 1] };
+    (PDA_PLACE_OF_BIRTH)    => { [1, 3, 6, 1, 5, 5, 7, 9, 2] };
+    (PDA_GENDER)            => { [1, 3, 6, 1, 5, 5, 7, 9, 3] };
+    (PDA_COUNTRY_OF_CITIZENSHIP) => { [1, 3, 6, 1, 5, 5, 7, 9, 4] };
+    (PDA_COUNTRY_OF_RESIDENCE) => { [1, 3, 6, 1, 5, 5, 7, 9, 5] };
+
     // Extended key usage purposes
     (SERVER_AUTH)           => { [1, 3, 6, 1, 5, 5, 7, 3, 1] };
     (CLIENT_AUTH)           => { [1, 3, 6, 1, 5, 5, 7, 3, 2] };
@@ -70,8 +154,28 @@ macro_rules! oid_name {
     (EMAIL_PROTECTION)      => { [1, 3, 6, 1, 5, 5, 7, 3, 4] };
     (TIME_STAMPING)         => { [1, 3, 6, 1, 5, 5, 7, 3, 8] };
     (OCSP_SIGNING)          => { [1, 3, 6, 1, 5, 5, 7, 3, 9] };
+
+    // Other name forms
+    (MS_SAN_UPN)            => { [1, 3, 6, 1, 4, 1, 311, 20, 2, 3] };
+    (PERMANENT_IDENTIFIER)  => { [1, 3, 6, 1, 5, 5, 7, 8, 3] };
+    (HARDWARE_MODULE_NAME)  => { [1, 3, 6, 1, 5, 5, 7, 8, 4] };
+    (SMTP_UTF8_MAILBOX)     => { [1, 3, 6, 1, 5, 5, 7, 8, 9] };
+
+    // Policy qualifiers
+    (QT_CPS)                => { [1, 3, 6, 1, 5, 5, 7, 2, 1] };
+    (QT_UNOTICE)            => { [1, 3, 6, 1, 5, 5, 7, 2, 2] };
+
+    // Access methods in AIA/SIA
+    (AD_OCSP)               => { [1, 3, 6, 1, 5, 5, 7, 48, 1] };
+    (AD_CA_ISSUERS)         => { [1, 3, 6, 1, 5, 5, 7, 48, 2] };
+    (AD_TIME_STAMPING)      => { [1, 3, 6, 1, 5, 5, 7, 48, 3] };
+    (AD_CA_REPOSITORY)      => { [1, 3, 6, 1, 5, 5, 7, 48, 5] };
+
+    // OCSP response types
+    (OCSP_BASIC)            => { [1, 3, 6, 1, 5, 5, 7, 48, 1, 1] };
 }
-pub use oid_name;
+
+verus! {
 
 // Generate axioms for OID names
 macro_rules! gen_oid_axioms {
@@ -86,14 +190,34 @@ macro_rules! gen_oid_axioms {
 }
 
 gen_oid_axioms! {
+    SUBJECT_DIR_ATTRS
     SUBJECT_KEY_IDENT
     KEY_USAGE
+    PRIVATE_KEY_USAGE_PERIOD
     SUBJECT_ALT_NAME
+    ISSUER_ALT_NAME
     BASIC_CONSTRAINTS
     NAME_CONSTRAINTS
+    CRL_DISTRIBUTION_POINTS
     CERT_POLICIES
+    POLICY_MAPPINGS
     AUTH_KEY_IDENT
+    POLICY_CONSTRAINTS
     EXTENDED_KEY_USAGE
+    FRESHEST_CRL
+    INHIBIT_ANY_POLICY
+    AUTHORITY_INFO_ACCESS
+    SUBJECT_INFO_ACCESS
+    CT_SCT_LIST
+    QC_STATEMENTS
+    TLS_FEATURE
+    CT_POISON
+    CRL_NUMBER
+    REASON_CODE
+    INVALIDITY_DATE
+    DELTA_CRL_INDICATOR
+    ISSUING_DIST_POINT
+    CERTIFICATE_ISSUER
     RSA_SIGNATURE_MD2
     RSA_SIGNATURE_MD5
     RSA_SIGNATURE_SHA1
@@ -101,13 +225,45 @@ gen_oid_axioms! {
     RSA_SIGNATURE_SHA384
     RSA_SIGNATURE_SHA512
     RSA_SIGNATURE_SHA224
+    RSASSA_PSS
     DSA_SIGNATURE
     ECDSA_SIGNATURE_SHA224
     ECDSA_SIGNATURE_SHA256
     ECDSA_SIGNATURE_SHA384
     ECDSA_SIGNATURE_SHA512
+    ED25519
+    ED448
     RSA_ENCRYPTION
     EC_PUBLIC_KEY
+    SHA1
+    SHA224
+    SHA256
+    SHA384
+    SHA512
+    MGF1
+    MS_SAN_UPN
+    PERMANENT_IDENTIFIER
+    HARDWARE_MODULE_NAME
+    SMTP_UTF8_MAILBOX
+    QT_CPS
+    QT_UNOTICE
+    CHALLENGE_PASSWORD
+    EXTENSION_REQUEST
+    AT_ROLE
+    AT_CLEARANCE
+    ACA_GROUP
+    QCS_COMPLIANCE
+    QCS_RETENTION_PERIOD
+    QCS_SSCD
+    QCS_PDS
+    QCS_TYPE
+    QCS_PSD2
+    PDA_DATE_OF_BIRTH
+    PDA_PLACE_OF_BIRTH
+    PDA_GENDER
+    PDA_COUNTRY_OF_CITIZENSHIP
+    PDA_COUNTRY_OF_RESIDENCE
+    OCSP_BASIC
 }
 
 impl ObjectIdentifierValue {
@@ -0,0 +1,188 @@
+use vstd::prelude::*;
+
+use crate::asn1::*;
+use crate::asn1::Integer;
+use crate::common::*;
+
+use super::macros::*;
+
+verus! {
+
+broadcast use super::oid::axiom_disjoint_oids;
+
+// PolicyQualifierInfo ::= SEQUENCE {
+//     policyQualifierId  PolicyQualifierId,
+//     qualifier          ANY DEFINED BY policyQualifierId }
+//
+// PolicyQualifierId ::= OBJECT IDENTIFIER ( id-qt-cps | id-qt-unotice )
+pub type PolicyQualifierInfoInner = Mapped<
+    LengthWrapped<
+        Depend<
+            ASN1<ObjectIdentifier>,
+            <PolicyQualifierParamCont as Continuation>::Output,
+            PolicyQualifierParamCont,
+        >,
+    >,
+    PolicyQualifierInfoMapper>;
+
+wrap_combinator! {
+    pub struct PolicyQualifierInfo: PolicyQualifierInfoInner =>
+        spec SpecPolicyQualifierInfoValue,
+        exec<'a> PolicyQualifierInfoValue<'a>,
+        owned PolicyQualifierInfoValueOwned,
+    = Mapped {
+            inner: LengthWrapped(Depend {
+                fst: ASN1(ObjectIdentifier),
+                snd: PolicyQualifierParamCont,
+                spec_snd: Ghost(|i| PolicyQualifierParamCont::spec_apply(i)),
+            }, Length),
+            mapper: PolicyQualifierInfoMapper,
+        };
+}
+
+asn1_tagged!(PolicyQualifierInfo, tag_of!(SEQUENCE));
+
+mapper! {
+    pub struct PolicyQualifierInfoMapper;
+
+    for <Id, Param>
+    from PolicyQualifierInfoFrom where type PolicyQualifierInfoFrom<Id, Param> = (Id, Param);
+    to PolicyQualifierInfoPoly where pub struct PolicyQualifierInfoPoly<Id, Param> {
+        pub id: Id,
+        pub param: Param,
+    }
+
+    spec SpecPolicyQualifierInfoValue with <SpecObjectIdentifierValue, SpecPolicyQualifierParamValue>;
+    exec PolicyQualifierInfoValue<'a> with <ObjectIdentifierValue, PolicyQualifierParamValue<'a>>;
+    owned PolicyQualifierInfoValueOwned with <ObjectIdentifierValueOwned, PolicyQualifierParamValueOwned>;
+
+    forward(x) {
+        PolicyQualifierInfoPoly {
+            id: x.0,
+            param: x.1,
+        }
+    }
+
+    backward(y) {
+        (y.id, y.param)
+    }
+}
+
+oid_match_continuation! {
+    continuation PolicyQualifierParam {
+        // CPSuri ::= IA5String
+        oid(QT_CPS) => CPSUri(ASN1(IA5String)): ASN1<IA5String>,
+
+        oid(QT_UNOTICE) => UserNotice(ASN1(UserNotice)): ASN1<UserNotice>,
+
+        _ => Other(Tail): Tail,
+    }
+}
+
+asn1! {
+    // UserNotice ::= SEQUENCE {
+    //     noticeRef        NoticeReference OPTIONAL,
+    //     explicitText     DisplayText OPTIONAL }
+    seq UserNotice {
+        #[optional] notice_ref: ASN1<NoticeReference> = ASN1(NoticeReference),
+        // NOTE: the unwrapped CHOICE is used so that the disjointness
+        // from notice_ref can be proved
+        #[optional] explicit_text: DisplayTextUnwrapped = DisplayText::unwrapped(),
+    }
+
+    // NoticeReference ::= SEQUENCE {
+    //     organization     DisplayText,
+    //     noticeNumbers    SEQUENCE OF INTEGER }
+    seq NoticeReference {
+        organization: DisplayText = DisplayText,
+        notice_numbers: ASN1<NoticeNumbers> = ASN1(NoticeNumbers),
+    }
+
+    seq of NoticeNumbers(ASN1(Integer)): ASN1<Integer>;
+
+    // DisplayText ::= CHOICE {
+    //     ia5String        IA5String      (SIZE (1..200)),
+    //     visibleString    VisibleString  (SIZE (1..200)),
+    //     bmpString        BMPString      (SIZE (1..200)),
+    //     utf8String       UTF8String     (SIZE (1..200)) }
+    choice DisplayText {
+        IA5String(ASN1(IA5String)): ASN1<IA5String>,
+        VisibleString(ASN1(VisibleString)): ASN1<VisibleString>,
+        BMPString(ASN1(BMPString)): ASN1<BMPString>,
+        UTF8String(ASN1(UTF8String)): ASN1<UTF8String>,
+    }
+}
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    verus! {
+        /// Check that all trait bounds and preconditions are satisfied
+        #[test]
+        fn is_combinator() {
+            let _ = ASN1(PolicyQualifierInfo).parse(&[]);
+            let _ = ASN1(UserNotice).parse(&[]);
+            let _ = DisplayText.parse(&[]);
+        }
+    }
+
+    #[test]
+    fn cps_uri() {
+        let parsed = ASN1(PolicyQualifierInfo).parse(&[
+            0x30, 0x16, 0x06, 0x08, 0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x02, 0x01,
+            0x16, 0x0A, 0x68, 0x74, 0x74, 0x70, 0x3A, 0x2F, 0x2F, 0x78, 0x2E, 0x79,
+        ]).unwrap().1;
+
+        match parsed.param {
+            PolicyQualifierParamValue::CPSUri(uri) => assert_eq!(uri, "http://x.y"),
+            _ => panic!("{:?}", parsed),
+        }
+    }
+
+    #[test]
+    fn user_notice() {
+        let parsed = ASN1(PolicyQualifierInfo).parse(&[
+            0x30, 0x1C, 0x06, 0x08, 0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x02, 0x02,
+            0x30, 0x10,
+                // noticeRef
+                0x30, 0x09, 0x0C, 0x02, 0x61, 0x62, 0x30, 0x03, 0x02, 0x01, 0x01,
+                // explicitText
+                0x0C, 0x03, 0x61, 0x62, 0x63,
+        ]).unwrap().1;
+
+        match parsed.param {
+            PolicyQualifierParamValue::UserNotice(notice) => {
+                match notice.notice_ref {
+                    OptionDeep::Some(notice_ref) => {
+                        assert_eq!(notice_ref.organization, DisplayTextValue::UTF8String("ab"));
+                        assert_eq!(notice_ref.notice_numbers.len(), 1);
+                    }
+                    OptionDeep::None => panic!("missing noticeRef"),
+                }
+                assert_eq!(notice.explicit_text, OptionDeep::Some(DisplayTextValue::UTF8String("abc")));
+            }
+            _ => panic!("{:?}", parsed),
+        }
+    }
+
+    #[test]
+    fn user_notice_text_only() {
+        let parsed = ASN1(UserNotice).parse(&[
+            0x30, 0x05, 0x16, 0x03, 0x61, 0x62, 0x63,
+        ]).unwrap().1;
+
+        assert!(matches!(parsed.notice_ref, OptionDeep::None));
+        assert_eq!(parsed.explicit_text, OptionDeep::Some(DisplayTextValue::IA5String("abc")));
+    }
+
+    #[test]
+    fn visible_string() {
+        assert_eq!(DisplayText.parse(&[ 0x1A, 0x03, 0x61, 0x62, 0x63 ]).unwrap().1, DisplayTextValue::VisibleString("abc"));
+
+        // Control characters are not allowed in VisibleString
+        assert!(DisplayText.parse(&[ 0x1A, 0x01, 0x0A ]).is_err());
+    }
+}
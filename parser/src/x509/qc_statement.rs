@@ -0,0 +1,202 @@
+use vstd::prelude::*;
+
+use crate::asn1::*;
+use crate::asn1::Integer;
+use crate::common::*;
+
+use super::macros::*;
+
+verus! {
+
+broadcast use super::oid::axiom_disjoint_oids;
+
+// RFC 3739, 3.2.6
+// QCStatements ::= SEQUENCE OF QCStatement
+//
+// QCStatement ::= SEQUENCE {
+//     statementId   QC-STATEMENT.&Id({SupportedStatements}),
+//     statementInfo QC-STATEMENT.&Type
+//                   ({SupportedStatements}{@statementId}) OPTIONAL }
+pub type QCStatementInner = Mapped<
+    LengthWrapped<
+        Depend<
+            ASN1<ObjectIdentifier>,
+            <QCStatementParamCont as Continuation>::Output,
+            QCStatementParamCont,
+        >,
+    >,
+    QCStatementMapper>;
+
+wrap_combinator! {
+    pub struct QCStatement: QCStatementInner =>
+        spec SpecQCStatementValue,
+        exec<'a> QCStatementValue<'a>,
+        owned QCStatementValueOwned,
+    = Mapped {
+            inner: LengthWrapped(Depend {
+                fst: ASN1(ObjectIdentifier),
+                snd: QCStatementParamCont,
+                spec_snd: Ghost(|i| QCStatementParamCont::spec_apply(i)),
+            }, Length),
+            mapper: QCStatementMapper,
+        };
+}
+
+asn1_tagged!(QCStatement, tag_of!(SEQUENCE));
+
+mapper! {
+    pub struct QCStatementMapper;
+
+    for <Id, Param>
+    from QCStatementFrom where type QCStatementFrom<Id, Param> = (Id, Param);
+    to QCStatementPoly where pub struct QCStatementPoly<Id, Param> {
+        pub id: Id,
+        pub param: Param,
+    }
+
+    spec SpecQCStatementValue with <SpecObjectIdentifierValue, SpecQCStatementParamValue>;
+    exec QCStatementValue<'a> with <ObjectIdentifierValue, QCStatementParamValue<'a>>;
+    owned QCStatementValueOwned with <ObjectIdentifierValueOwned, QCStatementParamValueOwned>;
+
+    forward(x) {
+        QCStatementPoly {
+            id: x.0,
+            param: x.1,
+        }
+    }
+
+    backward(y) {
+        (y.id, y.param)
+    }
+}
+
+// Statements defined in ETSI EN 319 412-5, 4.2 and ETSI TS 119 495, 5.1
+oid_match_continuation! {
+    continuation QCStatementParam {
+        // esi4-qcStatement-1 (no statementInfo)
+        oid(QCS_COMPLIANCE) => Compliance(End): End,
+
+        // QcEuRetentionPeriod ::= INTEGER
+        oid(QCS_RETENTION_PERIOD) => RetentionPeriod(ASN1(Integer)): ASN1<Integer>,
+
+        // esi4-qcStatement-4 (no statementInfo)
+        oid(QCS_SSCD) => SSCD(End): End,
+
+        oid(QCS_PDS) => PDS(ASN1(QcPDS)): ASN1<QcPDS>,
+
+        oid(QCS_TYPE) => QcType(ASN1(QcTypes)): ASN1<QcTypes>,
+
+        oid(QCS_PSD2) => PSD2(ASN1(PSD2QcType)): ASN1<PSD2QcType>,
+
+        _ => Other(Tail): Tail,
+    }
+}
+
+asn1! {
+    seq of QCStatements(ASN1(QCStatement)): ASN1<QCStatement>;
+
+    // QcEuPDS ::= PdsLocations
+    // PdsLocations ::= SEQUENCE SIZE (1..MAX) OF PdsLocation
+    seq of QcPDS(ASN1(PdsLocation)): ASN1<PdsLocation>;
+
+    // PdsLocation ::= SEQUENCE {
+    //     url       IA5String,
+    //     language  PrintableString (SIZE(2)) }
+    seq PdsLocation {
+        url: ASN1<IA5String> = ASN1(IA5String),
+        language: ASN1<PrintableString> = ASN1(PrintableString),
+    }
+
+    // QcType ::= SEQUENCE OF OBJECT IDENTIFIER
+    seq of QcTypes(ASN1(ObjectIdentifier)): ASN1<ObjectIdentifier>;
+
+    // PSD2QcType ::= SEQUENCE {
+    //     rolesOfPSP  RolesOfPSP,
+    //     nCAName     NCAName,
+    //     nCAId       NCAId }
+    //
+    // NCAName ::= UTF8String (SIZE (1..256))
+    // NCAId ::= UTF8String (SIZE (1..256))
+    seq PSD2QcType {
+        roles: ASN1<RolesOfPSP> = ASN1(RolesOfPSP),
+        nca_name: ASN1<UTF8String> = ASN1(UTF8String),
+        nca_id: ASN1<UTF8String> = ASN1(UTF8String),
+    }
+
+    // RolesOfPSP ::= SEQUENCE OF RoleOfPSP
+    seq of RolesOfPSP(ASN1(RoleOfPSP)): ASN1<RoleOfPSP>;
+
+    // RoleOfPSP ::= SEQUENCE {
+    //     roleOfPspOid   RoleOfPspOid,
+    //     roleOfPspName  RoleOfPspName }
+    //
+    // RoleOfPspOid ::= OBJECT IDENTIFIER
+    // RoleOfPspName ::= UTF8String (SIZE(256))
+    seq RoleOfPSP {
+        id: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+        name: ASN1<UTF8String> = ASN1(UTF8String),
+    }
+}
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::x509::*;
+
+    verus! {
+        /// Check that all trait bounds and preconditions are satisfied
+        #[test]
+        fn is_combinator() {
+            let _ = ASN1(QCStatement).parse(&[]);
+            let _ = ASN1(QCStatements).parse(&[]);
+        }
+    }
+
+    /// QcCompliance, QcType (web), QcPDS, PSD2 and QcLimitValue (unsupported)
+    #[test]
+    fn eidas() {
+        let (_, stmts) = ASN1(QCStatements).parse(&[
+            0x30, 0x81, 0x89, 0x30, 0x08, 0x06, 0x06, 0x04, 0x00, 0x8E, 0x46, 0x01, 0x01, 0x30, 0x13, 0x06, 0x06, 0x04, 0x00, 0x8E,
+            0x46, 0x01, 0x06, 0x30, 0x09, 0x06, 0x07, 0x04, 0x00, 0x8E, 0x46, 0x01, 0x06, 0x03, 0x30, 0x21, 0x06, 0x06, 0x04, 0x00,
+            0x8E, 0x46, 0x01, 0x05, 0x30, 0x17, 0x30, 0x15, 0x16, 0x0F, 0x68, 0x74, 0x74, 0x70, 0x73, 0x3A, 0x2F, 0x2F, 0x78, 0x2E,
+            0x79, 0x2F, 0x70, 0x64, 0x73, 0x13, 0x02, 0x65, 0x6E, 0x30, 0x2E, 0x06, 0x06, 0x04, 0x00, 0x81, 0x98, 0x27, 0x02, 0x30,
+            0x24, 0x30, 0x11, 0x06, 0x07, 0x04, 0x00, 0x81, 0x98, 0x27, 0x01, 0x03, 0x0C, 0x06, 0x50, 0x53, 0x50, 0x5F, 0x41, 0x49,
+            0x0C, 0x05, 0x42, 0x61, 0x46, 0x69, 0x6E, 0x0C, 0x08, 0x44, 0x45, 0x2D, 0x42, 0x41, 0x46, 0x49, 0x4E, 0x30, 0x15, 0x06,
+            0x06, 0x04, 0x00, 0x8E, 0x46, 0x01, 0x02, 0x30, 0x0B, 0x13, 0x03, 0x45, 0x55, 0x52, 0x02, 0x01, 0x01, 0x02, 0x01, 0x03,
+        ]).unwrap();
+
+        assert_eq!(stmts.len(), 5);
+        assert!(matches!(stmts.get(0).param, QCStatementParamValue::Compliance(..)));
+
+        match &stmts.get(1).param {
+            QCStatementParamValue::QcType(types) => {
+                assert_eq!(types.len(), 1);
+                assert!(types.get(0).polyfill_eq(&oid!(QCT_WEB)));
+            }
+            _ => panic!("{:?}", stmts),
+        }
+
+        match &stmts.get(2).param {
+            QCStatementParamValue::PDS(locs) => {
+                assert_eq!(locs.len(), 1);
+                assert_eq!(locs.get(0).url, "https://x.y/pds");
+                assert_eq!(locs.get(0).language, "en");
+            }
+            _ => panic!("{:?}", stmts),
+        }
+
+        match &stmts.get(3).param {
+            QCStatementParamValue::PSD2(psd2) => {
+                assert_eq!(psd2.roles.len(), 1);
+                assert_eq!(psd2.roles.get(0).name, "PSP_AI");
+                assert_eq!(psd2.nca_name, "BaFin");
+                assert_eq!(psd2.nca_id, "DE-BAFIN");
+            }
+            _ => panic!("{:?}", stmts),
+        }
+
+        assert!(matches!(stmts.get(4).param, QCStatementParamValue::Other(..)));
+    }
+}
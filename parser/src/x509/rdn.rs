@@ -9,7 +9,6 @@ use super::macros::asn1;
 verus! {
 
 // In X.509: RelativeDistinguishedName ::= SET OF AttributeTypeAndValue
-// TODO: support SET OF instead of using a sequence
 asn1! {
     set of RDN(ASN1(AttributeTypeAndValue)): ASN1<AttributeTypeAndValue>;
 }
@@ -34,4 +33,23 @@ mod test {
             0x31, 0x0B, 0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x06, 0x13, 0x02, 0x50, 0x41,
         ]).is_ok());
     }
+
+    #[test]
+    fn multi_valued() {
+        // CN=a + C=PA, sorted by encoding
+        let parsed = ASN1(RDN).parse(&[
+            0x31, 0x16,
+                0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0C, 0x02, 0x61, 0x61,
+                0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x06, 0x13, 0x02, 0x50, 0x41,
+        ]).unwrap().1;
+        assert_eq!(parsed.len(), 2);
+
+        // Same set of attributes in the wrong order. NOTE: this used to be
+        // accepted when RDN was parsed as a SEQUENCE OF
+        assert!(ASN1(RDN).parse(&[
+            0x31, 0x16,
+                0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x06, 0x13, 0x02, 0x50, 0x41,
+                0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0C, 0x02, 0x61, 0x61,
+        ]).is_err());
+    }
 }
@@ -0,0 +1,177 @@
+use vstd::prelude::*;
+
+use crate::asn1::*;
+use crate::common::*;
+use crate::tls::*;
+
+use super::macros::*;
+
+verus! {
+
+// Embedded SCTs (RFC 6962, Section 3.3), in the TLS presentation language
+//
+// opaque SerializedSCT<1..2^16-1>;
+//
+// struct {
+//     SerializedSCT sct_list <1..2^16-1>;
+// } SignedCertificateTimestampList;
+//
+// Both bounds are non-empty: sct_list is checked by NonEmptySCTList, and
+// each SerializedSCT is at least as long as the fixed-size fields of
+// SignedCertificateTimestamp
+wrap_combinator! {
+    pub struct SignedCertTimestampList: TLSVec16<Refined<Repeat<TLSVec16<SignedCertTimestamp>>, NonEmptySCTList>> =
+        LengthWrapped(Refined {
+            inner: Repeat(LengthWrapped(SignedCertTimestamp, TLSLength16)),
+            predicate: NonEmptySCTList,
+        }, TLSLength16);
+}
+
+/// Rejects an empty sct_list, which has the bound <1..2^16-1>
+#[derive(View)]
+pub struct NonEmptySCTList;
+
+impl SpecPred for NonEmptySCTList {
+    type Input = Seq<SpecSignedCertTimestampValue>;
+
+    open spec fn spec_apply(&self, s: &Self::Input) -> bool {
+        s.len() > 0
+    }
+}
+
+impl Pred for NonEmptySCTList {
+    type Input<'a> = VecDeep<SignedCertTimestampValue<'a>>;
+    type InputOwned = VecDeep<SignedCertTimestampValueOwned>;
+
+    fn apply(&self, s: &Self::Input<'_>) -> (res: bool) {
+        s.len() > 0
+    }
+}
+
+// struct {
+//     Version sct_version;                // v1(0)
+//     LogID id;                           // opaque key_id[32]
+//     uint64 timestamp;
+//     CtExtensions extensions;            // opaque CtExtensions<0..2^16-1>
+//     digitally-signed struct {
+//         ...
+//     };
+// } SignedCertificateTimestamp;
+//
+// where digitally-signed is (RFC 5246, Section 4.7)
+//
+// struct {
+//     SignatureAndHashAlgorithm algorithm; // uint8 hash, uint8 signature
+//     opaque signature<0..2^16-1>;
+// } DigitallySigned;
+pub type SignedCertTimestampInner = Mapped<
+    Pair<U8, Pair<Bytes, Pair<VarUInt, Pair<TLSVec16<Tail>, Pair<U8, Pair<U8, TLSVec16<Tail>>>>>>>,
+    SignedCertTimestampMapper>;
+
+wrap_combinator! {
+    pub struct SignedCertTimestamp: SignedCertTimestampInner =>
+        spec SpecSignedCertTimestampValue,
+        exec<'a> SignedCertTimestampValue<'a>,
+        owned SignedCertTimestampValueOwned,
+    = Mapped {
+            inner: Pair(U8, Pair(Bytes(32), Pair(VarUInt(8), Pair(LengthWrapped(Tail, TLSLength16), Pair(U8, Pair(U8, LengthWrapped(Tail, TLSLength16))))))),
+            mapper: SignedCertTimestampMapper,
+        };
+}
+
+mapper! {
+    pub struct SignedCertTimestampMapper;
+
+    for <Version, LogId, Timestamp, Extensions, HashAlg, SigAlg, Signature>
+    from SignedCertTimestampFrom where
+        type SignedCertTimestampFrom<Version, LogId, Timestamp, Extensions, HashAlg, SigAlg, Signature> =
+            PairValue<Version, PairValue<LogId, PairValue<Timestamp, PairValue<Extensions, PairValue<HashAlg, PairValue<SigAlg, Signature>>>>>>;
+    to SignedCertTimestampPoly where pub struct SignedCertTimestampPoly<Version, LogId, Timestamp, Extensions, HashAlg, SigAlg, Signature> {
+        pub version: Version,
+        pub log_id: LogId,
+        pub timestamp: Timestamp,
+        pub extensions: Extensions,
+        pub hash_alg: HashAlg,
+        pub sig_alg: SigAlg,
+        pub signature: Signature,
+    }
+
+    spec SpecSignedCertTimestampValue with <u8, Seq<u8>, VarUIntResult, Seq<u8>, u8, u8, Seq<u8>>;
+    exec SignedCertTimestampValue<'a> with <u8, &'a [u8], VarUIntResult, &'a [u8], u8, u8, &'a [u8]>;
+    owned SignedCertTimestampValueOwned with <u8, Vec<u8>, VarUIntResult, Vec<u8>, u8, u8, Vec<u8>>;
+
+    forward(x) {
+        SignedCertTimestampPoly {
+            version: x.0,
+            log_id: x.1.0,
+            timestamp: x.1.1.0,
+            extensions: x.1.1.1.0,
+            hash_alg: x.1.1.1.1.0,
+            sig_alg: x.1.1.1.1.1.0,
+            signature: x.1.1.1.1.1.1,
+        }
+    }
+
+    backward(y) {
+        PairValue(y.version, PairValue(y.log_id, PairValue(y.timestamp, PairValue(y.extensions,
+            PairValue(y.hash_alg, PairValue(y.sig_alg, y.signature))))))
+    }
+}
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    verus! {
+        /// Check that all trait bounds and preconditions are satisfied
+        #[test]
+        fn is_combinator() {
+            let _ = SignedCertTimestampList.parse(&[]);
+            let _ = SignedCertTimestamp.parse(&[]);
+        }
+    }
+
+    #[test]
+    fn sct_list() {
+        let mut sct = vec![0x00];
+        sct.extend([0xAA; 32]);
+        sct.extend([0x00, 0x00, 0x01, 0x8B, 0x00, 0x00, 0x00, 0x01]);
+        sct.extend([0x00, 0x00, 0x04, 0x03, 0x00, 0x02, 0xAB, 0xCD]);
+        assert_eq!(sct.len(), 0x31);
+
+        let mut list = vec![0x00, 0x33, 0x00, 0x31];
+        list.extend(sct);
+
+        let (len, parsed) = SignedCertTimestampList.parse(&list).unwrap();
+        assert_eq!(len, list.len());
+        assert_eq!(parsed.len(), 1);
+
+        let sct = parsed.get(0);
+        assert_eq!(sct.version, 0);
+        assert_eq!(sct.log_id, &[0xAA; 32]);
+        assert_eq!(sct.timestamp, 0x0000018B00000001);
+        assert_eq!(sct.extensions.len(), 0);
+        assert_eq!(sct.hash_alg, 4);
+        assert_eq!(sct.sig_alg, 3);
+        assert_eq!(sct.signature, &[0xAB, 0xCD]);
+    }
+
+    #[test]
+    fn sct_list_bad_length() {
+        // The inner SerializedSCT length exceeds the list length
+        assert!(SignedCertTimestampList.parse(&[
+            0x00, 0x03, 0x00, 0x31, 0x00,
+        ]).is_err());
+    }
+
+    #[test]
+    fn sct_list_empty() {
+        // sct_list has the bound <1..2^16-1>
+        assert!(SignedCertTimestampList.parse(&[ 0x00, 0x00 ]).is_err());
+
+        // Empty SerializedSCT
+        assert!(SignedCertTimestampList.parse(&[ 0x00, 0x02, 0x00, 0x00 ]).is_err());
+    }
+}
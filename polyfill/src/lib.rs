@@ -195,6 +195,44 @@ pub fn str_to_utf8(s: &str) -> (res: &[u8])
     s.as_bytes()
 }
 
+/// Unicode scalar values, i.e. code points excluding surrogates
+pub open spec fn spec_is_unicode_scalar(v: u32) -> bool {
+    v < 0xD800 || (0xE000 <= v && v <= 0x10FFFF)
+}
+
+/// Verus does not support casting u32 to char,
+/// so we axiomatize the conversion (on scalar values)
+/// and its relation to `c as u32`
+pub closed spec fn spec_char_from_u32(v: u32) -> char;
+
+#[verifier::external_body]
+pub proof fn lemma_char_from_u32(v: u32)
+    ensures spec_is_unicode_scalar(v) ==> spec_char_from_u32(v) as u32 == v
+{}
+
+#[verifier::external_body]
+pub proof fn lemma_char_to_u32(c: char)
+    ensures
+        spec_is_unicode_scalar(c as u32),
+        spec_char_from_u32(c as u32) == c,
+{}
+
+#[verifier::external_body]
+pub fn char_from_u32(v: u32) -> (res: Option<char>)
+    ensures
+        res is Some <==> spec_is_unicode_scalar(v),
+        res matches Some(c) ==> c == spec_char_from_u32(v),
+{
+    char::from_u32(v)
+}
+
+#[verifier::external_body]
+pub fn chars_to_string(chars: &[char]) -> (res: String)
+    ensures res@ == chars@
+{
+    chars.iter().collect()
+}
+
 pub closed spec fn spec_u64_to_string(x: u64) -> (res: Seq<char>);
 
 /// TODO: specify this
@@ -365,6 +403,13 @@ pub fn slice_take<V>(s: &[V], n: usize) -> (res: &[V])
     &s[..n]
 }
 
+#[verifier::external_body]
+pub fn vec_extend_from_slice<T: Copy>(v: &mut Vec<T>, s: &[T])
+    ensures v@ == old(v)@ + s@
+{
+    v.extend_from_slice(s)
+}
+
 #[verifier::external_type_specification]
 #[verifier::external_body]
 pub struct ExtTryFromIntError(TryFromIntError);
@@ -1,7 +1,7 @@
 mod error;
 
 use base64::{Engine, prelude::BASE64_STANDARD};
-use std::io;
+use std::io::{self, Read};
 use std::process::ExitCode;
 use std::fs::File;
 use std::collections::HashMap;
@@ -16,7 +16,8 @@ use clap::{command, Parser, Subcommand};
 use regex::Regex;
 
 use chain::utils::*;
-use parser::{x509, VecDeep};
+use parser::{x509, VecDeep, Combinator};
+use parser::asn1::{ber_to_der, BERReport, TLVNode, TagClass, TagForm, ASN1, ObjectIdentifier};
 use vpl::Backend;
 use error::*;
 
@@ -40,6 +41,9 @@ enum Action {
 
     /// Compare the results of two CT logs
     DiffResults(DiffResultsArgs),
+
+    /// Dump the DER structure of a file (PEM or raw DER), similar to dumpasn1
+    Dump(DumpArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -56,6 +60,12 @@ struct ParseCTLogArgs {
 
     #[clap(short = 'e', long, default_value_t = false)]
     ignore_parse_errors: bool,
+
+    /// Retry certificates that are not valid DER with the
+    /// lenient BER profile (see parser::asn1::spec_ber_to_der),
+    /// and report the relaxations used
+    #[clap(long, default_value_t = false)]
+    ber: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -92,6 +102,10 @@ struct ValidateCTLogArgs {
     #[clap(short = 't', long)]
     override_time: Option<i64>,
 
+    /// Accept legacy SHA-1 RSA signatures when checking issuers
+    #[arg(long, default_value_t = false)]
+    allow_sha1: bool,
+
     #[clap(short = 'j', long = "jobs", default_value = "1")]
     num_jobs: usize,
 }
@@ -172,6 +186,10 @@ fn parse_cert_ct_logs(args: ParseCTLogArgs) -> Result<(), Error>
 
     let mut num_parsed_total = 0;
 
+    // Certificates only accepted under the BER profile
+    let mut num_ber_total = 0;
+    let mut ber_report = BERReport::default();
+
     for path in args.csv_files {
         let mut num_parsed = 0;
 
@@ -183,7 +201,20 @@ fn parse_cert_ct_logs(args: ParseCTLogArgs) -> Result<(), Error>
         for result in reader.deserialize() {
             let result: CTLogEntry = result?;
 
-            let cert_bytes = BASE64_STANDARD.decode(result.cert_base64)?;
+            let mut cert_bytes = BASE64_STANDARD.decode(result.cert_base64)?;
+
+            if args.ber && parse_x509_certificate(&cert_bytes).is_err() {
+                if let Ok((der_bytes, report)) = ber_to_der(&cert_bytes) {
+                    if !report.is_der() {
+                        let used = report.used().iter().map(|r| r.to_string()).collect::<Vec<_>>();
+                        eprintln!("certificate {} is not DER: {}", result.hash, used.join(", "));
+
+                        num_ber_total += 1;
+                        ber_report.merge(&report);
+                    }
+                    cert_bytes = der_bytes;
+                }
+            }
 
             match parse_x509_certificate(&cert_bytes) {
                 Ok(cert) => {
@@ -213,6 +244,10 @@ fn parse_cert_ct_logs(args: ParseCTLogArgs) -> Result<(), Error>
 
     eprintln!("parsed {} certificate(s) in total", num_parsed_total);
 
+    if args.ber {
+        eprintln!("{} certificate(s) required BER relaxations: {:?}", num_ber_total, ber_report);
+    }
+
     Ok(())
 }
 
@@ -250,6 +285,7 @@ fn validate_ct_logs_job<C: vpl::Compiled>(
         chain: &chain,
         domain: &entry.domain.to_lowercase(),
         now: timestamp,
+        allow_sha1: args.allow_sha1,
     };
 
     if args.debug {
@@ -475,12 +511,136 @@ fn diff_ct_log_results(args: DiffResultsArgs) -> Result<(), Error>
     Ok(())
 }
 
+#[derive(Parser, Debug)]
+struct DumpArgs {
+    /// Input file; if omitted, we read from stdin
+    path: Option<String>,
+}
+
+/// Name of a tag in the style of dumpasn1
+fn tag_name(node: &TLVNode) -> String {
+    let num = node.tag.num;
+
+    match node.tag.class {
+        TagClass::Universal => match num {
+            0x01 => "BOOLEAN",
+            0x02 => "INTEGER",
+            0x03 => "BIT STRING",
+            0x04 => "OCTET STRING",
+            0x05 => "NULL",
+            0x06 => "OBJECT IDENTIFIER",
+            0x09 => "REAL",
+            0x0a => "ENUMERATED",
+            0x0c => "UTF8String",
+            0x10 => "SEQUENCE",
+            0x11 => "SET",
+            0x12 => "NumericString",
+            0x13 => "PrintableString",
+            0x14 => "TeletexString",
+            0x16 => "IA5String",
+            0x17 => "UTCTime",
+            0x18 => "GeneralizedTime",
+            0x1a => "VisibleString",
+            0x1b => "GeneralString",
+            0x1c => "UniversalString",
+            0x1e => "BMPString",
+            _ => return format!("[UNIVERSAL {}]", num),
+        }.to_string(),
+        TagClass::Application => format!("[APPLICATION {}]", num),
+        TagClass::ContextSpecific => format!("[{}]", num),
+        TagClass::Private => format!("[PRIVATE {}]", num),
+    }
+}
+
+/// Short description of the content of a primitive value
+fn tag_summary(node: &TLVNode) -> String {
+    const MAX_HEX_BYTES: usize = 16;
+
+    if !matches!(node.tag.class, TagClass::Universal) {
+        return hex_summary(node.content, MAX_HEX_BYTES);
+    }
+
+    match node.tag.num {
+        0x01 => (if node.content == [0x00] { "FALSE" } else { "TRUE" }).to_string(),
+        0x06 => match ASN1(ObjectIdentifier).parse(node.raw) {
+            Ok((_, oid)) => {
+                let arcs = oid.0.to_vec();
+                let dotted = arcs.iter().map(|arc| arc.to_string()).collect::<Vec<_>>().join(".");
+
+                match x509::oid_to_name(arcs) {
+                    Some(name) => format!("{} ({})", dotted, name),
+                    None => dotted,
+                }
+            }
+            Err(..) => format!("<invalid> {}", hex_summary(node.content, MAX_HEX_BYTES)),
+        },
+        0x0c | 0x12 | 0x13 | 0x14 | 0x16 | 0x17 | 0x18 | 0x1a | 0x1b =>
+            format!("{:?}", String::from_utf8_lossy(node.content)),
+        0x05 => String::new(),
+        _ => hex_summary(node.content, MAX_HEX_BYTES),
+    }
+}
+
+fn hex_summary(bytes: &[u8], max_len: usize) -> String {
+    let hex = bytes.iter().take(max_len).map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ");
+
+    if bytes.len() > max_len {
+        format!("{} ...", hex)
+    } else {
+        hex
+    }
+}
+
+/// Print a tree built by TLVNode::parse (whose structure is
+/// verified, see TLVNode::wf); this only formats the nodes
+fn print_tlv_tree(node: &TLVNode, depth: usize) {
+    let indent = "  ".repeat(depth);
+
+    if matches!(node.tag.form, TagForm::Constructed) {
+        println!("{:>5} {:>4}: {}{} {{", node.offset, node.content.len(), indent, tag_name(node));
+    } else if node.encapsulated {
+        println!("{:>5} {:>4}: {}{}, encapsulates {{", node.offset, node.content.len(), indent, tag_name(node));
+    } else {
+        println!("{:>5} {:>4}: {}{} {}", node.offset, node.content.len(), indent, tag_name(node), tag_summary(node));
+        return;
+    }
+
+    for child in &node.children {
+        print_tlv_tree(child, depth + 1);
+    }
+
+    println!("           {}}}", indent);
+}
+
+fn dump_asn1(args: DumpArgs) -> Result<(), Error> {
+    let mut bytes = vec![];
+
+    match &args.path {
+        Some(path) => { File::open(path)?.read_to_end(&mut bytes)?; }
+        None => { io::stdin().lock().read_to_end(&mut bytes)?; }
+    }
+
+    // Decode PEM certificates if present, otherwise treat the input as DER
+    let ders = if bytes.windows(27).any(|w| w == b"-----BEGIN CERTIFICATE-----") {
+        read_pem_as_bytes(&bytes[..])?
+    } else {
+        vec![bytes]
+    };
+
+    for der in ders {
+        print_tlv_tree(&TLVNode::parse(&der)?, 0);
+    }
+
+    Ok(())
+}
+
 fn main_args(args: Args) -> Result<(), Error> {
     match args.action {
         Action::Parse(args) => parse_cert_from_stdin(args),
         Action::ParseCTLog(args) => parse_cert_ct_logs(args),
         Action::ValidateCTLog(args) => validate_ct_logs(args),
         Action::DiffResults(args) => diff_ct_log_results(args),
+        Action::Dump(args) => dump_asn1(args),
     }
 }
 